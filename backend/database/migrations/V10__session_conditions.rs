@@ -0,0 +1,17 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("session_conditions", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("session", types::integer());
+        t.add_column("condition_index", types::double());
+        t.add_column("amount_of_drivers", types::integer());
+    });
+
+    m.inject_custom("ALTER TABLE session_conditions ADD FOREIGN KEY (session) REFERENCES session(id) ON DELETE CASCADE;");
+    m.inject_custom("CREATE UNIQUE INDEX session_conditions_session_key ON session_conditions (session);");
+
+    m.make::<Pg>()
+}
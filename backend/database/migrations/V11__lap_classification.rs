@@ -0,0 +1,19 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+
+    // the laps are classified when a heat is saved, existing laps are classified by the classify_laps binary
+    m.change_table("laps", |t| {
+        t.add_column("classification", types::varchar(255).default("clean"));
+    });
+
+    // laps faster than the minimum lap time can not be driven on the track
+    m.change_table("tracks", |t| {
+        t.add_column("min_lap_time", types::double().default(45.0));
+        t.add_column("outlier_threshold", types::double().default(3.5));
+    });
+
+    m.make::<Pg>()
+}
@@ -0,0 +1,22 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("records", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("track", types::integer());
+        t.add_column("category", types::varchar(255));
+        t.add_column("scope", types::varchar(255));
+        t.add_column("lap", types::integer());
+        t.add_column("set_at", types::custom("timestamp without time zone"));
+        t.add_column("broken_at", types::custom("timestamp without time zone").nullable(true));
+    });
+
+    m.inject_custom("ALTER TABLE records ADD FOREIGN KEY (track) REFERENCES tracks(id);");
+    m.inject_custom("ALTER TABLE records ADD FOREIGN KEY (lap) REFERENCES laps(id) ON DELETE CASCADE;");
+    // a record has one holder at a time, the previous holders are kept as history
+    m.inject_custom("CREATE UNIQUE INDEX records_current_key ON records (track, category, scope) WHERE broken_at IS NULL;");
+
+    m.make::<Pg>()
+}
@@ -0,0 +1,19 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+
+    // the uncertainty of a rating grows with the time since the last rated heat
+    m.change_table("driver_ratings", |t| {
+        t.add_column("last_rated", types::custom("timestamp without time zone").nullable(true));
+        t.add_column("amount_of_sessions", types::integer().default(0));
+    });
+
+    m.inject_custom("UPDATE driver_ratings dr SET last_rated = h.last_rated, amount_of_sessions = h.amount_of_sessions \
+                     FROM (SELECT r.driver, r.system, max(s.start_date) AS last_rated, count(*) AS amount_of_sessions \
+                           FROM rating_history r INNER JOIN session s ON s.id = r.session GROUP BY r.driver, r.system) h \
+                     WHERE h.driver = dr.driver AND h.system = dr.system;");
+
+    m.make::<Pg>()
+}
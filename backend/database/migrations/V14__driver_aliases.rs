@@ -0,0 +1,17 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("driver_aliases", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("alias", types::varchar(255).unique(true));
+        t.add_column("driver", types::integer());
+        t.add_column("created_at", types::custom("timestamp without time zone"));
+    });
+
+    m.inject_custom("ALTER TABLE driver_aliases ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;");
+    m.inject_custom("CREATE INDEX driver_aliases_driver ON driver_aliases (driver);");
+
+    m.make::<Pg>()
+}
@@ -0,0 +1,13 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("record_rebuilds", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("created_at", types::custom("timestamp without time zone"));
+        t.add_column("finished_at", types::custom("timestamp without time zone").nullable(true));
+    });
+
+    m.make::<Pg>()
+}
@@ -0,0 +1,16 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("heat_payloads", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("heat_id", types::varchar(255));
+        t.add_column("fetched_at", types::custom("timestamp without time zone"));
+        t.add_column("payload", types::text());
+    });
+
+    m.inject_custom("CREATE INDEX heat_payloads_heat_id_fetched_at ON heat_payloads (heat_id, fetched_at);");
+
+    m.make::<Pg>()
+}
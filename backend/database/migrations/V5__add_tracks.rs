@@ -0,0 +1,26 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("tracks", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("name", types::varchar(255));
+        t.add_column("layout", types::varchar(255));
+    });
+
+    m.inject_custom("CREATE UNIQUE INDEX tracks_name_layout_key ON tracks (name, layout);");
+
+    // all heats before this migration were driven on the same layout
+    m.inject_custom("INSERT INTO tracks (name, layout) VALUES ('KartbaanGroningen', 'LONG BEACH');");
+
+    m.change_table("session", |t| {
+        t.add_column("track", types::integer().default(1));
+    });
+
+    m.inject_custom("ALTER TABLE session ADD FOREIGN KEY (track) REFERENCES tracks(id);");
+    m.inject_custom("ALTER TABLE session ALTER COLUMN track DROP DEFAULT;");
+    m.inject_custom("CREATE INDEX session_track ON session (track);");
+
+    m.make::<Pg>()
+}
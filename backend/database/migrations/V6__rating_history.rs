@@ -0,0 +1,22 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("rating_history", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("driver", types::integer());
+        t.add_column("session", types::integer());
+        t.add_column("rating_before", types::double());
+        t.add_column("uncertainty_before", types::double());
+        t.add_column("rating_after", types::double());
+        t.add_column("uncertainty_after", types::double());
+    });
+
+    // the history goes when the heat or driver it belongs to is deleted
+    m.inject_custom("ALTER TABLE rating_history ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;");
+    m.inject_custom("ALTER TABLE rating_history ADD FOREIGN KEY (session) REFERENCES session(id) ON DELETE CASCADE;");
+    m.inject_custom("CREATE UNIQUE INDEX rating_history_driver_session_key ON rating_history (driver, session);");
+
+    m.make::<Pg>()
+}
@@ -0,0 +1,30 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("driver_ratings", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("driver", types::integer());
+        t.add_column("system", types::varchar(32));
+        t.add_column("rating", types::double());
+        t.add_column("uncertainty", types::double());
+        t.add_column("volatility", types::double().nullable(true));
+    });
+
+    m.inject_custom("ALTER TABLE driver_ratings ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;");
+    m.inject_custom("CREATE UNIQUE INDEX driver_ratings_driver_system_key ON driver_ratings (driver, system);");
+
+    // until now every rating was a weng-lin rating
+    m.inject_custom("INSERT INTO driver_ratings (driver, system, rating, uncertainty) SELECT id, 'weng_lin', rating, uncertainty FROM drivers;");
+
+    m.change_table("rating_history", |t| {
+        t.add_column("system", types::varchar(32).default("weng_lin"));
+    });
+
+    m.inject_custom("ALTER TABLE rating_history ALTER COLUMN system DROP DEFAULT;");
+    m.inject_custom("DROP INDEX rating_history_driver_session_key;");
+    m.inject_custom("CREATE UNIQUE INDEX rating_history_driver_session_system_key ON rating_history (driver, session, system);");
+
+    m.make::<Pg>()
+}
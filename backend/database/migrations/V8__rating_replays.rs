@@ -0,0 +1,23 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+
+    // glicko-2 also needs the volatility to restore a rating from the history
+    m.change_table("rating_history", |t| {
+        t.add_column("volatility_before", types::double().nullable(true));
+        t.add_column("volatility_after", types::double().nullable(true));
+    });
+
+    m.create_table("rating_replays", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("from_date", types::custom("timestamp without time zone").nullable(true));
+        t.add_column("last_start_date", types::custom("timestamp without time zone").nullable(true));
+        t.add_column("last_session", types::integer().nullable(true));
+        t.add_column("created_at", types::custom("timestamp without time zone"));
+        t.add_column("finished_at", types::custom("timestamp without time zone").nullable(true));
+    });
+
+    m.make::<Pg>()
+}
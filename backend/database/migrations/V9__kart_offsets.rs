@@ -0,0 +1,18 @@
+use barrel::{types, Migration, backend::Pg};
+
+
+pub fn migration() -> String {
+    let mut m = Migration::new();
+    m.create_table("kart_offsets", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("kart", types::integer());
+        t.add_column("period", types::custom("timestamp without time zone"));
+        t.add_column("time_offset", types::double());
+        t.add_column("amount_of_laps", types::integer());
+    });
+
+    m.inject_custom("ALTER TABLE kart_offsets ADD FOREIGN KEY (kart) REFERENCES cars(id) ON DELETE CASCADE;");
+    m.inject_custom("CREATE UNIQUE INDEX kart_offsets_kart_period_key ON kart_offsets (kart, period);");
+
+    m.make::<Pg>()
+}
@@ -23,28 +23,42 @@ select * from public.drivers WHERE id = any(:ids::int[]);
 select
     d.*,
     min(l.lap_time) as fastest_lap_time,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as adjusted_fastest_lap_time,
+    min((l.lap_time - coalesce(o.time_offset, 0)) / coalesce(c.condition_index, 1)) as normalized_fastest_lap_time,
     avg(l.lap_time) as avg_lap_time,
     percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as median_lap_time,
     CAST(count(l.lap_time) AS INT) as total_laps,
-    CAST(count(DISTINCT l.heat) AS INT) as total_heats
+    CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+    s.track
 from public.drivers d
          inner join public.laps l on d.id = l.driver
-where d.name like concat('%', :name::text ,'%')
-GROUP BY d.id
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+         left join public.session_conditions c on c.session = s.id
+where d.name like concat('%', :name::text ,'%') and s.track = :track
+  and (not :clean_only::bool or l.classification = 'clean')
+GROUP BY d.id, s.track
 limit :limit offset :offset;
 
 --! get_driver_with_stats : DriverWithStats
 select
     d.*,
     min(l.lap_time) as fastest_lap_time,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as adjusted_fastest_lap_time,
+    min((l.lap_time - coalesce(o.time_offset, 0)) / coalesce(c.condition_index, 1)) as normalized_fastest_lap_time,
     avg(l.lap_time) as avg_lap_time,
     percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as median_lap_time,
     CAST(count(l.lap_time) AS INT) as total_laps,
-    CAST(count(DISTINCT l.heat) AS INT) as total_heats
+    CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+    s.track
 from public.drivers d
          inner join public.laps l on d.id = l.driver
-where d.id = :id::int
-GROUP BY d.id;
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+         left join public.session_conditions c on c.session = s.id
+where d.id = :id::int and s.track = :track
+  and (not :clean_only::bool or l.classification = 'clean')
+GROUP BY d.id, s.track;
 
 --! get_driver_by_name : Driver
 select
@@ -52,6 +66,13 @@ select
 from public.drivers
 WHERE name = :name::text;
 
+--! get_driver_by_alias : Driver
+select
+    d.*
+from public.drivers d
+         inner join public.driver_aliases a on a.driver = d.id
+WHERE a.alias = :alias::text;
+
 --! search_driver_by_name : Driver
 select
     *
@@ -59,17 +80,6 @@ from public.drivers
 WHERE name LIKE concat('%', :name::text, '%')
 LIMIT :limit OFFSET :offset;
 
---! get_drivers_from_heat_in_order_fastest_lap
-select
-    d.*
-from public.drivers d
-inner join public.laps l on d.id = l.driver
-inner join public.session h on h.id = l.heat
-where h.id = :heat
-group by d.id
-order by min(l.lap_time) asc;
-
-
 --! update_driver_rating
 with update_rows AS (
     UPDATE
@@ -83,3 +93,88 @@ with update_rows AS (
 
 --! create_driver : Driver
 INSERT INTO public.drivers (name, rating, uncertainty) values (:name, :rating, :uncertainty) RETURNING *;
+
+--! create_driver_if_not_exists : Driver
+INSERT INTO public.drivers (name, rating, uncertainty) values (:name, :rating, :uncertainty)
+on conflict (name) do nothing
+RETURNING *;
+
+--! reset_driver_ratings
+with update_rows AS (
+    UPDATE
+        public.drivers
+    set
+        rating=:rating,
+        uncertainty=:uncertainty
+    RETURNING 1
+) SELECT count(*) FROM update_rows;
+
+--! set_driver_ratings_from_system
+update public.drivers d
+set
+    rating = r.rating,
+    uncertainty = r.uncertainty
+from public.driver_ratings r
+where r.driver = d.id and r.system = :system::text;
+
+--! delete_drivers_without_laps
+delete from public.drivers d where not exists (select 1 from public.laps l where l.driver = d.id);
+
+--! delete_driver
+delete from public.drivers where id = :id;
+
+--: DriverHeat()
+--! get_heats_of_drivers : DriverHeat
+select distinct driver, heat from public.laps;
+
+--! count_shared_heats
+select count(distinct a.heat)
+from public.laps a
+         inner join public.laps b on b.heat = a.heat
+where a.driver = :driver and b.driver = :other;
+
+--: LeaderboardEntry(rating?)
+--! get_leaderboard : LeaderboardEntry
+with entries as (
+    select
+        d.id as driver,
+        d.name as driver_name,
+        min(l.lap_time) filter (where l.classification = 'clean') as fastest_lap_time,
+        min(l.lap_time - coalesce(o.time_offset, 0)) filter (where l.classification = 'clean') as adjusted_fastest_lap_time,
+        (
+            select r.rating_after
+            from public.rating_history r
+                     inner join public.session rs on rs.id = r.session
+            where r.driver = d.id and r.system = :system::text and rs.track = :track
+              and rs.start_date >= :from_date and rs.start_date < :to_date
+            order by rs.start_date desc
+            limit 1
+        ) as rating,
+        CAST(count(l.id) AS INT) as total_laps,
+        CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+        coalesce(dr.amount_of_sessions, 0) < :provisional_sessions as provisional
+    from public.drivers d
+             inner join public.laps l on d.id = l.driver
+             inner join public.session s on s.id = l.heat
+             left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+             left join public.driver_ratings dr on dr.driver = d.id and dr.system = :system::text
+    where s.track = :track and s.start_date >= :from_date and s.start_date < :to_date
+    group by d.id, dr.amount_of_sessions
+    having count(l.id) filter (where l.classification = 'clean') >= :min_laps
+), ranked as (
+    select
+        e.*,
+        CAST(rank() over (order by case :metric::text
+            when 'adjusted_fastest_lap' then e.adjusted_fastest_lap_time
+            when 'rating' then -e.rating
+            when 'laps_driven' then -e.total_laps
+            else e.fastest_lap_time
+        end) AS INT) as position,
+        CAST(count(*) over () AS INT) as total_entries
+    from entries e
+    where (:metric::text <> 'rating' or e.rating is not null)
+      and (:include_provisional::bool or not e.provisional)
+)
+select * from ranked
+order by position, driver_name
+limit :limit offset :offset;
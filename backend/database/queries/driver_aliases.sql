@@ -0,0 +1,27 @@
+--
+-- CLASSES
+--
+
+--: DriverAlias()
+
+
+--
+-- GETTERS
+--
+
+--! get_aliases_of_driver : DriverAlias
+select * from public.driver_aliases where driver = :driver order by created_at;
+
+
+--
+-- SETTERS
+--
+
+--! insert_driver_alias : DriverAlias
+insert into public.driver_aliases (alias, driver, created_at)
+values (:alias, :driver, now() at time zone 'utc')
+on conflict (alias) do update set driver = excluded.driver
+returning *;
+
+--! move_driver_aliases
+update public.driver_aliases set driver = :keep where driver = :merged;
@@ -0,0 +1,55 @@
+--
+-- CLASSES
+--
+
+--: DriverRating(volatility?, last_rated?)
+
+
+--
+-- GETTERS
+--
+
+--! get_ratings_of_driver : DriverRating
+select * from public.driver_ratings where driver = :driver order by system;
+
+--! get_ratings_of_drivers_in_system : DriverRating
+select * from public.driver_ratings where system = :system::text and driver = any(:drivers::int[]);
+
+
+--
+-- SETTERS
+--
+
+--! set_driver_rating (volatility?) : DriverRating
+insert into public.driver_ratings (driver, system, rating, uncertainty, volatility, last_rated, amount_of_sessions)
+values (:driver, :system, :rating, :uncertainty, :volatility, :last_rated, 1)
+on conflict (driver, system) do update set
+    rating = excluded.rating,
+    uncertainty = excluded.uncertainty,
+    volatility = excluded.volatility,
+    last_rated = excluded.last_rated,
+    amount_of_sessions = public.driver_ratings.amount_of_sessions + 1
+returning *;
+
+--! delete_all_driver_ratings
+delete from public.driver_ratings;
+
+--! restore_driver_ratings_from_history
+insert into public.driver_ratings (driver, system, rating, uncertainty, volatility, last_rated, amount_of_sessions)
+select distinct on (r.driver, r.system)
+    r.driver,
+    r.system,
+    r.rating_after,
+    r.uncertainty_after,
+    r.volatility_after,
+    s.start_date,
+    CAST(count(*) over (partition by r.driver, r.system) AS INT)
+from public.rating_history r
+         inner join public.session s on s.id = r.session
+order by r.driver, r.system, s.start_date desc, s.id desc
+on conflict (driver, system) do update set
+    rating = excluded.rating,
+    uncertainty = excluded.uncertainty,
+    volatility = excluded.volatility,
+    last_rated = excluded.last_rated,
+    amount_of_sessions = excluded.amount_of_sessions;
@@ -0,0 +1,17 @@
+--: HeatPayload()
+
+--! insert_heat_payload_if_changed : HeatPayload
+insert into public.heat_payloads (heat_id, fetched_at, payload)
+select :heat_id::varchar, :fetched_at::timestamp, :payload::text
+where not exists (
+    select 1
+    from (select payload from public.heat_payloads where heat_id = :heat_id order by fetched_at desc limit 1) latest
+    where latest.payload = :payload
+)
+returning *;
+
+--! get_latest_heat_payload : HeatPayload
+select * from public.heat_payloads where heat_id = :heat_id order by fetched_at desc limit 1;
+
+--! get_latest_heat_payloads : HeatPayload
+select distinct on (heat_id) * from public.heat_payloads order by heat_id, fetched_at desc;
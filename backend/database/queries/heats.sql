@@ -1,6 +1,6 @@
 
 
---: Heat(id, heat_id, heat_type, start_date)
+--: Heat(id, heat_id, heat_type, start_date, track)
 --: GetHeatWithStats()
 
 --! get_all_heats : Heat
@@ -15,30 +15,36 @@ select * from public.session WHERE id = any(:ids::int[]);
 --! get_heat_from_name: Heat
 select * from public.session where heat_id = :name::text;
 
---! get_all_heats_with_stats : GetHeatWithStats
+--! get_all_heats_with_stats (track?) : GetHeatWithStats
 select
     h.heat_id,
     h.heat_type,
     h.start_date as start_time,
+    h.track,
     CAST(count(l.*) as INT) as amount_of_laps,
     CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
     min(l.lap_time) as fastest_lap_time,
     avg(l.lap_time) as average_lap_time
 from public.session h
          inner join public.laps l on h.id = l.heat
+where (:track::int is null or h.track = :track)
+  and (not :clean_only::bool or l.classification = 'clean')
 group by h.id;
 
---! get_heat_with_stats_paginated : GetHeatWithStats
+--! get_heat_with_stats_paginated (track?) : GetHeatWithStats
 select
     h.heat_id,
     h.heat_type,
     h.start_date as start_time,
+    h.track,
     CAST(count(l.*) as INT) as amount_of_laps,
     CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
     min(l.lap_time) as fastest_lap_time,
     avg(l.lap_time) as average_lap_time
 from public.session h
          inner join public.laps l on h.id = l.heat
+where (:track::int is null or h.track = :track)
+  and (not :clean_only::bool or l.classification = 'clean')
 group by h.id, start_time
 limit :limit
 offset :offset;
@@ -48,6 +54,7 @@ select
     h.heat_id,
     h.heat_type,
     h.start_date as start_time,
+    h.track,
     CAST(count(l.*) as INT) as amount_of_laps,
     CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
     min(l.lap_time) as fastest_lap_time,
@@ -55,14 +62,36 @@ select
 from public.session h
          inner join public.laps l on h.id = l.heat
 where h.heat_id = :heat
+  and (not :clean_only::bool or l.classification = 'clean')
 group by h.id;
 
 --! get_all_chronologicaly : Heat
-select * from public.session order by start_date;
+select * from public.session order by start_date, id;
+
+--! get_next_heat_to_replay : Heat
+select
+    s.*
+from public.session s, public.rating_replays r
+where r.id = :replay
+  and ((r.last_session is null and (r.from_date is null or s.start_date >= r.from_date))
+    or (s.start_date, s.id) > (r.last_start_date, r.last_session))
+order by s.start_date, s.id
+limit 1;
+
+--! count_heats_driven_after
+select
+    count(*)
+from public.session
+where start_date > (select start_date from public.session where id = :id);
 
 
 --! delete_heat
 delete from public.session where id = :id returning *;
 
 --! create_new_heat: Heat
-insert into public.session (heat_id, heat_type, start_date) VALUES (:heat_id, :heat_type, :start_date) returning  *;
\ No newline at end of file
+insert into public.session (heat_id, heat_type, start_date, track) VALUES (:heat_id, :heat_type, :start_date, :track) returning  *;
+
+--! create_heat_if_not_exists: Heat
+insert into public.session (heat_id, heat_type, start_date, track) VALUES (:heat_id, :heat_type, :start_date, :track)
+on conflict (heat_id) do nothing
+returning *;
\ No newline at end of file
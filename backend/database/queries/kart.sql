@@ -49,28 +49,40 @@ select
 from public.cars k
          inner join public.laps l on k.id = l.kart_id
          inner join public.session h on h.id = l.heat
+where h.track = :track
+  and (not :clean_only::bool or l.classification = 'clean')
 group by k.id, k.number, h.start_date;
 
 
---! get_kart_with_stats : KartWithStats
+--! get_kart_with_stats (track?) : KartWithStats
 select
     k.*,
     CAST(count(l.id) AS INT) as lap_count,
     CAST(count(DISTINCT l.driver) AS INT) as driver_count
 from public.cars k
 inner join public.laps l on k.id = l.kart_id
-where k.number = :kart_number
+inner join public.session h on h.id = l.heat
+where k.number = :kart_number and (:track::int is null or h.track = :track)
+  and (not :clean_only::bool or l.classification = 'clean')
 group by k.id;
 
---! get_all_karts_with_stats : KartWithStats
+--! get_all_karts_with_stats (track?) : KartWithStats
 select
     k.*,
     CAST(count(l.id) AS INT) as lap_count,
     CAST(count(DISTINCT l.driver) AS INT) as driver_count
 from public.cars k
          inner join public.laps l on k.id = l.kart_id
+         inner join public.session h on h.id = l.heat
+where (:track::int is null or h.track = :track)
+  and (not :clean_only::bool or l.classification = 'clean')
 group by k.id
 order by :order_column;
 
 --! create_kart
 INSERT INTO public.cars (number, brand, model, horsepower, modified) values (:number, :brand, :model, :horsepower, :modified) RETURNING id;
+
+--! create_kart_if_not_exists : Kart
+INSERT INTO public.cars (number, brand, model, horsepower, modified) values (:number, :brand, :model, :horsepower, :modified)
+on conflict (number) do nothing
+RETURNING *;
@@ -0,0 +1,44 @@
+--
+-- CLASSES
+--
+
+--: KartOffset()
+--: KartObservation()
+
+
+--
+-- GETTERS
+--
+
+--! get_kart_offsets_of_karts : KartOffset
+select * from public.kart_offsets where kart = any(:karts::int[]);
+
+--! get_kart_observations : KartObservation
+select
+    l.heat,
+    l.driver,
+    l.kart_id as kart,
+    date_trunc('month', s.start_date) as period,
+    percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as lap_time,
+    CAST(count(*) AS INT) as amount_of_laps
+from public.laps l
+         inner join public.session s on s.id = l.heat
+where l.classification = 'clean'
+group by l.heat, l.driver, l.kart_id, s.start_date;
+
+
+--
+-- SETTERS
+--
+
+--! insert_kart_offsets_bulk : KartOffset
+insert into public.kart_offsets (kart, period, time_offset, amount_of_laps)
+select
+    unnest(:karts::int[]),
+    unnest(:periods::timestamp[]),
+    unnest(:time_offsets::float[]),
+    unnest(:amounts_of_laps::int[])
+returning *;
+
+--! delete_all_kart_offsets
+delete from public.kart_offsets;
@@ -21,10 +21,19 @@ select * from public.laps where heat = :heat;
 select * from public.laps where heat = any(:heats::int[]);
 
 
+--! update_laps_classification : Lap
+update public.laps set classification = :classification where id = any(:ids::int[])
+returning *;
+
+
 --! delete_laps_from_heat
 delete from public.laps where heat = :heat;
 
 
+--! move_laps_to_driver
+update public.laps set driver = :keep where driver = :merged;
+
+
 --! insert_laps_bulk: Lap
 INSERT INTO
     public.laps (heat, driver, lap_in_heat, lap_time, kart_id)
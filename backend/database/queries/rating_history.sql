@@ -0,0 +1,52 @@
+--
+-- CLASSES
+--
+
+--: RatingHistory(volatility_before?, volatility_after?)
+--: DriverRatingChange()
+
+
+--
+-- GETTERS
+--
+
+--! get_rating_history_of_driver : DriverRatingChange
+select
+    s.heat_id,
+    s.start_date,
+    r.rating_before,
+    r.uncertainty_before,
+    r.rating_after,
+    r.uncertainty_after
+from public.rating_history r
+         inner join public.session s on s.id = r.session
+where r.driver = :driver and r.system = :system::text
+order by s.start_date;
+
+--! get_rating_history_of_system : RatingHistory
+select * from public.rating_history where system = :system::text;
+
+
+--
+-- SETTERS
+--
+
+--! insert_rating_history (volatility_before?, volatility_after?) : RatingHistory
+insert into public.rating_history (driver, session, system, rating_before, uncertainty_before, volatility_before, rating_after, uncertainty_after, volatility_after)
+values (:driver, :session, :system, :rating_before, :uncertainty_before, :volatility_before, :rating_after, :uncertainty_after, :volatility_after)
+on conflict (driver, session, system) do update set
+    rating_before = excluded.rating_before,
+    uncertainty_before = excluded.uncertainty_before,
+    volatility_before = excluded.volatility_before,
+    rating_after = excluded.rating_after,
+    uncertainty_after = excluded.uncertainty_after,
+    volatility_after = excluded.volatility_after
+returning *;
+
+--! delete_rating_history_from (from_date?)
+with deleted_rows as (
+    delete from public.rating_history r
+    using public.session s
+    where s.id = r.session and (:from_date::timestamp is null or s.start_date >= :from_date)
+    returning 1
+) select count(*) from deleted_rows;
@@ -0,0 +1,41 @@
+--
+-- CLASSES
+--
+
+--: RatingReplay(from_date?, last_start_date?, last_session?, finished_at?)
+
+
+--
+-- GETTERS
+--
+
+--! get_unfinished_rating_replay : RatingReplay
+select * from public.rating_replays where finished_at is null order by id limit 1 for update;
+
+
+--
+-- SETTERS
+--
+
+--! create_rating_replay (from_date?) : RatingReplay
+insert into public.rating_replays (from_date, created_at) values (:from_date, now()) returning *;
+
+--! restart_rating_replay (from_date?) : RatingReplay
+update public.rating_replays
+set
+    from_date = :from_date,
+    last_start_date = null,
+    last_session = null
+where id = :id
+returning *;
+
+--! set_rating_replay_progress : RatingReplay
+update public.rating_replays
+set
+    last_start_date = (select start_date from public.session where id = :session),
+    last_session = :session
+where id = :id
+returning *;
+
+--! finish_rating_replay : RatingReplay
+update public.rating_replays set finished_at = now() where id = :id returning *;
@@ -0,0 +1,92 @@
+--
+-- CLASSES
+--
+
+--: Record(broken_at?)
+--: RecordCandidate()
+
+
+--
+-- GETTERS
+--
+
+--! get_record_candidates : RecordCandidate
+select
+    l.id as lap,
+    l.driver,
+    d.name as driver_name,
+    k.number as kart,
+    s.heat_id,
+    s.heat_type,
+    s.start_date,
+    l.lap_time
+from public.laps l
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where l.heat = :heat and l.classification = 'clean';
+
+--! count_scheduled_record_rebuilds
+select count(*) from public.record_rebuilds where finished_at is null;
+
+--! get_current_record : Record
+select
+    r.id,
+    r.track,
+    r.category,
+    r.scope,
+    d.name as driver_name,
+    s.heat_id,
+    k.number as kart,
+    l.lap_time,
+    r.set_at,
+    r.broken_at
+from public.records r
+         inner join public.laps l on l.id = r.lap
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where r.track = :track and r.category = :category and r.scope = :scope and r.broken_at is null;
+
+--! get_records_of_track : Record
+select
+    r.id,
+    r.track,
+    r.category,
+    r.scope,
+    d.name as driver_name,
+    s.heat_id,
+    k.number as kart,
+    l.lap_time,
+    r.set_at,
+    r.broken_at
+from public.records r
+         inner join public.laps l on l.id = r.lap
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where r.track = :track
+order by r.category, r.scope, r.set_at desc, r.id desc;
+
+
+--
+-- SETTERS
+--
+
+--! insert_record
+insert into public.records (track, category, scope, lap, set_at)
+values (:track, :category, :scope, :lap, :set_at)
+returning id;
+
+--! break_record
+update public.records set broken_at = :broken_at where id = :id
+returning id;
+
+--! delete_all_records
+delete from public.records;
+
+--! schedule_record_rebuild
+insert into public.record_rebuilds (created_at) values (now());
+
+--! finish_record_rebuilds
+update public.record_rebuilds set finished_at = now() where finished_at is null;
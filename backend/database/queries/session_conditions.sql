@@ -0,0 +1,54 @@
+--
+-- CLASSES
+--
+
+--: SessionCondition()
+--: ConditionObservation()
+--: SessionConditionOfTrack()
+
+
+--
+-- GETTERS
+--
+
+--! get_condition_observations : ConditionObservation
+select
+    s.id as session,
+    s.track,
+    l.driver,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as best_lap_time
+from public.laps l
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+where l.classification = 'clean'
+group by s.id, l.driver;
+
+--! get_session_conditions_of_sessions : SessionCondition
+select * from public.session_conditions where session = any(:sessions::int[]);
+
+--! get_session_conditions_of_track : SessionConditionOfTrack
+select
+    s.heat_id,
+    s.start_date,
+    c.condition_index,
+    c.amount_of_drivers
+from public.session_conditions c
+         inner join public.session s on s.id = c.session
+where s.track = :track
+order by s.start_date;
+
+
+--
+-- SETTERS
+--
+
+--! insert_session_conditions_bulk : SessionCondition
+insert into public.session_conditions (session, condition_index, amount_of_drivers)
+select
+    unnest(:sessions::int[]),
+    unnest(:condition_indices::float[]),
+    unnest(:amounts_of_drivers::int[])
+returning *;
+
+--! delete_all_session_conditions
+delete from public.session_conditions;
@@ -0,0 +1,31 @@
+--
+-- CLASSES
+--
+
+--: Track()
+
+
+--
+-- GETTERS
+--
+
+--! get_all_tracks : Track
+select * from public.tracks order by id;
+
+--! get_track_by_id : Track
+select * from public.tracks where id = :id;
+
+
+--
+-- SETTERS
+--
+
+--! ensure_track : Track
+insert into public.tracks (name, layout) values (:name, :layout)
+on conflict (name, layout) do update set name = excluded.name
+returning *;
+
+--! set_track_lap_thresholds : Track
+update public.tracks set min_lap_time = :min_lap_time, outlier_threshold = :outlier_threshold
+where id = :id
+returning *;
@@ -10,7 +10,15 @@ CREATE TABLE public.session (
                                 id integer NOT NULL,
                                 heat_id character varying NOT NULL,
                                 heat_type character varying NOT NULL,
-                                start_date timestamp without time zone NOT NULL
+                                start_date timestamp without time zone NOT NULL,
+                                track integer NOT NULL
+);
+CREATE TABLE public.tracks (
+                               id integer NOT NULL,
+                               name character varying NOT NULL,
+                               layout character varying NOT NULL,
+                               min_lap_time double precision DEFAULT 45.0 NOT NULL,
+                               outlier_threshold double precision DEFAULT 3.5 NOT NULL
 );
 CREATE TABLE public.drivers (
                                 id integer NOT NULL,
@@ -24,7 +32,77 @@ CREATE TABLE public.laps (
                              driver integer NOT NULL,
                              lap_in_heat integer NOT NULL,
                              lap_time double precision NOT NULL,
-                             kart_id integer NOT NULL
+                             kart_id integer NOT NULL,
+                             classification character varying DEFAULT 'clean' NOT NULL
+);
+CREATE TABLE public.heat_payloads (
+                                      id integer NOT NULL,
+                                      heat_id character varying NOT NULL,
+                                      fetched_at timestamp without time zone NOT NULL,
+                                      payload text NOT NULL
+);
+CREATE TABLE public.rating_history (
+                                       id integer NOT NULL,
+                                       driver integer NOT NULL,
+                                       session integer NOT NULL,
+                                       rating_before double precision NOT NULL,
+                                       uncertainty_before double precision NOT NULL,
+                                       rating_after double precision NOT NULL,
+                                       uncertainty_after double precision NOT NULL,
+                                       system character varying NOT NULL,
+                                       volatility_before double precision NULL,
+                                       volatility_after double precision NULL
+);
+CREATE TABLE public.driver_ratings (
+                                       id integer NOT NULL,
+                                       driver integer NOT NULL,
+                                       system character varying NOT NULL,
+                                       rating double precision NOT NULL,
+                                       uncertainty double precision NOT NULL,
+                                       volatility double precision NULL,
+                                       last_rated timestamp without time zone NULL,
+                                       amount_of_sessions integer DEFAULT 0 NOT NULL
+);
+CREATE TABLE public.rating_replays (
+                                       id integer NOT NULL,
+                                       from_date timestamp without time zone NULL,
+                                       last_start_date timestamp without time zone NULL,
+                                       last_session integer NULL,
+                                       created_at timestamp without time zone NOT NULL,
+                                       finished_at timestamp without time zone NULL
+);
+CREATE TABLE public.kart_offsets (
+                                       id integer NOT NULL,
+                                       kart integer NOT NULL,
+                                       period timestamp without time zone NOT NULL,
+                                       time_offset double precision NOT NULL,
+                                       amount_of_laps integer NOT NULL
+);
+CREATE TABLE public.session_conditions (
+                                       id integer NOT NULL,
+                                       session integer NOT NULL,
+                                       condition_index double precision NOT NULL,
+                                       amount_of_drivers integer NOT NULL
+);
+CREATE TABLE public.records (
+                                id integer NOT NULL,
+                                track integer NOT NULL,
+                                category character varying NOT NULL,
+                                scope character varying NOT NULL,
+                                lap integer NOT NULL,
+                                set_at timestamp without time zone NOT NULL,
+                                broken_at timestamp without time zone NULL
+);
+CREATE TABLE public.driver_aliases (
+                                       id integer NOT NULL,
+                                       alias character varying NOT NULL,
+                                       driver integer NOT NULL,
+                                       created_at timestamp without time zone NOT NULL
+);
+CREATE TABLE public.record_rebuilds (
+                                       id integer NOT NULL,
+                                       created_at timestamp without time zone NOT NULL,
+                                       finished_at timestamp without time zone NULL
 );
 CREATE TABLE public.refinery_schema_history (
                                                 version integer NOT NULL,
@@ -40,6 +118,26 @@ CREATE UNIQUE INDEX drivers_name_key ON public.drivers USING btree (name);
 CREATE UNIQUE INDEX heats_heat_id_key ON public.session USING btree (heat_id);
 CREATE UNIQUE INDEX refinery_schema_history_pkey ON public.refinery_schema_history USING btree (version);
 CREATE UNIQUE INDEX laps_pkey ON public.laps USING btree (id);
+CREATE UNIQUE INDEX heat_payloads_pkey ON public.heat_payloads USING btree (id);
+CREATE INDEX heat_payloads_heat_id_fetched_at ON public.heat_payloads USING btree (heat_id, fetched_at);
+CREATE UNIQUE INDEX tracks_pkey ON public.tracks USING btree (id);
+CREATE UNIQUE INDEX tracks_name_layout_key ON public.tracks USING btree (name, layout);
+CREATE INDEX session_track ON public.session USING btree (track);
+CREATE UNIQUE INDEX rating_history_pkey ON public.rating_history USING btree (id);
+CREATE UNIQUE INDEX rating_history_driver_session_system_key ON public.rating_history USING btree (driver, session, system);
+CREATE UNIQUE INDEX driver_ratings_pkey ON public.driver_ratings USING btree (id);
+CREATE UNIQUE INDEX driver_ratings_driver_system_key ON public.driver_ratings USING btree (driver, system);
+CREATE UNIQUE INDEX rating_replays_pkey ON public.rating_replays USING btree (id);
+CREATE UNIQUE INDEX kart_offsets_pkey ON public.kart_offsets USING btree (id);
+CREATE UNIQUE INDEX kart_offsets_kart_period_key ON public.kart_offsets USING btree (kart, period);
+CREATE UNIQUE INDEX session_conditions_pkey ON public.session_conditions USING btree (id);
+CREATE UNIQUE INDEX session_conditions_session_key ON public.session_conditions USING btree (session);
+CREATE UNIQUE INDEX records_pkey ON public.records USING btree (id);
+CREATE UNIQUE INDEX records_current_key ON public.records USING btree (track, category, scope) WHERE broken_at IS NULL;
+CREATE UNIQUE INDEX driver_aliases_pkey ON public.driver_aliases USING btree (id);
+CREATE UNIQUE INDEX driver_aliases_alias_key ON public.driver_aliases USING btree (alias);
+CREATE INDEX driver_aliases_driver ON public.driver_aliases USING btree (driver);
+CREATE UNIQUE INDEX record_rebuilds_pkey ON public.record_rebuilds USING btree (id);
 ALTER TABLE session ADD CONSTRAINT heats_heat_id_key_ UNIQUE (heat_id);
 ALTER TABLE cars ADD CONSTRAINT karts_number_key_ UNIQUE (number);
 ALTER TABLE laps ADD FOREIGN KEY (kart_id) REFERENCES cars(id);
@@ -51,3 +149,22 @@ ALTER TABLE drivers ADD PRIMARY KEY (id);
 ALTER TABLE refinery_schema_history ADD PRIMARY KEY (version);
 ALTER TABLE laps ADD FOREIGN KEY (heat) REFERENCES session(id);
 ALTER TABLE cars ADD PRIMARY KEY (id);
+ALTER TABLE heat_payloads ADD PRIMARY KEY (id);
+ALTER TABLE tracks ADD PRIMARY KEY (id);
+ALTER TABLE session ADD FOREIGN KEY (track) REFERENCES tracks(id);
+ALTER TABLE rating_history ADD PRIMARY KEY (id);
+ALTER TABLE rating_history ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;
+ALTER TABLE rating_history ADD FOREIGN KEY (session) REFERENCES session(id) ON DELETE CASCADE;
+ALTER TABLE driver_ratings ADD PRIMARY KEY (id);
+ALTER TABLE driver_ratings ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;
+ALTER TABLE rating_replays ADD PRIMARY KEY (id);
+ALTER TABLE kart_offsets ADD PRIMARY KEY (id);
+ALTER TABLE kart_offsets ADD FOREIGN KEY (kart) REFERENCES cars(id) ON DELETE CASCADE;
+ALTER TABLE session_conditions ADD PRIMARY KEY (id);
+ALTER TABLE session_conditions ADD FOREIGN KEY (session) REFERENCES session(id) ON DELETE CASCADE;
+ALTER TABLE records ADD PRIMARY KEY (id);
+ALTER TABLE records ADD FOREIGN KEY (track) REFERENCES tracks(id);
+ALTER TABLE records ADD FOREIGN KEY (lap) REFERENCES laps(id) ON DELETE CASCADE;
+ALTER TABLE driver_aliases ADD PRIMARY KEY (id);
+ALTER TABLE driver_aliases ADD FOREIGN KEY (driver) REFERENCES drivers(id) ON DELETE CASCADE;
+ALTER TABLE record_rebuilds ADD PRIMARY KEY (id);
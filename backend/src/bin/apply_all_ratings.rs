@@ -1,14 +1,41 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use karting_groningen_analytics::modules::helpers::logging::setup_logging;
-use karting_groningen_analytics::modules::database::models::session::Session;
-use log::info;
+use karting_groningen_analytics::modules::database::models::general::{get_pool, setup_pool};
+use karting_groningen_analytics::modules::database::models::rating_replay::RatingReplay;
+use log::{error, info};
 
+/// recompute the ratings of the drivers by replaying the heats in the order they were driven.
+///
+/// * `apply_all_ratings` - continue the unfinished replay, or replay all heats when there is none
+/// * `apply_all_ratings --full` - replay all heats
+/// * `apply_all_ratings <date>` - replay the heats driven on or after the date (`YYYY-MM-DD`)
 #[tokio::main]
 pub async fn main() {
     setup_logging().expect("Error setting up logging");
+    setup_pool().await;
 
-    let heats = Session::get_all_chronologicaly().await;
-    for heat in heats {
-        info!(target:"apply_ratings", "applying ratings of heat: {} ", heat.heat_id);
-        heat.apply_ratings().await;
+    let client = get_pool().get().await.unwrap();
+    match std::env::args().nth(1).as_deref() {
+        None => {
+            if RatingReplay::get_unfinished(&client).await.unwrap().is_none() {
+                RatingReplay::schedule(&client, None).await.unwrap();
+            }
+        }
+        Some("--full") => {
+            RatingReplay::schedule(&client, None).await.unwrap();
+        }
+        Some(date) => {
+            let from: NaiveDateTime = match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(date) => date.and_hms_opt(0, 0, 0).unwrap(),
+                Err(_) => {
+                    error!(target:"apply_ratings", "invalid date: {}, expected YYYY-MM-DD", date);
+                    return;
+                }
+            };
+            RatingReplay::schedule(&client, Some(from)).await.unwrap();
+        }
     }
+
+    let rated = RatingReplay::run().await.unwrap();
+    info!(target:"apply_ratings", "rated {} heats", rated);
 }
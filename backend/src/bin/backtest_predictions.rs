@@ -0,0 +1,25 @@
+use std::env;
+
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use karting_groningen_analytics::modules::predictions::BacktestReport;
+use karting_groningen_analytics::modules::rating_systems::{get_main_rating_system, get_rating_system};
+use log::info;
+
+/// test how well the ratings predicted the heats that were driven.
+/// the rating system can be given as argument, the main rating system is used otherwise.
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let system = match env::args().nth(1) {
+        Some(name) => get_rating_system(&name).expect("unknown rating system"),
+        None => get_main_rating_system(),
+    };
+
+    let report = BacktestReport::new(system).await.unwrap();
+    info!(target:"backtest_predictions", "tested {} heats, {:.1}% of the pairs and {:.1}% of the winners predicted correctly",
+        report.amount_of_heats, report.pairwise_accuracy * 100.0, report.winner_accuracy * 100.0);
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+}
@@ -0,0 +1,72 @@
+use karting_groningen_analytics::modules::cache_tags::CacheTag;
+use karting_groningen_analytics::modules::database::models::driver::Driver;
+use karting_groningen_analytics::modules::database::models::general::{get_pool, setup_pool};
+use karting_groningen_analytics::modules::database::models::kart_offset::KartOffset;
+use karting_groningen_analytics::modules::database::models::lap::Lap;
+use karting_groningen_analytics::modules::database::models::record::Record;
+use karting_groningen_analytics::modules::database::models::session::Session;
+use karting_groningen_analytics::modules::database::models::session_condition::SessionCondition;
+use karting_groningen_analytics::modules::database::models::track::Track;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::{error, info};
+
+/// classify the laps of all heats again, for example after changing the thresholds of a track.
+/// the records, kart offsets and track conditions only use clean laps, so they are redone after.
+///
+/// * `classify_laps` - classify the laps of all heats
+/// * `classify_laps <track> <min lap time> <outlier threshold>` - set the thresholds of the track
+///   and classify the laps of its heats
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let track = match args.as_slice() {
+        [] => None,
+        [track, min_lap_time, outlier_threshold] => {
+            let (Ok(track), Ok(min_lap_time), Ok(outlier_threshold)) = (track.parse::<i32>(), min_lap_time.parse::<f64>(), outlier_threshold.parse::<f64>()) else {
+                error!(target:"classify_laps", "invalid arguments, expected <track> <min lap time> <outlier threshold>");
+                return;
+            };
+
+            let track = Track::get_by_id(track).await.unwrap();
+            Some(track.set_lap_thresholds(min_lap_time, outlier_threshold).await.unwrap())
+        }
+        _ => {
+            error!(target:"classify_laps", "invalid arguments, expected <track> <min lap time> <outlier threshold>");
+            return;
+        }
+    };
+
+    let heats: Vec<Session> = Session::get_all().await
+        .unwrap()
+        .into_iter()
+        .filter(|heat| track.as_ref().is_none_or(|track| heat.track == track.id))
+        .collect();
+
+    let mut client = get_pool().get().await.unwrap();
+    for heat in &heats {
+        let transaction = client.transaction().await.unwrap();
+        Lap::classify_heat(&transaction, heat).await.unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    info!(target:"classify_laps", "classified the laps of {} heats", heats.len());
+
+    let records = Record::rebuild_all().await.unwrap();
+    info!(target:"classify_laps", "rebuilt {} records", records);
+
+    let offsets = KartOffset::fit_all().await.unwrap();
+    info!(target:"classify_laps", "stored {} kart offsets", offsets);
+
+    let conditions = SessionCondition::compute_all().await.unwrap();
+    info!(target:"classify_laps", "stored the conditions of {} heats", conditions);
+
+    // the pages of the heats and their drivers show the classifications
+    let mut tags = vec![CacheTag::Drivers, CacheTag::Heats];
+    tags.extend(CacheTag::of_heats(&heats));
+    tags.extend(CacheTag::of_drivers(&Driver::get_all().await.unwrap()));
+    tags.extend(CacheTag::of_tracks(&Track::get_all().await.unwrap()));
+    CacheTag::invalidate(&tags).await;
+}
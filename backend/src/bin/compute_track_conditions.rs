@@ -0,0 +1,14 @@
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::database::models::session_condition::SessionCondition;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::info;
+
+/// estimate how fast the track was during every heat compared to usual
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let amount = SessionCondition::compute_all().await.unwrap();
+    info!(target:"compute_track_conditions", "stored the conditions of {} heats", amount);
+}
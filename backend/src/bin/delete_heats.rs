@@ -1,14 +1,26 @@
 use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
 use log::info;
+use karting_groningen_analytics::modules::database::models::rating_replay::RatingReplay;
+use karting_groningen_analytics::modules::database::models::record::Record;
 use karting_groningen_analytics::modules::database::models::session::Session;
 
 #[tokio::main]
 async fn main() {
     setup_logging().expect("Error setting up logging");
+    setup_pool().await;
 
     let heats = [];
     for heat_id in heats {
         let heat = Session::delete_id(heat_id).await;
         info!(target:"delete_heat", "Deleted heat: {:?}", heat);
     }
+
+    // undo the ratings of the deleted heats
+    let rated = RatingReplay::run().await.unwrap();
+    info!(target:"delete_heat", "replayed the ratings of {} heats", rated);
+
+    // records set in the deleted heats are gone, so the records they broke have to stand again
+    let records = Record::rebuild_all().await.unwrap();
+    info!(target:"delete_heat", "rebuilt {} records", records);
 }
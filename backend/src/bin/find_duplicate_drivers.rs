@@ -0,0 +1,23 @@
+use std::env;
+
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::duplicate_drivers::PossibleDuplicate;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::info;
+
+/// list drivers that might be the same person, to be merged with `merge_drivers`.
+/// the amount of characters the names may differ in can be given as argument, it is 2 otherwise.
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let max_distance = match env::args().nth(1) {
+        Some(distance) => distance.parse().expect("the maximum distance has to be a number"),
+        None => 2,
+    };
+
+    let duplicates = PossibleDuplicate::find(max_distance).await.unwrap();
+    info!(target:"find_duplicate_drivers", "found {} possible duplicates", duplicates.len());
+    println!("{}", serde_json::to_string_pretty(&duplicates).unwrap());
+}
@@ -0,0 +1,14 @@
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::database::models::kart_offset::KartOffset;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::info;
+
+/// estimate how much faster or slower every kart was than the average kart, per month
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let amount = KartOffset::fit_all().await.unwrap();
+    info!(target:"fit_kart_offsets", "stored {} kart offsets", amount);
+}
@@ -1,5 +1,6 @@
 use karting_groningen_analytics::cron_jobs::load_todays_heats;
 use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::heat_source::get_heat_source;
 use karting_groningen_analytics::modules::helpers::logging::setup_logging;
 
 #[tokio::main]
@@ -7,5 +8,5 @@ pub async fn main() {
     setup_logging().expect("Error setting up logging");
     setup_pool().await;
 
-    load_todays_heats().await;
+    load_todays_heats(get_heat_source().as_ref()).await;
 }
@@ -0,0 +1,28 @@
+use std::env;
+
+use karting_groningen_analytics::modules::database::models::driver::Driver;
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::database::models::rating_replay::RatingReplay;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::info;
+
+/// merge the second driver into the first, the name of the second driver becomes an alias of the first.
+/// the ratings are redone with the merged laps.
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let mut args = env::args().skip(1);
+    let (Some(keep), Some(merged)) = (args.next(), args.next()) else {
+        panic!("usage: merge_drivers <driver to keep> <driver to merge>");
+    };
+
+    let driver = Driver::get_by_name(&keep).await.unwrap();
+    let merged = Driver::get_by_name(&merged).await.unwrap();
+    driver.merge(&merged).await.unwrap();
+    info!(target:"merge_drivers", "merged {} into {}", merged.name, driver.name);
+
+    let rated = RatingReplay::run().await.unwrap();
+    info!(target:"merge_drivers", "replayed the ratings of {} heats", rated);
+}
@@ -0,0 +1,14 @@
+use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::database::models::record::Record;
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::info;
+
+/// set all records again from the first heat on, without publishing them
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let amount = Record::rebuild_all().await.unwrap();
+    info!(target:"rebuild_records", "set {} records", amount);
+}
@@ -0,0 +1,64 @@
+use chrono::NaiveDateTime;
+use karting_groningen_analytics::errors::Error;
+use karting_groningen_analytics::modules::database::models::driver::Driver;
+use karting_groningen_analytics::modules::database::models::general::{get_pool, setup_pool};
+use karting_groningen_analytics::modules::database::models::rating_replay::RatingReplay;
+use karting_groningen_analytics::modules::database::models::record::Record;
+use karting_groningen_analytics::modules::heat_api::{replace_heat, WebResponse};
+use karting_groningen_analytics::modules::heat_source::{ArchiveHeatSource, HeatSource};
+use karting_groningen_analytics::modules::helpers::logging::setup_logging;
+use log::{error, info, warn};
+
+/// rebuild the heats, laps and drivers from the archived payloads.
+/// heats that are not in the archive are left alone.
+#[tokio::main]
+async fn main() {
+    setup_logging().expect("Error setting up logging");
+    setup_pool().await;
+
+    let source = ArchiveHeatSource {};
+    let heat_ids = source.get_todays_heats().await.unwrap();
+
+    // parse everything first so a heat that no longer parses is not deleted
+    let mut heats: Vec<WebResponse> = Vec::new();
+    for heat_id in heat_ids {
+        match source.get_heat(&heat_id).await {
+            Ok(heat) => heats.push(heat),
+            Err(err) => {
+                error!(target:"reingest_heats", "failed parsing archived heat. (heat_id: {}, error: {})", heat_id, err);
+            }
+        }
+    }
+    heats.sort_by_key(|heat| NaiveDateTime::parse_from_str(&heat.heat.start_time, "%Y-%m-%dT%H:%M:%S%.f%z").ok());
+
+    for heat in heats {
+        let heat_id = heat.heat.id.clone();
+
+        // the old heat is only deleted when the new one is saved
+        match replace_heat(heat).await {
+            Ok(_) => {
+                info!(target:"reingest_heats", "reingested heat: {}", heat_id);
+            }
+            Err(Error::InvalidNameError { .. }) => {
+                warn!(target:"reingest_heats", "invalid driver names in heat {}", heat_id);
+            }
+            Err(err) => {
+                error!(target:"reingest_heats", "failed saving heat. (heat_id: {}, error: {})", heat_id, err);
+            }
+        }
+    }
+
+    let client = get_pool().get().await.unwrap();
+
+    // drivers can be left without laps when the way names are cleaned up changed
+    let deleted = Driver::delete_without_laps(&client).await.unwrap();
+    info!(target:"reingest_heats", "deleted {} drivers without laps", deleted);
+
+    // the deleted heats scheduled a replay of the ratings from the first of them
+    let rated = RatingReplay::run().await.unwrap();
+    info!(target:"reingest_heats", "replayed the ratings of {} heats", rated);
+
+    // the records are not updated while replacing the heats
+    let records = Record::rebuild_all().await.unwrap();
+    info!(target:"reingest_heats", "rebuilt {} records", records);
+}
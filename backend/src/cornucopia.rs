@@ -4,7 +4,7 @@
 #[allow(unused_imports)] #[allow(dead_code)] pub mod types { }#[allow(clippy :: all, clippy :: pedantic)] #[allow(unused_variables)]
 #[allow(unused_imports)] #[allow(dead_code)] pub mod queries
 { pub mod driver
-{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct SearchDriverWithStatsPaginatedParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub limit : i64,pub offset : i64,}#[derive( Debug)] pub struct SearchDriverByNameParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub limit : i64,pub offset : i64,}#[derive(Clone,Copy, Debug)] pub struct UpdateDriverRatingParams < > { pub rating : f64,pub uncertainty : f64,pub id : i32,}#[derive( Debug)] pub struct CreateDriverParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub rating : f64,pub uncertainty : f64,}#[derive( Debug, Clone, PartialEq, )] pub struct Driver
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct SearchDriverWithStatsPaginatedParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub track : i32,pub clean_only : bool,pub limit : i64,pub offset : i64,}#[derive(Clone,Copy, Debug)] pub struct GetDriverWithStatsParams < > { pub id : i32,pub track : i32,pub clean_only : bool,}#[derive( Debug)] pub struct SearchDriverByNameParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub limit : i64,pub offset : i64,}#[derive(Clone,Copy, Debug)] pub struct UpdateDriverRatingParams < > { pub rating : f64,pub uncertainty : f64,pub id : i32,}#[derive( Debug)] pub struct CreateDriverParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub rating : f64,pub uncertainty : f64,}#[derive( Debug)] pub struct CreateDriverIfNotExistsParams < T1 : cornucopia_async::StringSql,> { pub name : T1,pub rating : f64,pub uncertainty : f64,}#[derive(Clone,Copy, Debug)] pub struct ResetDriverRatingsParams < > { pub rating : f64,pub uncertainty : f64,}#[derive(Clone,Copy, Debug)] pub struct CountSharedHeatsParams < > { pub driver : i32,pub other : i32,}#[derive( Debug)] pub struct GetLeaderboardParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub system : T1,pub track : i32,pub from_date : time::PrimitiveDateTime,pub to_date : time::PrimitiveDateTime,pub provisional_sessions : i32,pub min_laps : i64,pub metric : T2,pub include_provisional : bool,pub limit : i64,pub offset : i64,}#[derive( Debug, Clone, PartialEq, )] pub struct Driver
 { pub id : i32,pub name : String,pub rating : f64,pub uncertainty : f64,}pub struct DriverBorrowed < 'a >
 { pub id : i32,pub name : &'a str,pub rating : f64,pub uncertainty : f64,} impl < 'a > From < DriverBorrowed <
 'a >> for Driver
@@ -52,13 +52,13 @@ where C : GenericClient
         Ok(it)
     }
 }#[derive( Debug, Clone, PartialEq, )] pub struct DriverWithStats
-{ pub id : i32,pub name : String,pub rating : f64,pub uncertainty : f64,pub fastest_lap_time : f64,pub avg_lap_time : f64,pub median_lap_time : f64,pub total_laps : i32,pub total_heats : i32,}pub struct DriverWithStatsBorrowed < 'a >
-{ pub id : i32,pub name : &'a str,pub rating : f64,pub uncertainty : f64,pub fastest_lap_time : f64,pub avg_lap_time : f64,pub median_lap_time : f64,pub total_laps : i32,pub total_heats : i32,} impl < 'a > From < DriverWithStatsBorrowed <
+{ pub id : i32,pub name : String,pub rating : f64,pub uncertainty : f64,pub fastest_lap_time : f64,pub adjusted_fastest_lap_time : f64,pub normalized_fastest_lap_time : f64,pub avg_lap_time : f64,pub median_lap_time : f64,pub total_laps : i32,pub total_heats : i32,pub track : i32,}pub struct DriverWithStatsBorrowed < 'a >
+{ pub id : i32,pub name : &'a str,pub rating : f64,pub uncertainty : f64,pub fastest_lap_time : f64,pub adjusted_fastest_lap_time : f64,pub normalized_fastest_lap_time : f64,pub avg_lap_time : f64,pub median_lap_time : f64,pub total_laps : i32,pub total_heats : i32,pub track : i32,} impl < 'a > From < DriverWithStatsBorrowed <
 'a >> for DriverWithStats
 {
     fn
-    from(DriverWithStatsBorrowed { id,name,rating,uncertainty,fastest_lap_time,avg_lap_time,median_lap_time,total_laps,total_heats,} : DriverWithStatsBorrowed < 'a >)
-    -> Self { Self { id,name: name.into(),rating,uncertainty,fastest_lap_time,avg_lap_time,median_lap_time,total_laps,total_heats,} }
+    from(DriverWithStatsBorrowed { id,name,rating,uncertainty,fastest_lap_time,adjusted_fastest_lap_time,normalized_fastest_lap_time,avg_lap_time,median_lap_time,total_laps,total_heats,track,} : DriverWithStatsBorrowed < 'a >)
+    -> Self { Self { id,name: name.into(),rating,uncertainty,fastest_lap_time,adjusted_fastest_lap_time,normalized_fastest_lap_time,avg_lap_time,median_lap_time,total_laps,total_heats,track,} }
 }pub struct DriverWithStatsQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
@@ -98,27 +98,59 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}#[derive( Debug, Clone, PartialEq, )] pub struct GetDriversFromHeatInOrderFastestLap
-{ pub id : i32,pub name : String,pub rating : f64,pub uncertainty : f64,}pub struct GetDriversFromHeatInOrderFastestLapBorrowed < 'a >
-{ pub id : i32,pub name : &'a str,pub rating : f64,pub uncertainty : f64,} impl < 'a > From < GetDriversFromHeatInOrderFastestLapBorrowed <
-'a >> for GetDriversFromHeatInOrderFastestLap
+}pub struct I64Query < 'a, C : GenericClient, T, const N : usize >
 {
-    fn
-    from(GetDriversFromHeatInOrderFastestLapBorrowed { id,name,rating,uncertainty,} : GetDriversFromHeatInOrderFastestLapBorrowed < 'a >)
-    -> Self { Self { id,name: name.into(),rating,uncertainty,} }
-}pub struct GetDriversFromHeatInOrderFastestLapQuery < 'a, C : GenericClient, T, const N : usize >
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i64,
+    mapper : fn(i64) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I64Query < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(i64) -> R) -> I64Query
+    < 'a, C, R, N >
+    {
+        I64Query
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, Copy)] pub struct DriverHeat
+{ pub driver : i32,pub heat : i32,}pub struct DriverHeatQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> GetDriversFromHeatInOrderFastestLapBorrowed,
-    mapper : fn(GetDriversFromHeatInOrderFastestLapBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > GetDriversFromHeatInOrderFastestLapQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DriverHeat,
+    mapper : fn(DriverHeat) -> T,
+} impl < 'a, C, T : 'a, const N : usize > DriverHeatQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(GetDriversFromHeatInOrderFastestLapBorrowed) -> R) -> GetDriversFromHeatInOrderFastestLapQuery
+    pub fn map < R > (self, mapper : fn(DriverHeat) -> R) -> DriverHeatQuery
     < 'a, C, R, N >
     {
-        GetDriversFromHeatInOrderFastestLapQuery
+        DriverHeatQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -145,19 +177,27 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}pub struct I64Query < 'a, C : GenericClient, T, const N : usize >
+}#[derive( Debug, Clone, PartialEq, )] pub struct LeaderboardEntry
+{ pub driver : i32,pub driver_name : String,pub fastest_lap_time : f64,pub adjusted_fastest_lap_time : f64,pub rating : Option<f64>,pub total_laps : i32,pub total_heats : i32,pub provisional : bool,pub position : i32,pub total_entries : i32,}pub struct LeaderboardEntryBorrowed < 'a >
+{ pub driver : i32,pub driver_name : &'a str,pub fastest_lap_time : f64,pub adjusted_fastest_lap_time : f64,pub rating : Option<f64>,pub total_laps : i32,pub total_heats : i32,pub provisional : bool,pub position : i32,pub total_entries : i32,} impl < 'a > From < LeaderboardEntryBorrowed <
+'a >> for LeaderboardEntry
+{
+    fn
+    from(LeaderboardEntryBorrowed { driver,driver_name,fastest_lap_time,adjusted_fastest_lap_time,rating,total_laps,total_heats,provisional,position,total_entries,} : LeaderboardEntryBorrowed < 'a >)
+    -> Self { Self { driver,driver_name: driver_name.into(),fastest_lap_time,adjusted_fastest_lap_time,rating,total_laps,total_heats,provisional,position,total_entries,} }
+}pub struct LeaderboardEntryQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i64,
-    mapper : fn(i64) -> T,
-} impl < 'a, C, T : 'a, const N : usize > I64Query < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> LeaderboardEntryBorrowed,
+    mapper : fn(LeaderboardEntryBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > LeaderboardEntryQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(i64) -> R) -> I64Query
+    pub fn map < R > (self, mapper : fn(LeaderboardEntryBorrowed) -> R) -> LeaderboardEntryQuery
     < 'a, C, R, N >
     {
-        I64Query
+        LeaderboardEntryQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -227,59 +267,82 @@ Driver, 1 >
 { SearchDriverWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt :: new("select
     d.*,
     min(l.lap_time) as fastest_lap_time,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as adjusted_fastest_lap_time,
+    min((l.lap_time - coalesce(o.time_offset, 0)) / coalesce(c.condition_index, 1)) as normalized_fastest_lap_time,
     avg(l.lap_time) as avg_lap_time,
     percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as median_lap_time,
     CAST(count(l.lap_time) AS INT) as total_laps,
-    CAST(count(DISTINCT l.heat) AS INT) as total_heats
+    CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+    s.track
 from public.drivers d
          inner join public.laps l on d.id = l.driver
-where d.name like concat('%', $1::text ,'%')
-GROUP BY d.id
-limit $2 offset $3")) } pub
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+         left join public.session_conditions c on c.session = s.id
+where d.name like concat('%', $1::text ,'%') and s.track = $2
+  and (not $3::bool or l.classification = 'clean')
+GROUP BY d.id, s.track
+limit $4 offset $5")) } pub
 struct SearchDriverWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt) ; impl
 SearchDriverWithStatsPaginatedStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-name : & 'a T1,limit : & 'a i64,offset : & 'a i64,) -> DriverWithStatsQuery < 'a, C,
-DriverWithStats, 3 >
+name : & 'a T1,track : & 'a i32,clean_only : & 'a bool,limit : & 'a i64,offset : & 'a i64,) -> DriverWithStatsQuery < 'a, C,
+DriverWithStats, 5 >
 {
     DriverWithStatsQuery
     {
-        client, params : [name,limit,offset,], stmt : & mut self.0, extractor :
-        | row | { DriverWithStatsBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),fastest_lap_time : row.get(4),avg_lap_time : row.get(5),median_lap_time : row.get(6),total_laps : row.get(7),total_heats : row.get(8),} }, mapper : | it | { <DriverWithStats>::from(it) },
+        client, params : [name,track,clean_only,limit,offset,], stmt : & mut self.0, extractor :
+        | row | { DriverWithStatsBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),fastest_lap_time : row.get(4),adjusted_fastest_lap_time : row.get(5),normalized_fastest_lap_time : row.get(6),avg_lap_time : row.get(7),median_lap_time : row.get(8),total_laps : row.get(9),total_heats : row.get(10),track : row.get(11),} }, mapper : | it | { <DriverWithStats>::from(it) },
     }
 } }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
 Params < 'a, SearchDriverWithStatsPaginatedParams < T1,>, DriverWithStatsQuery < 'a,
-C, DriverWithStats, 3 >, C > for SearchDriverWithStatsPaginatedStmt
+C, DriverWithStats, 5 >, C > for SearchDriverWithStatsPaginatedStmt
 {
     fn
     params(& 'a mut self, client : & 'a  C, params : & 'a
     SearchDriverWithStatsPaginatedParams < T1,>) -> DriverWithStatsQuery < 'a, C,
-    DriverWithStats, 3 >
-    { self.bind(client, & params.name,& params.limit,& params.offset,) }
+    DriverWithStats, 5 >
+    { self.bind(client, & params.name,& params.track,& params.clean_only,& params.limit,& params.offset,) }
 }pub fn get_driver_with_stats() -> GetDriverWithStatsStmt
 { GetDriverWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
     d.*,
     min(l.lap_time) as fastest_lap_time,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as adjusted_fastest_lap_time,
+    min((l.lap_time - coalesce(o.time_offset, 0)) / coalesce(c.condition_index, 1)) as normalized_fastest_lap_time,
     avg(l.lap_time) as avg_lap_time,
     percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as median_lap_time,
     CAST(count(l.lap_time) AS INT) as total_laps,
-    CAST(count(DISTINCT l.heat) AS INT) as total_heats
+    CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+    s.track
 from public.drivers d
          inner join public.laps l on d.id = l.driver
-where d.id = $1::int
-GROUP BY d.id")) } pub
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+         left join public.session_conditions c on c.session = s.id
+where d.id = $1::int and s.track = $2
+  and (not $3::bool or l.classification = 'clean')
+GROUP BY d.id, s.track")) } pub
 struct GetDriverWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
 GetDriverWithStatsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-id : & 'a i32,) -> DriverWithStatsQuery < 'a, C,
-DriverWithStats, 1 >
+id : & 'a i32,track : & 'a i32,clean_only : & 'a bool,) -> DriverWithStatsQuery < 'a, C,
+DriverWithStats, 3 >
 {
     DriverWithStatsQuery
     {
-        client, params : [id,], stmt : & mut self.0, extractor :
-        | row | { DriverWithStatsBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),fastest_lap_time : row.get(4),avg_lap_time : row.get(5),median_lap_time : row.get(6),total_laps : row.get(7),total_heats : row.get(8),} }, mapper : | it | { <DriverWithStats>::from(it) },
+        client, params : [id,track,clean_only,], stmt : & mut self.0, extractor :
+        | row | { DriverWithStatsBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),fastest_lap_time : row.get(4),adjusted_fastest_lap_time : row.get(5),normalized_fastest_lap_time : row.get(6),avg_lap_time : row.get(7),median_lap_time : row.get(8),total_laps : row.get(9),total_heats : row.get(10),track : row.get(11),} }, mapper : | it | { <DriverWithStats>::from(it) },
     }
-} }pub fn get_driver_by_name() -> GetDriverByNameStmt
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, GetDriverWithStatsParams < >, DriverWithStatsQuery < 'a,
+C, DriverWithStats, 3 >, C > for GetDriverWithStatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetDriverWithStatsParams < >) -> DriverWithStatsQuery < 'a, C,
+    DriverWithStats, 3 >
+    { self.bind(client, & params.id,& params.track,& params.clean_only,) }
+}pub fn get_driver_by_name() -> GetDriverByNameStmt
 { GetDriverByNameStmt(cornucopia_async :: private :: Stmt :: new("select
     *
 from public.drivers
@@ -295,6 +358,23 @@ Driver, 1 >
         client, params : [name,], stmt : & mut self.0, extractor :
         | row | { DriverBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),} }, mapper : | it | { <Driver>::from(it) },
     }
+} }pub fn get_driver_by_alias() -> GetDriverByAliasStmt
+{ GetDriverByAliasStmt(cornucopia_async :: private :: Stmt :: new("select
+    d.*
+from public.drivers d
+         inner join public.driver_aliases a on a.driver = d.id
+WHERE a.alias = $1::text")) } pub
+struct GetDriverByAliasStmt(cornucopia_async :: private :: Stmt) ; impl
+GetDriverByAliasStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+alias : & 'a T1,) -> DriverQuery < 'a, C,
+Driver, 1 >
+{
+    DriverQuery
+    {
+        client, params : [alias,], stmt : & mut self.0, extractor :
+        | row | { DriverBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),} }, mapper : | it | { <Driver>::from(it) },
+    }
 } }pub fn search_driver_by_name() -> SearchDriverByNameStmt
 { SearchDriverByNameStmt(cornucopia_async :: private :: Stmt :: new("select
     *
@@ -321,27 +401,7 @@ C, Driver, 3 >, C > for SearchDriverByNameStmt
     SearchDriverByNameParams < T1,>) -> DriverQuery < 'a, C,
     Driver, 3 >
     { self.bind(client, & params.name,& params.limit,& params.offset,) }
-}pub fn get_drivers_from_heat_in_order_fastest_lap() -> GetDriversFromHeatInOrderFastestLapStmt
-{ GetDriversFromHeatInOrderFastestLapStmt(cornucopia_async :: private :: Stmt :: new("select
-    d.*
-from public.drivers d
-inner join public.laps l on d.id = l.driver
-inner join public.session h on h.id = l.heat
-where h.id = $1
-group by d.id
-order by min(l.lap_time) asc")) } pub
-struct GetDriversFromHeatInOrderFastestLapStmt(cornucopia_async :: private :: Stmt) ; impl
-GetDriversFromHeatInOrderFastestLapStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-heat : & 'a i32,) -> GetDriversFromHeatInOrderFastestLapQuery < 'a, C,
-GetDriversFromHeatInOrderFastestLap, 1 >
-{
-    GetDriversFromHeatInOrderFastestLapQuery
-    {
-        client, params : [heat,], stmt : & mut self.0, extractor :
-        | row | { GetDriversFromHeatInOrderFastestLapBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),} }, mapper : | it | { <GetDriversFromHeatInOrderFastestLap>::from(it) },
-    }
-} }pub fn update_driver_rating() -> UpdateDriverRatingStmt
+}pub fn update_driver_rating() -> UpdateDriverRatingStmt
 { UpdateDriverRatingStmt(cornucopia_async :: private :: Stmt :: new("with update_rows AS (
     UPDATE
         public.drivers
@@ -393,28 +453,215 @@ C, Driver, 3 >, C > for CreateDriverStmt
     CreateDriverParams < T1,>) -> DriverQuery < 'a, C,
     Driver, 3 >
     { self.bind(client, & params.name,& params.rating,& params.uncertainty,) }
-}}pub mod heats
-{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive(Clone,Copy, Debug)] pub struct GetHeatWithStatsPaginatedParams < > { pub limit : i64,pub offset : i64,}#[derive( Debug)] pub struct CreateNewHeatParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub heat_id : T1,pub heat_type : T2,pub start_date : time::PrimitiveDateTime,}#[derive( Debug, Clone, PartialEq, )] pub struct Heat
-{ pub id : i32,pub heat_id : String,pub heat_type : String,pub start_date : time::PrimitiveDateTime,}pub struct HeatBorrowed < 'a >
-{ pub id : i32,pub heat_id : &'a str,pub heat_type : &'a str,pub start_date : time::PrimitiveDateTime,} impl < 'a > From < HeatBorrowed <
-'a >> for Heat
+}pub fn create_driver_if_not_exists() -> CreateDriverIfNotExistsStmt
+{ CreateDriverIfNotExistsStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO public.drivers (name, rating, uncertainty) values ($1, $2, $3)
+on conflict (name) do nothing
+RETURNING *")) } pub
+struct CreateDriverIfNotExistsStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateDriverIfNotExistsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+name : & 'a T1,rating : & 'a f64,uncertainty : & 'a f64,) -> DriverQuery < 'a, C,
+Driver, 3 >
+{
+    DriverQuery
+    {
+        client, params : [name,rating,uncertainty,], stmt : & mut self.0, extractor :
+        | row | { DriverBorrowed { id : row.get(0),name : row.get(1),rating : row.get(2),uncertainty : row.get(3),} }, mapper : | it | { <Driver>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, CreateDriverIfNotExistsParams < T1,>, DriverQuery < 'a,
+C, Driver, 3 >, C > for CreateDriverIfNotExistsStmt
 {
     fn
-    from(HeatBorrowed { id,heat_id,heat_type,start_date,} : HeatBorrowed < 'a >)
-    -> Self { Self { id,heat_id: heat_id.into(),heat_type: heat_type.into(),start_date,} }
-}pub struct HeatQuery < 'a, C : GenericClient, T, const N : usize >
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CreateDriverIfNotExistsParams < T1,>) -> DriverQuery < 'a, C,
+    Driver, 3 >
+    { self.bind(client, & params.name,& params.rating,& params.uncertainty,) }
+}pub fn reset_driver_ratings() -> ResetDriverRatingsStmt
+{ ResetDriverRatingsStmt(cornucopia_async :: private :: Stmt :: new("with update_rows AS (
+    UPDATE
+        public.drivers
+    set
+        rating=$1,
+        uncertainty=$2
+    RETURNING 1
+) SELECT count(*) FROM update_rows")) } pub
+struct ResetDriverRatingsStmt(cornucopia_async :: private :: Stmt) ; impl
+ResetDriverRatingsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+rating : & 'a f64,uncertainty : & 'a f64,) -> I64Query < 'a, C,
+i64, 2 >
+{
+    I64Query
+    {
+        client, params : [rating,uncertainty,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, ResetDriverRatingsParams < >, I64Query < 'a,
+C, i64, 2 >, C > for ResetDriverRatingsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    ResetDriverRatingsParams < >) -> I64Query < 'a, C,
+    i64, 2 >
+    { self.bind(client, & params.rating,& params.uncertainty,) }
+}pub fn set_driver_ratings_from_system() -> SetDriverRatingsFromSystemStmt
+{ SetDriverRatingsFromSystemStmt(cornucopia_async :: private :: Stmt :: new("update public.drivers d
+set
+    rating = r.rating,
+    uncertainty = r.uncertainty
+from public.driver_ratings r
+where r.driver = d.id and r.system = $1::text")) } pub
+struct SetDriverRatingsFromSystemStmt(cornucopia_async :: private :: Stmt) ; impl
+SetDriverRatingsFromSystemStmt { pub async fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+system : & 'a T1,) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & [system,]) .await
+} }pub fn delete_drivers_without_laps() -> DeleteDriversWithoutLapsStmt
+{ DeleteDriversWithoutLapsStmt(cornucopia_async :: private :: Stmt :: new("delete from public.drivers d where not exists (select 1 from public.laps l where l.driver = d.id)")) } pub
+struct DeleteDriversWithoutLapsStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteDriversWithoutLapsStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }pub fn delete_driver() -> DeleteDriverStmt
+{ DeleteDriverStmt(cornucopia_async :: private :: Stmt :: new("delete from public.drivers where id = $1")) } pub
+struct DeleteDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteDriverStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & [id,]) .await
+} }pub fn get_heats_of_drivers() -> GetHeatsOfDriversStmt
+{ GetHeatsOfDriversStmt(cornucopia_async :: private :: Stmt :: new("select distinct driver, heat from public.laps")) } pub
+struct GetHeatsOfDriversStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatsOfDriversStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> DriverHeatQuery < 'a, C,
+DriverHeat, 0 >
+{
+    DriverHeatQuery
+    {
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { DriverHeat { driver : row.get(0),heat : row.get(1),} }, mapper : | it | { <DriverHeat>::from(it) },
+    }
+} }pub fn count_shared_heats() -> CountSharedHeatsStmt
+{ CountSharedHeatsStmt(cornucopia_async :: private :: Stmt :: new("select count(distinct a.heat)
+from public.laps a
+         inner join public.laps b on b.heat = a.heat
+where a.driver = $1 and b.driver = $2")) } pub
+struct CountSharedHeatsStmt(cornucopia_async :: private :: Stmt) ; impl
+CountSharedHeatsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,other : & 'a i32,) -> I64Query < 'a, C,
+i64, 2 >
+{
+    I64Query
+    {
+        client, params : [driver,other,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, CountSharedHeatsParams < >, I64Query < 'a,
+C, i64, 2 >, C > for CountSharedHeatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CountSharedHeatsParams < >) -> I64Query < 'a, C,
+    i64, 2 >
+    { self.bind(client, & params.driver,& params.other,) }
+}pub fn get_leaderboard() -> GetLeaderboardStmt
+{ GetLeaderboardStmt(cornucopia_async :: private :: Stmt :: new("with entries as (
+    select
+        d.id as driver,
+        d.name as driver_name,
+        min(l.lap_time) filter (where l.classification = 'clean') as fastest_lap_time,
+        min(l.lap_time - coalesce(o.time_offset, 0)) filter (where l.classification = 'clean') as adjusted_fastest_lap_time,
+        (
+            select r.rating_after
+            from public.rating_history r
+                     inner join public.session rs on rs.id = r.session
+            where r.driver = d.id and r.system = $1::text and rs.track = $2
+              and rs.start_date >= $3 and rs.start_date < $4
+            order by rs.start_date desc
+            limit 1
+        ) as rating,
+        CAST(count(l.id) AS INT) as total_laps,
+        CAST(count(DISTINCT l.heat) AS INT) as total_heats,
+        coalesce(dr.amount_of_sessions, 0) < $5 as provisional
+    from public.drivers d
+             inner join public.laps l on d.id = l.driver
+             inner join public.session s on s.id = l.heat
+             left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+             left join public.driver_ratings dr on dr.driver = d.id and dr.system = $1::text
+    where s.track = $2 and s.start_date >= $3 and s.start_date < $4
+    group by d.id, dr.amount_of_sessions
+    having count(l.id) filter (where l.classification = 'clean') >= $6
+), ranked as (
+    select
+        e.*,
+        CAST(rank() over (order by case $7::text
+            when 'adjusted_fastest_lap' then e.adjusted_fastest_lap_time
+            when 'rating' then -e.rating
+            when 'laps_driven' then -e.total_laps
+            else e.fastest_lap_time
+        end) AS INT) as position,
+        CAST(count(*) over () AS INT) as total_entries
+    from entries e
+    where ($7::text <> 'rating' or e.rating is not null)
+      and ($8::bool or not e.provisional)
+)
+select * from ranked
+order by position, driver_name
+limit $9 offset $10")) } pub
+struct GetLeaderboardStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLeaderboardStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+system : & 'a T1,track : & 'a i32,from_date : & 'a time::PrimitiveDateTime,to_date : & 'a time::PrimitiveDateTime,provisional_sessions : & 'a i32,min_laps : & 'a i64,metric : & 'a T2,include_provisional : & 'a bool,limit : & 'a i64,offset : & 'a i64,) -> LeaderboardEntryQuery < 'a, C,
+LeaderboardEntry, 10 >
+{
+    LeaderboardEntryQuery
+    {
+        client, params : [system,track,from_date,to_date,provisional_sessions,min_laps,metric,include_provisional,limit,offset,], stmt : & mut self.0, extractor :
+        | row | { LeaderboardEntryBorrowed { driver : row.get(0),driver_name : row.get(1),fastest_lap_time : row.get(2),adjusted_fastest_lap_time : row.get(3),rating : row.get(4),total_laps : row.get(5),total_heats : row.get(6),provisional : row.get(7),position : row.get(8),total_entries : row.get(9),} }, mapper : | it | { <LeaderboardEntry>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, GetLeaderboardParams < T1,T2,>, LeaderboardEntryQuery < 'a,
+C, LeaderboardEntry, 10 >, C > for GetLeaderboardStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetLeaderboardParams < T1,T2,>) -> LeaderboardEntryQuery < 'a, C,
+    LeaderboardEntry, 10 >
+    { self.bind(client, & params.system,& params.track,& params.from_date,& params.to_date,& params.provisional_sessions,& params.min_laps,& params.metric,& params.include_provisional,& params.limit,& params.offset,) }
+}}pub mod driver_aliases
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct InsertDriverAliasParams < T1 : cornucopia_async::StringSql,> { pub alias : T1,pub driver : i32,}#[derive(Clone,Copy, Debug)] pub struct MoveDriverAliasesParams < > { pub keep : i32,pub merged : i32,}#[derive( Debug, Clone, PartialEq, )] pub struct DriverAlias
+{ pub id : i32,pub alias : String,pub driver : i32,pub created_at : time::PrimitiveDateTime,}pub struct DriverAliasBorrowed < 'a >
+{ pub id : i32,pub alias : &'a str,pub driver : i32,pub created_at : time::PrimitiveDateTime,} impl < 'a > From < DriverAliasBorrowed <
+'a >> for DriverAlias
+{
+    fn
+    from(DriverAliasBorrowed { id,alias,driver,created_at,} : DriverAliasBorrowed < 'a >)
+    -> Self { Self { id,alias: alias.into(),driver,created_at,} }
+}pub struct DriverAliasQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> HeatBorrowed,
-    mapper : fn(HeatBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > HeatQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DriverAliasBorrowed,
+    mapper : fn(DriverAliasBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > DriverAliasQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(HeatBorrowed) -> R) -> HeatQuery
+    pub fn map < R > (self, mapper : fn(DriverAliasBorrowed) -> R) -> DriverAliasQuery
     < 'a, C, R, N >
     {
-        HeatQuery
+        DriverAliasQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -441,27 +688,84 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}#[derive( Debug, Clone, PartialEq, )] pub struct GetHeatWithStats
-{ pub heat_id : String,pub heat_type : String,pub start_time : time::PrimitiveDateTime,pub amount_of_laps : i32,pub amount_of_drivers : i32,pub fastest_lap_time : f64,pub average_lap_time : f64,}pub struct GetHeatWithStatsBorrowed < 'a >
-{ pub heat_id : &'a str,pub heat_type : &'a str,pub start_time : time::PrimitiveDateTime,pub amount_of_laps : i32,pub amount_of_drivers : i32,pub fastest_lap_time : f64,pub average_lap_time : f64,} impl < 'a > From < GetHeatWithStatsBorrowed <
-'a >> for GetHeatWithStats
+}pub fn get_aliases_of_driver() -> GetAliasesOfDriverStmt
+{ GetAliasesOfDriverStmt(cornucopia_async :: private :: Stmt :: new("select * from public.driver_aliases where driver = $1 order by created_at")) } pub
+struct GetAliasesOfDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAliasesOfDriverStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,) -> DriverAliasQuery < 'a, C,
+DriverAlias, 1 >
+{
+    DriverAliasQuery
+    {
+        client, params : [driver,], stmt : & mut self.0, extractor :
+        | row | { DriverAliasBorrowed { id : row.get(0),alias : row.get(1),driver : row.get(2),created_at : row.get(3),} }, mapper : | it | { <DriverAlias>::from(it) },
+    }
+} }pub fn insert_driver_alias() -> InsertDriverAliasStmt
+{ InsertDriverAliasStmt(cornucopia_async :: private :: Stmt :: new("insert into public.driver_aliases (alias, driver, created_at)
+values ($1, $2, now() at time zone 'utc')
+on conflict (alias) do update set driver = excluded.driver
+returning *")) } pub
+struct InsertDriverAliasStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertDriverAliasStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+alias : & 'a T1,driver : & 'a i32,) -> DriverAliasQuery < 'a, C,
+DriverAlias, 2 >
+{
+    DriverAliasQuery
+    {
+        client, params : [alias,driver,], stmt : & mut self.0, extractor :
+        | row | { DriverAliasBorrowed { id : row.get(0),alias : row.get(1),driver : row.get(2),created_at : row.get(3),} }, mapper : | it | { <DriverAlias>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, InsertDriverAliasParams < T1,>, DriverAliasQuery < 'a,
+C, DriverAlias, 2 >, C > for InsertDriverAliasStmt
 {
     fn
-    from(GetHeatWithStatsBorrowed { heat_id,heat_type,start_time,amount_of_laps,amount_of_drivers,fastest_lap_time,average_lap_time,} : GetHeatWithStatsBorrowed < 'a >)
-    -> Self { Self { heat_id: heat_id.into(),heat_type: heat_type.into(),start_time,amount_of_laps,amount_of_drivers,fastest_lap_time,average_lap_time,} }
-}pub struct GetHeatWithStatsQuery < 'a, C : GenericClient, T, const N : usize >
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertDriverAliasParams < T1,>) -> DriverAliasQuery < 'a, C,
+    DriverAlias, 2 >
+    { self.bind(client, & params.alias,& params.driver,) }
+}pub fn move_driver_aliases() -> MoveDriverAliasesStmt
+{ MoveDriverAliasesStmt(cornucopia_async :: private :: Stmt :: new("update public.driver_aliases set driver = $1 where driver = $2")) } pub
+struct MoveDriverAliasesStmt(cornucopia_async :: private :: Stmt) ; impl
+MoveDriverAliasesStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+keep : & 'a i32,merged : & 'a i32,) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & [keep,merged,]) .await
+} }impl < 'a, C : GenericClient + Send + Sync, >
+cornucopia_async :: Params < 'a, MoveDriverAliasesParams < >, std :: pin :: Pin < Box < dyn futures :: Future < Output = Result <
+u64, tokio_postgres :: Error > > + Send + 'a > >, C > for MoveDriverAliasesStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    MoveDriverAliasesParams < >) -> std :: pin :: Pin < Box < dyn futures :: Future < Output = Result <
+u64, tokio_postgres :: Error > > + Send + 'a > >
+    { Box :: pin(self.bind(client, & params.keep,& params.merged,)) }
+}}pub mod driver_ratings
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct GetRatingsOfDriversInSystemParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,> { pub system : T1,pub drivers : T2,}#[derive( Debug)] pub struct SetDriverRatingParams < T1 : cornucopia_async::StringSql,> { pub driver : i32,pub system : T1,pub rating : f64,pub uncertainty : f64,pub volatility : Option<f64>,pub last_rated : time::PrimitiveDateTime,}#[derive( Debug, Clone, PartialEq, )] pub struct DriverRating
+{ pub id : i32,pub driver : i32,pub system : String,pub rating : f64,pub uncertainty : f64,pub volatility : Option<f64>,pub last_rated : Option<time::PrimitiveDateTime>,pub amount_of_sessions : i32,}pub struct DriverRatingBorrowed < 'a >
+{ pub id : i32,pub driver : i32,pub system : &'a str,pub rating : f64,pub uncertainty : f64,pub volatility : Option<f64>,pub last_rated : Option<time::PrimitiveDateTime>,pub amount_of_sessions : i32,} impl < 'a > From < DriverRatingBorrowed <
+'a >> for DriverRating
+{
+    fn
+    from(DriverRatingBorrowed { id,driver,system,rating,uncertainty,volatility,last_rated,amount_of_sessions,} : DriverRatingBorrowed < 'a >)
+    -> Self { Self { id,driver,system: system.into(),rating,uncertainty,volatility,last_rated,amount_of_sessions,} }
+}pub struct DriverRatingQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> GetHeatWithStatsBorrowed,
-    mapper : fn(GetHeatWithStatsBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > GetHeatWithStatsQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DriverRatingBorrowed,
+    mapper : fn(DriverRatingBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > DriverRatingQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(GetHeatWithStatsBorrowed) -> R) -> GetHeatWithStatsQuery
+    pub fn map < R > (self, mapper : fn(DriverRatingBorrowed) -> R) -> DriverRatingQuery
     < 'a, C, R, N >
     {
-        GetHeatWithStatsQuery
+        DriverRatingQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -488,27 +792,128 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}#[derive( Debug, Clone, PartialEq, )] pub struct DeleteHeat
-{ pub id : i32,pub heat_id : String,pub heat_type : String,pub start_date : time::PrimitiveDateTime,}pub struct DeleteHeatBorrowed < 'a >
-{ pub id : i32,pub heat_id : &'a str,pub heat_type : &'a str,pub start_date : time::PrimitiveDateTime,} impl < 'a > From < DeleteHeatBorrowed <
-'a >> for DeleteHeat
+}pub fn get_ratings_of_driver() -> GetRatingsOfDriverStmt
+{ GetRatingsOfDriverStmt(cornucopia_async :: private :: Stmt :: new("select * from public.driver_ratings where driver = $1 order by system")) } pub
+struct GetRatingsOfDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRatingsOfDriverStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,) -> DriverRatingQuery < 'a, C,
+DriverRating, 1 >
+{
+    DriverRatingQuery
+    {
+        client, params : [driver,], stmt : & mut self.0, extractor :
+        | row | { DriverRatingBorrowed { id : row.get(0),driver : row.get(1),system : row.get(2),rating : row.get(3),uncertainty : row.get(4),volatility : row.get(5),last_rated : row.get(6),amount_of_sessions : row.get(7),} }, mapper : | it | { <DriverRating>::from(it) },
+    }
+} }pub fn get_ratings_of_drivers_in_system() -> GetRatingsOfDriversInSystemStmt
+{ GetRatingsOfDriversInSystemStmt(cornucopia_async :: private :: Stmt :: new("select * from public.driver_ratings where system = $1::text and driver = any($2::int[])")) } pub
+struct GetRatingsOfDriversInSystemStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRatingsOfDriversInSystemStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+system : & 'a T1,drivers : & 'a T2,) -> DriverRatingQuery < 'a, C,
+DriverRating, 2 >
+{
+    DriverRatingQuery
+    {
+        client, params : [system,drivers,], stmt : & mut self.0, extractor :
+        | row | { DriverRatingBorrowed { id : row.get(0),driver : row.get(1),system : row.get(2),rating : row.get(3),uncertainty : row.get(4),volatility : row.get(5),last_rated : row.get(6),amount_of_sessions : row.get(7),} }, mapper : | it | { <DriverRating>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
+Params < 'a, GetRatingsOfDriversInSystemParams < T1,T2,>, DriverRatingQuery < 'a,
+C, DriverRating, 2 >, C > for GetRatingsOfDriversInSystemStmt
 {
     fn
-    from(DeleteHeatBorrowed { id,heat_id,heat_type,start_date,} : DeleteHeatBorrowed < 'a >)
-    -> Self { Self { id,heat_id: heat_id.into(),heat_type: heat_type.into(),start_date,} }
-}pub struct DeleteHeatQuery < 'a, C : GenericClient, T, const N : usize >
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetRatingsOfDriversInSystemParams < T1,T2,>) -> DriverRatingQuery < 'a, C,
+    DriverRating, 2 >
+    { self.bind(client, & params.system,& params.drivers,) }
+}pub fn set_driver_rating() -> SetDriverRatingStmt
+{ SetDriverRatingStmt(cornucopia_async :: private :: Stmt :: new("insert into public.driver_ratings (driver, system, rating, uncertainty, volatility, last_rated, amount_of_sessions)
+values ($1, $2, $3, $4, $5, $6, 1)
+on conflict (driver, system) do update set
+    rating = excluded.rating,
+    uncertainty = excluded.uncertainty,
+    volatility = excluded.volatility,
+    last_rated = excluded.last_rated,
+    amount_of_sessions = public.driver_ratings.amount_of_sessions + 1
+returning *")) } pub
+struct SetDriverRatingStmt(cornucopia_async :: private :: Stmt) ; impl
+SetDriverRatingStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,system : & 'a T1,rating : & 'a f64,uncertainty : & 'a f64,volatility : & 'a Option<f64>,last_rated : & 'a time::PrimitiveDateTime,) -> DriverRatingQuery < 'a, C,
+DriverRating, 6 >
+{
+    DriverRatingQuery
+    {
+        client, params : [driver,system,rating,uncertainty,volatility,last_rated,], stmt : & mut self.0, extractor :
+        | row | { DriverRatingBorrowed { id : row.get(0),driver : row.get(1),system : row.get(2),rating : row.get(3),uncertainty : row.get(4),volatility : row.get(5),last_rated : row.get(6),amount_of_sessions : row.get(7),} }, mapper : | it | { <DriverRating>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, SetDriverRatingParams < T1,>, DriverRatingQuery < 'a,
+C, DriverRating, 6 >, C > for SetDriverRatingStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    SetDriverRatingParams < T1,>) -> DriverRatingQuery < 'a, C,
+    DriverRating, 6 >
+    { self.bind(client, & params.driver,& params.system,& params.rating,& params.uncertainty,& params.volatility,& params.last_rated,) }
+}pub fn delete_all_driver_ratings() -> DeleteAllDriverRatingsStmt
+{ DeleteAllDriverRatingsStmt(cornucopia_async :: private :: Stmt :: new("delete from public.driver_ratings")) } pub
+struct DeleteAllDriverRatingsStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteAllDriverRatingsStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }pub fn restore_driver_ratings_from_history() -> RestoreDriverRatingsFromHistoryStmt
+{ RestoreDriverRatingsFromHistoryStmt(cornucopia_async :: private :: Stmt :: new("insert into public.driver_ratings (driver, system, rating, uncertainty, volatility, last_rated, amount_of_sessions)
+select distinct on (r.driver, r.system)
+    r.driver,
+    r.system,
+    r.rating_after,
+    r.uncertainty_after,
+    r.volatility_after,
+    s.start_date,
+    CAST(count(*) over (partition by r.driver, r.system) AS INT)
+from public.rating_history r
+         inner join public.session s on s.id = r.session
+order by r.driver, r.system, s.start_date desc, s.id desc
+on conflict (driver, system) do update set
+    rating = excluded.rating,
+    uncertainty = excluded.uncertainty,
+    volatility = excluded.volatility,
+    last_rated = excluded.last_rated,
+    amount_of_sessions = excluded.amount_of_sessions")) } pub
+struct RestoreDriverRatingsFromHistoryStmt(cornucopia_async :: private :: Stmt) ; impl
+RestoreDriverRatingsFromHistoryStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }}pub mod heat_payloads
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct InsertHeatPayloadIfChangedParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub heat_id : T1,pub fetched_at : time::PrimitiveDateTime,pub payload : T2,}#[derive( Debug, Clone, PartialEq, )] pub struct HeatPayload
+{ pub id : i32,pub heat_id : String,pub fetched_at : time::PrimitiveDateTime,pub payload : String,}pub struct HeatPayloadBorrowed < 'a >
+{ pub id : i32,pub heat_id : &'a str,pub fetched_at : time::PrimitiveDateTime,pub payload : &'a str,} impl < 'a > From < HeatPayloadBorrowed <
+'a >> for HeatPayload
+{
+    fn
+    from(HeatPayloadBorrowed { id,heat_id,fetched_at,payload,} : HeatPayloadBorrowed < 'a >)
+    -> Self { Self { id,heat_id: heat_id.into(),fetched_at,payload: payload.into(),} }
+}pub struct HeatPayloadQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DeleteHeatBorrowed,
-    mapper : fn(DeleteHeatBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > DeleteHeatQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> HeatPayloadBorrowed,
+    mapper : fn(HeatPayloadBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > HeatPayloadQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(DeleteHeatBorrowed) -> R) -> DeleteHeatQuery
+    pub fn map < R > (self, mapper : fn(HeatPayloadBorrowed) -> R) -> HeatPayloadQuery
     < 'a, C, R, N >
     {
-        DeleteHeatQuery
+        HeatPayloadQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -535,209 +940,83 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}pub fn get_all_heats() -> GetAllHeatsStmt
-{ GetAllHeatsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session")) } pub
-struct GetAllHeatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetAllHeatsStmt { pub fn bind < 'a, C : GenericClient, >
+}pub fn insert_heat_payload_if_changed() -> InsertHeatPayloadIfChangedStmt
+{ InsertHeatPayloadIfChangedStmt(cornucopia_async :: private :: Stmt :: new("insert into public.heat_payloads (heat_id, fetched_at, payload)
+select $1::varchar, $2::timestamp, $3::text
+where not exists (
+    select 1
+    from (select payload from public.heat_payloads where heat_id = $1 order by fetched_at desc limit 1) latest
+    where latest.payload = $3
+)
+returning *")) } pub
+struct InsertHeatPayloadIfChangedStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertHeatPayloadIfChangedStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-) -> HeatQuery < 'a, C,
-Heat, 0 >
+heat_id : & 'a T1,fetched_at : & 'a time::PrimitiveDateTime,payload : & 'a T2,) -> HeatPayloadQuery < 'a, C,
+HeatPayload, 3 >
 {
-    HeatQuery
+    HeatPayloadQuery
     {
-        client, params : [], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
+        client, params : [heat_id,fetched_at,payload,], stmt : & mut self.0, extractor :
+        | row | { HeatPayloadBorrowed { id : row.get(0),heat_id : row.get(1),fetched_at : row.get(2),payload : row.get(3),} }, mapper : | it | { <HeatPayload>::from(it) },
     }
-} }pub fn get_heat_from_id() -> GetHeatFromIdStmt
-{ GetHeatFromIdStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session where id = $1")) } pub
-struct GetHeatFromIdStmt(cornucopia_async :: private :: Stmt) ; impl
-GetHeatFromIdStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-id : & 'a i32,) -> HeatQuery < 'a, C,
-Heat, 1 >
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, InsertHeatPayloadIfChangedParams < T1,T2,>, HeatPayloadQuery < 'a,
+C, HeatPayload, 3 >, C > for InsertHeatPayloadIfChangedStmt
 {
-    HeatQuery
-    {
-        client, params : [id,], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
-    }
-} }pub fn get_heats_from_ids() -> GetHeatsFromIdsStmt
-{ GetHeatsFromIdsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session WHERE id = any($1::int[])")) } pub
-struct GetHeatsFromIdsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetHeatsFromIdsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertHeatPayloadIfChangedParams < T1,T2,>) -> HeatPayloadQuery < 'a, C,
+    HeatPayload, 3 >
+    { self.bind(client, & params.heat_id,& params.fetched_at,& params.payload,) }
+}pub fn get_latest_heat_payload() -> GetLatestHeatPayloadStmt
+{ GetLatestHeatPayloadStmt(cornucopia_async :: private :: Stmt :: new("select * from public.heat_payloads where heat_id = $1 order by fetched_at desc limit 1")) } pub
+struct GetLatestHeatPayloadStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLatestHeatPayloadStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-ids : & 'a T1,) -> HeatQuery < 'a, C,
-Heat, 1 >
+heat_id : & 'a T1,) -> HeatPayloadQuery < 'a, C,
+HeatPayload, 1 >
 {
-    HeatQuery
+    HeatPayloadQuery
     {
-        client, params : [ids,], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
+        client, params : [heat_id,], stmt : & mut self.0, extractor :
+        | row | { HeatPayloadBorrowed { id : row.get(0),heat_id : row.get(1),fetched_at : row.get(2),payload : row.get(3),} }, mapper : | it | { <HeatPayload>::from(it) },
     }
-} }pub fn get_heat_from_name() -> GetHeatFromNameStmt
-{ GetHeatFromNameStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session where heat_id = $1::text")) } pub
-struct GetHeatFromNameStmt(cornucopia_async :: private :: Stmt) ; impl
-GetHeatFromNameStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+} }pub fn get_latest_heat_payloads() -> GetLatestHeatPayloadsStmt
+{ GetLatestHeatPayloadsStmt(cornucopia_async :: private :: Stmt :: new("select distinct on (heat_id) * from public.heat_payloads order by heat_id, fetched_at desc")) } pub
+struct GetLatestHeatPayloadsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLatestHeatPayloadsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-name : & 'a T1,) -> HeatQuery < 'a, C,
-Heat, 1 >
+) -> HeatPayloadQuery < 'a, C,
+HeatPayload, 0 >
 {
-    HeatQuery
+    HeatPayloadQuery
     {
-        client, params : [name,], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { HeatPayloadBorrowed { id : row.get(0),heat_id : row.get(1),fetched_at : row.get(2),payload : row.get(3),} }, mapper : | it | { <HeatPayload>::from(it) },
     }
-} }pub fn get_all_heats_with_stats() -> GetAllHeatsWithStatsStmt
-{ GetAllHeatsWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
-    h.heat_id,
-    h.heat_type,
-    h.start_date as start_time,
-    CAST(count(l.*) as INT) as amount_of_laps,
-    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
-    min(l.lap_time) as fastest_lap_time,
-    avg(l.lap_time) as average_lap_time
-from public.session h
-         inner join public.laps l on h.id = l.heat
-group by h.id")) } pub
-struct GetAllHeatsWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetAllHeatsWithStatsStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-) -> GetHeatWithStatsQuery < 'a, C,
-GetHeatWithStats, 0 >
-{
-    GetHeatWithStatsQuery
-    {
-        client, params : [], stmt : & mut self.0, extractor :
-        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),amount_of_laps : row.get(3),amount_of_drivers : row.get(4),fastest_lap_time : row.get(5),average_lap_time : row.get(6),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
-    }
-} }pub fn get_heat_with_stats_paginated() -> GetHeatWithStatsPaginatedStmt
-{ GetHeatWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt :: new("select
-    h.heat_id,
-    h.heat_type,
-    h.start_date as start_time,
-    CAST(count(l.*) as INT) as amount_of_laps,
-    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
-    min(l.lap_time) as fastest_lap_time,
-    avg(l.lap_time) as average_lap_time
-from public.session h
-         inner join public.laps l on h.id = l.heat
-group by h.id, start_time
-limit $1
-offset $2")) } pub
-struct GetHeatWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt) ; impl
-GetHeatWithStatsPaginatedStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-limit : & 'a i64,offset : & 'a i64,) -> GetHeatWithStatsQuery < 'a, C,
-GetHeatWithStats, 2 >
-{
-    GetHeatWithStatsQuery
-    {
-        client, params : [limit,offset,], stmt : & mut self.0, extractor :
-        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),amount_of_laps : row.get(3),amount_of_drivers : row.get(4),fastest_lap_time : row.get(5),average_lap_time : row.get(6),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
-    }
-} }impl < 'a, C : GenericClient, > cornucopia_async ::
-Params < 'a, GetHeatWithStatsPaginatedParams < >, GetHeatWithStatsQuery < 'a,
-C, GetHeatWithStats, 2 >, C > for GetHeatWithStatsPaginatedStmt
-{
-    fn
-    params(& 'a mut self, client : & 'a  C, params : & 'a
-    GetHeatWithStatsPaginatedParams < >) -> GetHeatWithStatsQuery < 'a, C,
-    GetHeatWithStats, 2 >
-    { self.bind(client, & params.limit,& params.offset,) }
-}pub fn get_heat_with_stats() -> GetHeatWithStatsStmt
-{ GetHeatWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
-    h.heat_id,
-    h.heat_type,
-    h.start_date as start_time,
-    CAST(count(l.*) as INT) as amount_of_laps,
-    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
-    min(l.lap_time) as fastest_lap_time,
-    avg(l.lap_time) as average_lap_time
-from public.session h
-         inner join public.laps l on h.id = l.heat
-where h.heat_id = $1
-group by h.id")) } pub
-struct GetHeatWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetHeatWithStatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
-(& 'a mut self, client : & 'a  C,
-heat : & 'a T1,) -> GetHeatWithStatsQuery < 'a, C,
-GetHeatWithStats, 1 >
-{
-    GetHeatWithStatsQuery
-    {
-        client, params : [heat,], stmt : & mut self.0, extractor :
-        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),amount_of_laps : row.get(3),amount_of_drivers : row.get(4),fastest_lap_time : row.get(5),average_lap_time : row.get(6),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
-    }
-} }pub fn get_all_chronologicaly() -> GetAllChronologicalyStmt
-{ GetAllChronologicalyStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session order by start_date")) } pub
-struct GetAllChronologicalyStmt(cornucopia_async :: private :: Stmt) ; impl
-GetAllChronologicalyStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-) -> HeatQuery < 'a, C,
-Heat, 0 >
-{
-    HeatQuery
-    {
-        client, params : [], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
-    }
-} }pub fn delete_heat() -> DeleteHeatStmt
-{ DeleteHeatStmt(cornucopia_async :: private :: Stmt :: new("delete from public.session where id = $1 returning *")) } pub
-struct DeleteHeatStmt(cornucopia_async :: private :: Stmt) ; impl
-DeleteHeatStmt { pub fn bind < 'a, C : GenericClient, >
-(& 'a mut self, client : & 'a  C,
-id : & 'a i32,) -> DeleteHeatQuery < 'a, C,
-DeleteHeat, 1 >
-{
-    DeleteHeatQuery
-    {
-        client, params : [id,], stmt : & mut self.0, extractor :
-        | row | { DeleteHeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <DeleteHeat>::from(it) },
-    }
-} }pub fn create_new_heat() -> CreateNewHeatStmt
-{ CreateNewHeatStmt(cornucopia_async :: private :: Stmt :: new("insert into public.session (heat_id, heat_type, start_date) VALUES ($1, $2, $3) returning  *")) } pub
-struct CreateNewHeatStmt(cornucopia_async :: private :: Stmt) ; impl
-CreateNewHeatStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
-(& 'a mut self, client : & 'a  C,
-heat_id : & 'a T1,heat_type : & 'a T2,start_date : & 'a time::PrimitiveDateTime,) -> HeatQuery < 'a, C,
-Heat, 3 >
-{
-    HeatQuery
-    {
-        client, params : [heat_id,heat_type,start_date,], stmt : & mut self.0, extractor :
-        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),} }, mapper : | it | { <Heat>::from(it) },
-    }
-} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
-Params < 'a, CreateNewHeatParams < T1,T2,>, HeatQuery < 'a,
-C, Heat, 3 >, C > for CreateNewHeatStmt
-{
-    fn
-    params(& 'a mut self, client : & 'a  C, params : & 'a
-    CreateNewHeatParams < T1,T2,>) -> HeatQuery < 'a, C,
-    Heat, 3 >
-    { self.bind(client, & params.heat_id,& params.heat_type,& params.start_date,) }
-}}pub mod kart
-{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct CreateKartParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub number : i32,pub brand : T1,pub model : T2,pub horsepower : i32,pub modified : bool,}#[derive( Debug, Clone, PartialEq, )] pub struct Kart
-{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,}pub struct KartBorrowed < 'a >
-{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,} impl < 'a > From < KartBorrowed <
-'a >> for Kart
+} }}pub mod heats
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive(Clone,Copy, Debug)] pub struct GetAllHeatsWithStatsParams < > { pub track : Option<i32>,pub clean_only : bool,}#[derive(Clone,Copy, Debug)] pub struct GetHeatWithStatsPaginatedParams < > { pub track : Option<i32>,pub clean_only : bool,pub limit : i64,pub offset : i64,}#[derive( Debug)] pub struct GetHeatWithStatsParams < T1 : cornucopia_async::StringSql,> { pub heat : T1,pub clean_only : bool,}#[derive( Debug)] pub struct CreateNewHeatParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub heat_id : T1,pub heat_type : T2,pub start_date : time::PrimitiveDateTime,pub track : i32,}#[derive( Debug)] pub struct CreateHeatIfNotExistsParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub heat_id : T1,pub heat_type : T2,pub start_date : time::PrimitiveDateTime,pub track : i32,}#[derive( Debug, Clone, PartialEq, )] pub struct Heat
+{ pub id : i32,pub heat_id : String,pub heat_type : String,pub start_date : time::PrimitiveDateTime,pub track : i32,}pub struct HeatBorrowed < 'a >
+{ pub id : i32,pub heat_id : &'a str,pub heat_type : &'a str,pub start_date : time::PrimitiveDateTime,pub track : i32,} impl < 'a > From < HeatBorrowed <
+'a >> for Heat
 {
     fn
-    from(KartBorrowed { id,number,brand,model,horsepower,modified,} : KartBorrowed < 'a >)
-    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,} }
-}pub struct KartQuery < 'a, C : GenericClient, T, const N : usize >
+    from(HeatBorrowed { id,heat_id,heat_type,start_date,track,} : HeatBorrowed < 'a >)
+    -> Self { Self { id,heat_id: heat_id.into(),heat_type: heat_type.into(),start_date,track,} }
+}pub struct HeatQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartBorrowed,
-    mapper : fn(KartBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > KartQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> HeatBorrowed,
+    mapper : fn(HeatBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > HeatQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(KartBorrowed) -> R) -> KartQuery
+    pub fn map < R > (self, mapper : fn(HeatBorrowed) -> R) -> HeatQuery
     < 'a, C, R, N >
     {
-        KartQuery
+        HeatQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -764,27 +1043,27 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}#[derive( Debug, Clone, PartialEq, )] pub struct GetKartsStatsPerDay
-{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,pub start_date : time::PrimitiveDateTime,pub min_laptime : f64,pub avg_laptime : f64,pub median_laptime : f64,}pub struct GetKartsStatsPerDayBorrowed < 'a >
-{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,pub start_date : time::PrimitiveDateTime,pub min_laptime : f64,pub avg_laptime : f64,pub median_laptime : f64,} impl < 'a > From < GetKartsStatsPerDayBorrowed <
-'a >> for GetKartsStatsPerDay
+}#[derive( Debug, Clone, PartialEq, )] pub struct GetHeatWithStats
+{ pub heat_id : String,pub heat_type : String,pub start_time : time::PrimitiveDateTime,pub track : i32,pub amount_of_laps : i32,pub amount_of_drivers : i32,pub fastest_lap_time : f64,pub average_lap_time : f64,}pub struct GetHeatWithStatsBorrowed < 'a >
+{ pub heat_id : &'a str,pub heat_type : &'a str,pub start_time : time::PrimitiveDateTime,pub track : i32,pub amount_of_laps : i32,pub amount_of_drivers : i32,pub fastest_lap_time : f64,pub average_lap_time : f64,} impl < 'a > From < GetHeatWithStatsBorrowed <
+'a >> for GetHeatWithStats
 {
     fn
-    from(GetKartsStatsPerDayBorrowed { id,number,brand,model,horsepower,modified,start_date,min_laptime,avg_laptime,median_laptime,} : GetKartsStatsPerDayBorrowed < 'a >)
-    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,start_date,min_laptime,avg_laptime,median_laptime,} }
-}pub struct GetKartsStatsPerDayQuery < 'a, C : GenericClient, T, const N : usize >
+    from(GetHeatWithStatsBorrowed { heat_id,heat_type,start_time,track,amount_of_laps,amount_of_drivers,fastest_lap_time,average_lap_time,} : GetHeatWithStatsBorrowed < 'a >)
+    -> Self { Self { heat_id: heat_id.into(),heat_type: heat_type.into(),start_time,track,amount_of_laps,amount_of_drivers,fastest_lap_time,average_lap_time,} }
+}pub struct GetHeatWithStatsQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> GetKartsStatsPerDayBorrowed,
-    mapper : fn(GetKartsStatsPerDayBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > GetKartsStatsPerDayQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> GetHeatWithStatsBorrowed,
+    mapper : fn(GetHeatWithStatsBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > GetHeatWithStatsQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(GetKartsStatsPerDayBorrowed) -> R) -> GetKartsStatsPerDayQuery
+    pub fn map < R > (self, mapper : fn(GetHeatWithStatsBorrowed) -> R) -> GetHeatWithStatsQuery
     < 'a, C, R, N >
     {
-        GetKartsStatsPerDayQuery
+        GetHeatWithStatsQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -811,27 +1090,19 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}#[derive( Debug, Clone, PartialEq, )] pub struct KartWithStats
-{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,pub lap_count : i32,pub driver_count : i32,}pub struct KartWithStatsBorrowed < 'a >
-{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,pub lap_count : i32,pub driver_count : i32,} impl < 'a > From < KartWithStatsBorrowed <
-'a >> for KartWithStats
-{
-    fn
-    from(KartWithStatsBorrowed { id,number,brand,model,horsepower,modified,lap_count,driver_count,} : KartWithStatsBorrowed < 'a >)
-    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,lap_count,driver_count,} }
-}pub struct KartWithStatsQuery < 'a, C : GenericClient, T, const N : usize >
+}pub struct I64Query < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartWithStatsBorrowed,
-    mapper : fn(KartWithStatsBorrowed) -> T,
-} impl < 'a, C, T : 'a, const N : usize > KartWithStatsQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i64,
+    mapper : fn(i64) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I64Query < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(KartWithStatsBorrowed) -> R) -> KartWithStatsQuery
+    pub fn map < R > (self, mapper : fn(i64) -> R) -> I64Query
     < 'a, C, R, N >
     {
-        KartWithStatsQuery
+        I64Query
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -858,19 +1129,27 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}pub struct I32Query < 'a, C : GenericClient, T, const N : usize >
+}#[derive( Debug, Clone, PartialEq, )] pub struct DeleteHeat
+{ pub id : i32,pub heat_id : String,pub heat_type : String,pub start_date : time::PrimitiveDateTime,pub track : i32,}pub struct DeleteHeatBorrowed < 'a >
+{ pub id : i32,pub heat_id : &'a str,pub heat_type : &'a str,pub start_date : time::PrimitiveDateTime,pub track : i32,} impl < 'a > From < DeleteHeatBorrowed <
+'a >> for DeleteHeat
+{
+    fn
+    from(DeleteHeatBorrowed { id,heat_id,heat_type,start_date,track,} : DeleteHeatBorrowed < 'a >)
+    -> Self { Self { id,heat_id: heat_id.into(),heat_type: heat_type.into(),start_date,track,} }
+}pub struct DeleteHeatQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i32,
-    mapper : fn(i32) -> T,
-} impl < 'a, C, T : 'a, const N : usize > I32Query < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DeleteHeatBorrowed,
+    mapper : fn(DeleteHeatBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > DeleteHeatQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(i32) -> R) -> I32Query
+    pub fn map < R > (self, mapper : fn(DeleteHeatBorrowed) -> R) -> DeleteHeatQuery
     < 'a, C, R, N >
     {
-        I32Query
+        DeleteHeatQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -897,200 +1176,1808 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}pub fn get_all_karts() -> GetAllKartsStmt
-{ GetAllKartsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars")) } pub
-struct GetAllKartsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetAllKartsStmt { pub fn bind < 'a, C : GenericClient, >
+}pub fn get_all_heats() -> GetAllHeatsStmt
+{ GetAllHeatsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session")) } pub
+struct GetAllHeatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllHeatsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-) -> KartQuery < 'a, C,
-Kart, 0 >
+) -> HeatQuery < 'a, C,
+Heat, 0 >
 {
-    KartQuery
+    HeatQuery
     {
         client, params : [], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
     }
-} }pub fn get_kart_by_id() -> GetKartByIdStmt
-{ GetKartByIdStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE id = $1")) } pub
-struct GetKartByIdStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartByIdStmt { pub fn bind < 'a, C : GenericClient, >
+} }pub fn get_heat_from_id() -> GetHeatFromIdStmt
+{ GetHeatFromIdStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session where id = $1")) } pub
+struct GetHeatFromIdStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatFromIdStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-id : & 'a i32,) -> KartQuery < 'a, C,
-Kart, 1 >
+id : & 'a i32,) -> HeatQuery < 'a, C,
+Heat, 1 >
 {
-    KartQuery
+    HeatQuery
     {
         client, params : [id,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
     }
-} }pub fn get_karts_by_ids() -> GetKartsByIdsStmt
-{ GetKartsByIdsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE id = any($1::int[])")) } pub
-struct GetKartsByIdsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartsByIdsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+} }pub fn get_heats_from_ids() -> GetHeatsFromIdsStmt
+{ GetHeatsFromIdsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session WHERE id = any($1::int[])")) } pub
+struct GetHeatsFromIdsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatsFromIdsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
 (& 'a mut self, client : & 'a  C,
-ids : & 'a T1,) -> KartQuery < 'a, C,
-Kart, 1 >
+ids : & 'a T1,) -> HeatQuery < 'a, C,
+Heat, 1 >
 {
-    KartQuery
+    HeatQuery
     {
         client, params : [ids,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
     }
-} }pub fn get_kart_by_number() -> GetKartByNumberStmt
-{ GetKartByNumberStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE number = $1")) } pub
-struct GetKartByNumberStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartByNumberStmt { pub fn bind < 'a, C : GenericClient, >
+} }pub fn get_heat_from_name() -> GetHeatFromNameStmt
+{ GetHeatFromNameStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session where heat_id = $1::text")) } pub
+struct GetHeatFromNameStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatFromNameStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-number : & 'a i32,) -> KartQuery < 'a, C,
-Kart, 1 >
+name : & 'a T1,) -> HeatQuery < 'a, C,
+Heat, 1 >
 {
-    KartQuery
+    HeatQuery
     {
-        client, params : [number,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        client, params : [name,], stmt : & mut self.0, extractor :
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
     }
-} }pub fn get_karts_by_numbers() -> GetKartsByNumbersStmt
-{ GetKartsByNumbersStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE number = any($1::int[])")) } pub
-struct GetKartsByNumbersStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartsByNumbersStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+} }pub fn get_all_heats_with_stats() -> GetAllHeatsWithStatsStmt
+{ GetAllHeatsWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
+    h.heat_id,
+    h.heat_type,
+    h.start_date as start_time,
+    h.track,
+    CAST(count(l.*) as INT) as amount_of_laps,
+    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
+    min(l.lap_time) as fastest_lap_time,
+    avg(l.lap_time) as average_lap_time
+from public.session h
+         inner join public.laps l on h.id = l.heat
+where ($1::int is null or h.track = $1)
+  and (not $2::bool or l.classification = 'clean')
+group by h.id")) } pub
+struct GetAllHeatsWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllHeatsWithStatsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-numbers : & 'a T1,) -> KartQuery < 'a, C,
-Kart, 1 >
+track : & 'a Option<i32>,clean_only : & 'a bool,) -> GetHeatWithStatsQuery < 'a, C,
+GetHeatWithStats, 2 >
 {
-    KartQuery
+    GetHeatWithStatsQuery
     {
-        client, params : [numbers,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        client, params : [track,clean_only,], stmt : & mut self.0, extractor :
+        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),track : row.get(3),amount_of_laps : row.get(4),amount_of_drivers : row.get(5),fastest_lap_time : row.get(6),average_lap_time : row.get(7),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
     }
-} }pub fn get_kart_from_lap() -> GetKartFromLapStmt
-{ GetKartFromLapStmt(cornucopia_async :: private :: Stmt :: new("SELECT k.* FROM public.cars k
-    INNER JOIN public.laps l on k.id = l.kart_id
-WHERE l.id = $1")) } pub
-struct GetKartFromLapStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartFromLapStmt { pub fn bind < 'a, C : GenericClient, >
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, GetAllHeatsWithStatsParams < >, GetHeatWithStatsQuery < 'a,
+C, GetHeatWithStats, 2 >, C > for GetAllHeatsWithStatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetAllHeatsWithStatsParams < >) -> GetHeatWithStatsQuery < 'a, C,
+    GetHeatWithStats, 2 >
+    { self.bind(client, & params.track,& params.clean_only,) }
+}pub fn get_heat_with_stats_paginated() -> GetHeatWithStatsPaginatedStmt
+{ GetHeatWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt :: new("select
+    h.heat_id,
+    h.heat_type,
+    h.start_date as start_time,
+    h.track,
+    CAST(count(l.*) as INT) as amount_of_laps,
+    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
+    min(l.lap_time) as fastest_lap_time,
+    avg(l.lap_time) as average_lap_time
+from public.session h
+         inner join public.laps l on h.id = l.heat
+where ($1::int is null or h.track = $1)
+  and (not $2::bool or l.classification = 'clean')
+group by h.id, start_time
+limit $3
+offset $4")) } pub
+struct GetHeatWithStatsPaginatedStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatWithStatsPaginatedStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-lap : & 'a i32,) -> KartQuery < 'a, C,
-Kart, 1 >
+track : & 'a Option<i32>,clean_only : & 'a bool,limit : & 'a i64,offset : & 'a i64,) -> GetHeatWithStatsQuery < 'a, C,
+GetHeatWithStats, 4 >
 {
-    KartQuery
+    GetHeatWithStatsQuery
     {
-        client, params : [lap,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        client, params : [track,clean_only,limit,offset,], stmt : & mut self.0, extractor :
+        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),track : row.get(3),amount_of_laps : row.get(4),amount_of_drivers : row.get(5),fastest_lap_time : row.get(6),average_lap_time : row.get(7),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
     }
-} }pub fn get_karts_from_laps() -> GetKartsFromLapsStmt
-{ GetKartsFromLapsStmt(cornucopia_async :: private :: Stmt :: new("SELECT k.* FROM public.cars k
-    INNER JOIN public.laps l on k.id = l.kart_id
-WHERE l.id = any($1::int[])")) } pub
-struct GetKartsFromLapsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartsFromLapsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, GetHeatWithStatsPaginatedParams < >, GetHeatWithStatsQuery < 'a,
+C, GetHeatWithStats, 4 >, C > for GetHeatWithStatsPaginatedStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetHeatWithStatsPaginatedParams < >) -> GetHeatWithStatsQuery < 'a, C,
+    GetHeatWithStats, 4 >
+    { self.bind(client, & params.track,& params.clean_only,& params.limit,& params.offset,) }
+}pub fn get_heat_with_stats() -> GetHeatWithStatsStmt
+{ GetHeatWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
+    h.heat_id,
+    h.heat_type,
+    h.start_date as start_time,
+    h.track,
+    CAST(count(l.*) as INT) as amount_of_laps,
+    CAST(count(DISTINCT l.driver) AS INT) as amount_of_drivers,
+    min(l.lap_time) as fastest_lap_time,
+    avg(l.lap_time) as average_lap_time
+from public.session h
+         inner join public.laps l on h.id = l.heat
+where h.heat_id = $1
+  and (not $2::bool or l.classification = 'clean')
+group by h.id")) } pub
+struct GetHeatWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetHeatWithStatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-laps : & 'a T1,) -> KartQuery < 'a, C,
-Kart, 1 >
+heat : & 'a T1,clean_only : & 'a bool,) -> GetHeatWithStatsQuery < 'a, C,
+GetHeatWithStats, 2 >
 {
-    KartQuery
+    GetHeatWithStatsQuery
     {
-        client, params : [laps,], stmt : & mut self.0, extractor :
-        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+        client, params : [heat,clean_only,], stmt : & mut self.0, extractor :
+        | row | { GetHeatWithStatsBorrowed { heat_id : row.get(0),heat_type : row.get(1),start_time : row.get(2),track : row.get(3),amount_of_laps : row.get(4),amount_of_drivers : row.get(5),fastest_lap_time : row.get(6),average_lap_time : row.get(7),} }, mapper : | it | { <GetHeatWithStats>::from(it) },
     }
-} }pub fn get_karts_stats_per_day() -> GetKartsStatsPerDayStmt
-{ GetKartsStatsPerDayStmt(cornucopia_async :: private :: Stmt :: new("select
-    k.*,
-    h.start_date,
-    min(lap_time) as min_laptime,
-    avg(lap_time) as avg_laptime,
-    percentile_cont(0.5) WITHIN GROUP (ORDER BY lap_time) as median_laptime
-from public.cars k
-         inner join public.laps l on k.id = l.kart_id
-         inner join public.session h on h.id = l.heat
-group by k.id, k.number, h.start_date")) } pub
-struct GetKartsStatsPerDayStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartsStatsPerDayStmt { pub fn bind < 'a, C : GenericClient, >
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, GetHeatWithStatsParams < T1,>, GetHeatWithStatsQuery < 'a,
+C, GetHeatWithStats, 2 >, C > for GetHeatWithStatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetHeatWithStatsParams < T1,>) -> GetHeatWithStatsQuery < 'a, C,
+    GetHeatWithStats, 2 >
+    { self.bind(client, & params.heat,& params.clean_only,) }
+}pub fn get_all_chronologicaly() -> GetAllChronologicalyStmt
+{ GetAllChronologicalyStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session order by start_date, id")) } pub
+struct GetAllChronologicalyStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllChronologicalyStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-) -> GetKartsStatsPerDayQuery < 'a, C,
-GetKartsStatsPerDay, 0 >
+) -> HeatQuery < 'a, C,
+Heat, 0 >
 {
-    GetKartsStatsPerDayQuery
+    HeatQuery
     {
         client, params : [], stmt : & mut self.0, extractor :
-        | row | { GetKartsStatsPerDayBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),start_date : row.get(6),min_laptime : row.get(7),avg_laptime : row.get(8),median_laptime : row.get(9),} }, mapper : | it | { <GetKartsStatsPerDay>::from(it) },
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
     }
-} }pub fn get_kart_with_stats() -> GetKartWithStatsStmt
-{ GetKartWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
-    k.*,
-    CAST(count(l.id) AS INT) as lap_count,
-    CAST(count(DISTINCT l.driver) AS INT) as driver_count
-from public.cars k
-inner join public.laps l on k.id = l.kart_id
-where k.number = $1
-group by k.id")) } pub
-struct GetKartWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetKartWithStatsStmt { pub fn bind < 'a, C : GenericClient, >
+} }pub fn get_next_heat_to_replay() -> GetNextHeatToReplayStmt
+{ GetNextHeatToReplayStmt(cornucopia_async :: private :: Stmt :: new("select
+    s.*
+from public.session s, public.rating_replays r
+where r.id = $1
+  and ((r.last_session is null and (r.from_date is null or s.start_date >= r.from_date))
+    or (s.start_date, s.id) > (r.last_start_date, r.last_session))
+order by s.start_date, s.id
+limit 1")) } pub
+struct GetNextHeatToReplayStmt(cornucopia_async :: private :: Stmt) ; impl
+GetNextHeatToReplayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+replay : & 'a i32,) -> HeatQuery < 'a, C,
+Heat, 1 >
+{
+    HeatQuery
+    {
+        client, params : [replay,], stmt : & mut self.0, extractor :
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
+    }
+} }pub fn count_heats_driven_after() -> CountHeatsDrivenAfterStmt
+{ CountHeatsDrivenAfterStmt(cornucopia_async :: private :: Stmt :: new("select
+    count(*)
+from public.session
+where start_date > (select start_date from public.session where id = $1)")) } pub
+struct CountHeatsDrivenAfterStmt(cornucopia_async :: private :: Stmt) ; impl
+CountHeatsDrivenAfterStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> I64Query < 'a, C,
+i64, 1 >
+{
+    I64Query
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }pub fn delete_heat() -> DeleteHeatStmt
+{ DeleteHeatStmt(cornucopia_async :: private :: Stmt :: new("delete from public.session where id = $1 returning *")) } pub
+struct DeleteHeatStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteHeatStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> DeleteHeatQuery < 'a, C,
+DeleteHeat, 1 >
+{
+    DeleteHeatQuery
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { DeleteHeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <DeleteHeat>::from(it) },
+    }
+} }pub fn create_new_heat() -> CreateNewHeatStmt
+{ CreateNewHeatStmt(cornucopia_async :: private :: Stmt :: new("insert into public.session (heat_id, heat_type, start_date, track) VALUES ($1, $2, $3, $4) returning  *")) } pub
+struct CreateNewHeatStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateNewHeatStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+heat_id : & 'a T1,heat_type : & 'a T2,start_date : & 'a time::PrimitiveDateTime,track : & 'a i32,) -> HeatQuery < 'a, C,
+Heat, 4 >
+{
+    HeatQuery
+    {
+        client, params : [heat_id,heat_type,start_date,track,], stmt : & mut self.0, extractor :
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, CreateNewHeatParams < T1,T2,>, HeatQuery < 'a,
+C, Heat, 4 >, C > for CreateNewHeatStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CreateNewHeatParams < T1,T2,>) -> HeatQuery < 'a, C,
+    Heat, 4 >
+    { self.bind(client, & params.heat_id,& params.heat_type,& params.start_date,& params.track,) }
+}pub fn create_heat_if_not_exists() -> CreateHeatIfNotExistsStmt
+{ CreateHeatIfNotExistsStmt(cornucopia_async :: private :: Stmt :: new("insert into public.session (heat_id, heat_type, start_date, track) VALUES ($1, $2, $3, $4)
+on conflict (heat_id) do nothing
+returning *")) } pub
+struct CreateHeatIfNotExistsStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateHeatIfNotExistsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+heat_id : & 'a T1,heat_type : & 'a T2,start_date : & 'a time::PrimitiveDateTime,track : & 'a i32,) -> HeatQuery < 'a, C,
+Heat, 4 >
+{
+    HeatQuery
+    {
+        client, params : [heat_id,heat_type,start_date,track,], stmt : & mut self.0, extractor :
+        | row | { HeatBorrowed { id : row.get(0),heat_id : row.get(1),heat_type : row.get(2),start_date : row.get(3),track : row.get(4),} }, mapper : | it | { <Heat>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, CreateHeatIfNotExistsParams < T1,T2,>, HeatQuery < 'a,
+C, Heat, 4 >, C > for CreateHeatIfNotExistsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CreateHeatIfNotExistsParams < T1,T2,>) -> HeatQuery < 'a, C,
+    Heat, 4 >
+    { self.bind(client, & params.heat_id,& params.heat_type,& params.start_date,& params.track,) }
+}}pub mod kart
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive(Clone,Copy, Debug)] pub struct GetKartsStatsPerDayParams < > { pub track : i32,pub clean_only : bool,}#[derive(Clone,Copy, Debug)] pub struct GetKartWithStatsParams < > { pub kart_number : i32,pub track : Option<i32>,pub clean_only : bool,}#[derive( Debug)] pub struct GetAllKartsWithStatsParams < T1 : cornucopia_async::StringSql,> { pub track : Option<i32>,pub clean_only : bool,pub order_column : T1,}#[derive( Debug)] pub struct CreateKartParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub number : i32,pub brand : T1,pub model : T2,pub horsepower : i32,pub modified : bool,}#[derive( Debug)] pub struct CreateKartIfNotExistsParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub number : i32,pub brand : T1,pub model : T2,pub horsepower : i32,pub modified : bool,}#[derive( Debug, Clone, PartialEq, )] pub struct Kart
+{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,}pub struct KartBorrowed < 'a >
+{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,} impl < 'a > From < KartBorrowed <
+'a >> for Kart
+{
+    fn
+    from(KartBorrowed { id,number,brand,model,horsepower,modified,} : KartBorrowed < 'a >)
+    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,} }
+}pub struct KartQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartBorrowed,
+    mapper : fn(KartBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > KartQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(KartBorrowed) -> R) -> KartQuery
+    < 'a, C, R, N >
+    {
+        KartQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, )] pub struct GetKartsStatsPerDay
+{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,pub start_date : time::PrimitiveDateTime,pub min_laptime : f64,pub avg_laptime : f64,pub median_laptime : f64,}pub struct GetKartsStatsPerDayBorrowed < 'a >
+{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,pub start_date : time::PrimitiveDateTime,pub min_laptime : f64,pub avg_laptime : f64,pub median_laptime : f64,} impl < 'a > From < GetKartsStatsPerDayBorrowed <
+'a >> for GetKartsStatsPerDay
+{
+    fn
+    from(GetKartsStatsPerDayBorrowed { id,number,brand,model,horsepower,modified,start_date,min_laptime,avg_laptime,median_laptime,} : GetKartsStatsPerDayBorrowed < 'a >)
+    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,start_date,min_laptime,avg_laptime,median_laptime,} }
+}pub struct GetKartsStatsPerDayQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> GetKartsStatsPerDayBorrowed,
+    mapper : fn(GetKartsStatsPerDayBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > GetKartsStatsPerDayQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(GetKartsStatsPerDayBorrowed) -> R) -> GetKartsStatsPerDayQuery
+    < 'a, C, R, N >
+    {
+        GetKartsStatsPerDayQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, )] pub struct KartWithStats
+{ pub id : i32,pub number : i32,pub brand : String,pub model : String,pub horsepower : i32,pub modified : bool,pub lap_count : i32,pub driver_count : i32,}pub struct KartWithStatsBorrowed < 'a >
+{ pub id : i32,pub number : i32,pub brand : &'a str,pub model : &'a str,pub horsepower : i32,pub modified : bool,pub lap_count : i32,pub driver_count : i32,} impl < 'a > From < KartWithStatsBorrowed <
+'a >> for KartWithStats
+{
+    fn
+    from(KartWithStatsBorrowed { id,number,brand,model,horsepower,modified,lap_count,driver_count,} : KartWithStatsBorrowed < 'a >)
+    -> Self { Self { id,number,brand: brand.into(),model: model.into(),horsepower,modified,lap_count,driver_count,} }
+}pub struct KartWithStatsQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartWithStatsBorrowed,
+    mapper : fn(KartWithStatsBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > KartWithStatsQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(KartWithStatsBorrowed) -> R) -> KartWithStatsQuery
+    < 'a, C, R, N >
+    {
+        KartWithStatsQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub struct I32Query < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i32,
+    mapper : fn(i32) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I32Query < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(i32) -> R) -> I32Query
+    < 'a, C, R, N >
+    {
+        I32Query
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_all_karts() -> GetAllKartsStmt
+{ GetAllKartsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars")) } pub
+struct GetAllKartsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllKartsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> KartQuery < 'a, C,
+Kart, 0 >
+{
+    KartQuery
+    {
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_kart_by_id() -> GetKartByIdStmt
+{ GetKartByIdStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE id = $1")) } pub
+struct GetKartByIdStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartByIdStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_karts_by_ids() -> GetKartsByIdsStmt
+{ GetKartsByIdsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE id = any($1::int[])")) } pub
+struct GetKartsByIdsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartsByIdsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+ids : & 'a T1,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [ids,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_kart_by_number() -> GetKartByNumberStmt
+{ GetKartByNumberStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE number = $1")) } pub
+struct GetKartByNumberStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartByNumberStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+number : & 'a i32,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [number,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_karts_by_numbers() -> GetKartsByNumbersStmt
+{ GetKartsByNumbersStmt(cornucopia_async :: private :: Stmt :: new("select * from public.cars WHERE number = any($1::int[])")) } pub
+struct GetKartsByNumbersStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartsByNumbersStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+numbers : & 'a T1,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [numbers,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_kart_from_lap() -> GetKartFromLapStmt
+{ GetKartFromLapStmt(cornucopia_async :: private :: Stmt :: new("SELECT k.* FROM public.cars k
+    INNER JOIN public.laps l on k.id = l.kart_id
+WHERE l.id = $1")) } pub
+struct GetKartFromLapStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartFromLapStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+lap : & 'a i32,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [lap,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_karts_from_laps() -> GetKartsFromLapsStmt
+{ GetKartsFromLapsStmt(cornucopia_async :: private :: Stmt :: new("SELECT k.* FROM public.cars k
+    INNER JOIN public.laps l on k.id = l.kart_id
+WHERE l.id = any($1::int[])")) } pub
+struct GetKartsFromLapsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartsFromLapsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+laps : & 'a T1,) -> KartQuery < 'a, C,
+Kart, 1 >
+{
+    KartQuery
+    {
+        client, params : [laps,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }pub fn get_karts_stats_per_day() -> GetKartsStatsPerDayStmt
+{ GetKartsStatsPerDayStmt(cornucopia_async :: private :: Stmt :: new("select
+    k.*,
+    h.start_date,
+    min(lap_time) as min_laptime,
+    avg(lap_time) as avg_laptime,
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY lap_time) as median_laptime
+from public.cars k
+         inner join public.laps l on k.id = l.kart_id
+         inner join public.session h on h.id = l.heat
+where h.track = $1
+  and (not $2::bool or l.classification = 'clean')
+group by k.id, k.number, h.start_date")) } pub
+struct GetKartsStatsPerDayStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartsStatsPerDayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+track : & 'a i32,clean_only : & 'a bool,) -> GetKartsStatsPerDayQuery < 'a, C,
+GetKartsStatsPerDay, 2 >
+{
+    GetKartsStatsPerDayQuery
+    {
+        client, params : [track,clean_only,], stmt : & mut self.0, extractor :
+        | row | { GetKartsStatsPerDayBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),start_date : row.get(6),min_laptime : row.get(7),avg_laptime : row.get(8),median_laptime : row.get(9),} }, mapper : | it | { <GetKartsStatsPerDay>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, GetKartsStatsPerDayParams < >, GetKartsStatsPerDayQuery < 'a,
+C, GetKartsStatsPerDay, 2 >, C > for GetKartsStatsPerDayStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetKartsStatsPerDayParams < >) -> GetKartsStatsPerDayQuery < 'a, C,
+    GetKartsStatsPerDay, 2 >
+    { self.bind(client, & params.track,& params.clean_only,) }
+}pub fn get_kart_with_stats() -> GetKartWithStatsStmt
+{ GetKartWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
+    k.*,
+    CAST(count(l.id) AS INT) as lap_count,
+    CAST(count(DISTINCT l.driver) AS INT) as driver_count
+from public.cars k
+inner join public.laps l on k.id = l.kart_id
+inner join public.session h on h.id = l.heat
+where k.number = $1 and ($2::int is null or h.track = $2)
+  and (not $3::bool or l.classification = 'clean')
+group by k.id")) } pub
+struct GetKartWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartWithStatsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+kart_number : & 'a i32,track : & 'a Option<i32>,clean_only : & 'a bool,) -> KartWithStatsQuery < 'a, C,
+KartWithStats, 3 >
+{
+    KartWithStatsQuery
+    {
+        client, params : [kart_number,track,clean_only,], stmt : & mut self.0, extractor :
+        | row | { KartWithStatsBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),lap_count : row.get(6),driver_count : row.get(7),} }, mapper : | it | { <KartWithStats>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, GetKartWithStatsParams < >, KartWithStatsQuery < 'a,
+C, KartWithStats, 3 >, C > for GetKartWithStatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetKartWithStatsParams < >) -> KartWithStatsQuery < 'a, C,
+    KartWithStats, 3 >
+    { self.bind(client, & params.kart_number,& params.track,& params.clean_only,) }
+}pub fn get_all_karts_with_stats() -> GetAllKartsWithStatsStmt
+{ GetAllKartsWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
+    k.*,
+    CAST(count(l.id) AS INT) as lap_count,
+    CAST(count(DISTINCT l.driver) AS INT) as driver_count
+from public.cars k
+         inner join public.laps l on k.id = l.kart_id
+         inner join public.session h on h.id = l.heat
+where ($1::int is null or h.track = $1)
+  and (not $2::bool or l.classification = 'clean')
+group by k.id
+order by $3")) } pub
+struct GetAllKartsWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllKartsWithStatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+track : & 'a Option<i32>,clean_only : & 'a bool,order_column : & 'a T1,) -> KartWithStatsQuery < 'a, C,
+KartWithStats, 3 >
+{
+    KartWithStatsQuery
+    {
+        client, params : [track,clean_only,order_column,], stmt : & mut self.0, extractor :
+        | row | { KartWithStatsBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),lap_count : row.get(6),driver_count : row.get(7),} }, mapper : | it | { <KartWithStats>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, GetAllKartsWithStatsParams < T1,>, KartWithStatsQuery < 'a,
+C, KartWithStats, 3 >, C > for GetAllKartsWithStatsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetAllKartsWithStatsParams < T1,>) -> KartWithStatsQuery < 'a, C,
+    KartWithStats, 3 >
+    { self.bind(client, & params.track,& params.clean_only,& params.order_column,) }
+}pub fn create_kart() -> CreateKartStmt
+{ CreateKartStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO public.cars (number, brand, model, horsepower, modified) values ($1, $2, $3, $4, $5) RETURNING id")) } pub
+struct CreateKartStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateKartStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+number : & 'a i32,brand : & 'a T1,model : & 'a T2,horsepower : & 'a i32,modified : & 'a bool,) -> I32Query < 'a, C,
+i32, 5 >
+{
+    I32Query
+    {
+        client, params : [number,brand,model,horsepower,modified,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, CreateKartParams < T1,T2,>, I32Query < 'a,
+C, i32, 5 >, C > for CreateKartStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CreateKartParams < T1,T2,>) -> I32Query < 'a, C,
+    i32, 5 >
+    { self.bind(client, & params.number,& params.brand,& params.model,& params.horsepower,& params.modified,) }
+}pub fn create_kart_if_not_exists() -> CreateKartIfNotExistsStmt
+{ CreateKartIfNotExistsStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO public.cars (number, brand, model, horsepower, modified) values ($1, $2, $3, $4, $5)
+on conflict (number) do nothing
+RETURNING *")) } pub
+struct CreateKartIfNotExistsStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateKartIfNotExistsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+number : & 'a i32,brand : & 'a T1,model : & 'a T2,horsepower : & 'a i32,modified : & 'a bool,) -> KartQuery < 'a, C,
+Kart, 5 >
+{
+    KartQuery
+    {
+        client, params : [number,brand,model,horsepower,modified,], stmt : & mut self.0, extractor :
+        | row | { KartBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),} }, mapper : | it | { <Kart>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, CreateKartIfNotExistsParams < T1,T2,>, KartQuery < 'a,
+C, Kart, 5 >, C > for CreateKartIfNotExistsStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    CreateKartIfNotExistsParams < T1,T2,>) -> KartQuery < 'a, C,
+    Kart, 5 >
+    { self.bind(client, & params.number,& params.brand,& params.model,& params.horsepower,& params.modified,) }
+}}pub mod kart_offsets
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct InsertKartOffsetsBulkParams < T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = time::PrimitiveDateTime>,T3 : cornucopia_async::ArraySql<Item = f64>,T4 : cornucopia_async::ArraySql<Item = i32>,> { pub karts : T1,pub periods : T2,pub time_offsets : T3,pub amounts_of_laps : T4,}#[derive( Debug, Clone, PartialEq, Copy)] pub struct KartOffset
+{ pub id : i32,pub kart : i32,pub period : time::PrimitiveDateTime,pub time_offset : f64,pub amount_of_laps : i32,}pub struct KartOffsetQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartOffset,
+    mapper : fn(KartOffset) -> T,
+} impl < 'a, C, T : 'a, const N : usize > KartOffsetQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(KartOffset) -> R) -> KartOffsetQuery
+    < 'a, C, R, N >
+    {
+        KartOffsetQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, Copy)] pub struct KartObservation
+{ pub heat : i32,pub driver : i32,pub kart : i32,pub period : time::PrimitiveDateTime,pub lap_time : f64,pub amount_of_laps : i32,}pub struct KartObservationQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> KartObservation,
+    mapper : fn(KartObservation) -> T,
+} impl < 'a, C, T : 'a, const N : usize > KartObservationQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(KartObservation) -> R) -> KartObservationQuery
+    < 'a, C, R, N >
+    {
+        KartObservationQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_kart_offsets_of_karts() -> GetKartOffsetsOfKartsStmt
+{ GetKartOffsetsOfKartsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.kart_offsets where kart = any($1::int[])")) } pub
+struct GetKartOffsetsOfKartsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartOffsetsOfKartsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+karts : & 'a T1,) -> KartOffsetQuery < 'a, C,
+KartOffset, 1 >
+{
+    KartOffsetQuery
+    {
+        client, params : [karts,], stmt : & mut self.0, extractor :
+        | row | { KartOffset { id : row.get(0),kart : row.get(1),period : row.get(2),time_offset : row.get(3),amount_of_laps : row.get(4),} }, mapper : | it | { <KartOffset>::from(it) },
+    }
+} }pub fn get_kart_observations() -> GetKartObservationsStmt
+{ GetKartObservationsStmt(cornucopia_async :: private :: Stmt :: new("select
+    l.heat,
+    l.driver,
+    l.kart_id as kart,
+    date_trunc('month', s.start_date) as period,
+    percentile_cont(0.5) WITHIN GROUP ( ORDER BY l.lap_time) as lap_time,
+    CAST(count(*) AS INT) as amount_of_laps
+from public.laps l
+         inner join public.session s on s.id = l.heat
+where l.classification = 'clean'
+group by l.heat, l.driver, l.kart_id, s.start_date")) } pub
+struct GetKartObservationsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetKartObservationsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> KartObservationQuery < 'a, C,
+KartObservation, 0 >
+{
+    KartObservationQuery
+    {
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { KartObservation { heat : row.get(0),driver : row.get(1),kart : row.get(2),period : row.get(3),lap_time : row.get(4),amount_of_laps : row.get(5),} }, mapper : | it | { <KartObservation>::from(it) },
+    }
+} }pub fn insert_kart_offsets_bulk() -> InsertKartOffsetsBulkStmt
+{ InsertKartOffsetsBulkStmt(cornucopia_async :: private :: Stmt :: new("insert into public.kart_offsets (kart, period, time_offset, amount_of_laps)
+select
+    unnest($1::int[]),
+    unnest($2::timestamp[]),
+    unnest($3::float[]),
+    unnest($4::int[])
+returning *")) } pub
+struct InsertKartOffsetsBulkStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertKartOffsetsBulkStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = time::PrimitiveDateTime>,T3 : cornucopia_async::ArraySql<Item = f64>,T4 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+karts : & 'a T1,periods : & 'a T2,time_offsets : & 'a T3,amounts_of_laps : & 'a T4,) -> KartOffsetQuery < 'a, C,
+KartOffset, 4 >
+{
+    KartOffsetQuery
+    {
+        client, params : [karts,periods,time_offsets,amounts_of_laps,], stmt : & mut self.0, extractor :
+        | row | { KartOffset { id : row.get(0),kart : row.get(1),period : row.get(2),time_offset : row.get(3),amount_of_laps : row.get(4),} }, mapper : | it | { <KartOffset>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = time::PrimitiveDateTime>,T3 : cornucopia_async::ArraySql<Item = f64>,T4 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
+Params < 'a, InsertKartOffsetsBulkParams < T1,T2,T3,T4,>, KartOffsetQuery < 'a,
+C, KartOffset, 4 >, C > for InsertKartOffsetsBulkStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertKartOffsetsBulkParams < T1,T2,T3,T4,>) -> KartOffsetQuery < 'a, C,
+    KartOffset, 4 >
+    { self.bind(client, & params.karts,& params.periods,& params.time_offsets,& params.amounts_of_laps,) }
+}pub fn delete_all_kart_offsets() -> DeleteAllKartOffsetsStmt
+{ DeleteAllKartOffsetsStmt(cornucopia_async :: private :: Stmt :: new("delete from public.kart_offsets")) } pub
+struct DeleteAllKartOffsetsStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteAllKartOffsetsStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }}pub mod laps
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct UpdateLapsClassificationParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,> { pub classification : T1,pub ids : T2,}#[derive(Clone,Copy, Debug)] pub struct MoveLapsToDriverParams < > { pub keep : i32,pub merged : i32,}#[derive( Debug)] pub struct InsertLapsBulkParams < T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,> { pub heats : T1,pub drivers : T2,pub laps_in_heat : T3,pub lap_times : T4,pub karts : T5,}#[derive(Clone,Copy, Debug)] pub struct InsertLapParams < > { pub heat : i32,pub driver : i32,pub lap_in_heat : i32,pub laptime : f64,pub kart_id : i32,}#[derive( Debug, Clone, PartialEq, )] pub struct Lap
+{ pub id : i32,pub heat : i32,pub driver : i32,pub lap_in_heat : i32,pub lap_time : f64,pub kart_id : i32,pub classification : String,}pub struct LapBorrowed < 'a >
+{ pub id : i32,pub heat : i32,pub driver : i32,pub lap_in_heat : i32,pub lap_time : f64,pub kart_id : i32,pub classification : &'a str,} impl < 'a > From < LapBorrowed <
+'a >> for Lap
+{
+    fn
+    from(LapBorrowed { id,heat,driver,lap_in_heat,lap_time,kart_id,classification,} : LapBorrowed < 'a >)
+    -> Self { Self { id,heat,driver,lap_in_heat,lap_time,kart_id,classification: classification.into(),} }
+}pub struct LapQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> LapBorrowed,
+    mapper : fn(LapBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > LapQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(LapBorrowed) -> R) -> LapQuery
+    < 'a, C, R, N >
+    {
+        LapQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_lap() -> GetLapStmt
+{ GetLapStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where id = $1")) } pub
+struct GetLapStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn get_laps_from_driver() -> GetLapsFromDriverStmt
+{ GetLapsFromDriverStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where driver = $1")) } pub
+struct GetLapsFromDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapsFromDriverStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [driver,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn get_laps_from_kart() -> GetLapsFromKartStmt
+{ GetLapsFromKartStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where kart_id = $1")) } pub
+struct GetLapsFromKartStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapsFromKartStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+kart : & 'a i32,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [kart,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn get_laps_from_drivers() -> GetLapsFromDriversStmt
+{ GetLapsFromDriversStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where driver = any($1::int[])")) } pub
+struct GetLapsFromDriversStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapsFromDriversStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+driver : & 'a T1,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [driver,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn get_laps_from_heat() -> GetLapsFromHeatStmt
+{ GetLapsFromHeatStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where heat = $1")) } pub
+struct GetLapsFromHeatStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapsFromHeatStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+heat : & 'a i32,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [heat,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn get_laps_from_heats() -> GetLapsFromHeatsStmt
+{ GetLapsFromHeatsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where heat = any($1::int[])")) } pub
+struct GetLapsFromHeatsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetLapsFromHeatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+heats : & 'a T1,) -> LapQuery < 'a, C,
+Lap, 1 >
+{
+    LapQuery
+    {
+        client, params : [heats,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }pub fn update_laps_classification() -> UpdateLapsClassificationStmt
+{ UpdateLapsClassificationStmt(cornucopia_async :: private :: Stmt :: new("update public.laps set classification = $1 where id = any($2::int[])
+returning *")) } pub
+struct UpdateLapsClassificationStmt(cornucopia_async :: private :: Stmt) ; impl
+UpdateLapsClassificationStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+classification : & 'a T1,ids : & 'a T2,) -> LapQuery < 'a, C,
+Lap, 2 >
+{
+    LapQuery
+    {
+        client, params : [classification,ids,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
+Params < 'a, UpdateLapsClassificationParams < T1,T2,>, LapQuery < 'a,
+C, Lap, 2 >, C > for UpdateLapsClassificationStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    UpdateLapsClassificationParams < T1,T2,>) -> LapQuery < 'a, C,
+    Lap, 2 >
+    { self.bind(client, & params.classification,& params.ids,) }
+}pub fn delete_laps_from_heat() -> DeleteLapsFromHeatStmt
+{ DeleteLapsFromHeatStmt(cornucopia_async :: private :: Stmt :: new("delete from public.laps where heat = $1")) } pub
+struct DeleteLapsFromHeatStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteLapsFromHeatStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+heat : & 'a i32,) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & [heat,]) .await
+} }pub fn move_laps_to_driver() -> MoveLapsToDriverStmt
+{ MoveLapsToDriverStmt(cornucopia_async :: private :: Stmt :: new("update public.laps set driver = $1 where driver = $2")) } pub
+struct MoveLapsToDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+MoveLapsToDriverStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+keep : & 'a i32,merged : & 'a i32,) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & [keep,merged,]) .await
+} }impl < 'a, C : GenericClient + Send + Sync, >
+cornucopia_async :: Params < 'a, MoveLapsToDriverParams < >, std :: pin :: Pin < Box < dyn futures :: Future < Output = Result <
+u64, tokio_postgres :: Error > > + Send + 'a > >, C > for MoveLapsToDriverStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    MoveLapsToDriverParams < >) -> std :: pin :: Pin < Box < dyn futures :: Future < Output = Result <
+u64, tokio_postgres :: Error > > + Send + 'a > >
+    { Box :: pin(self.bind(client, & params.keep,& params.merged,)) }
+}pub fn insert_laps_bulk() -> InsertLapsBulkStmt
+{ InsertLapsBulkStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO
+    public.laps (heat, driver, lap_in_heat, lap_time, kart_id)
+select
+    unnest($1::int[]),
+    unnest($2::int[]),
+    unnest($3::int[]),
+    unnest($4::float[]),
+    unnest($5::int[])
+returning *")) } pub
+struct InsertLapsBulkStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertLapsBulkStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,>
+(& 'a mut self, client : & 'a  C,
+heats : & 'a T1,drivers : & 'a T2,laps_in_heat : & 'a T3,lap_times : & 'a T4,karts : & 'a T5,) -> LapQuery < 'a, C,
+Lap, 5 >
+{
+    LapQuery
+    {
+        client, params : [heats,drivers,laps_in_heat,lap_times,karts,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
+Params < 'a, InsertLapsBulkParams < T1,T2,T3,T4,T5,>, LapQuery < 'a,
+C, Lap, 5 >, C > for InsertLapsBulkStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertLapsBulkParams < T1,T2,T3,T4,T5,>) -> LapQuery < 'a, C,
+    Lap, 5 >
+    { self.bind(client, & params.heats,& params.drivers,& params.laps_in_heat,& params.lap_times,& params.karts,) }
+}pub fn insert_lap() -> InsertLapStmt
+{ InsertLapStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO
+    public.laps (heat, driver, lap_in_heat, lap_time, kart_id)
+VALUES ($1, $2, $3, $4, $5)
+returning *")) } pub
+struct InsertLapStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertLapStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+heat : & 'a i32,driver : & 'a i32,lap_in_heat : & 'a i32,laptime : & 'a f64,kart_id : & 'a i32,) -> LapQuery < 'a, C,
+Lap, 5 >
+{
+    LapQuery
+    {
+        client, params : [heat,driver,lap_in_heat,laptime,kart_id,], stmt : & mut self.0, extractor :
+        | row | { LapBorrowed { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),classification : row.get(6),} }, mapper : | it | { <Lap>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, InsertLapParams < >, LapQuery < 'a,
+C, Lap, 5 >, C > for InsertLapStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertLapParams < >) -> LapQuery < 'a, C,
+    Lap, 5 >
+    { self.bind(client, & params.heat,& params.driver,& params.lap_in_heat,& params.laptime,& params.kart_id,) }
+}}pub mod rating_history
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct GetRatingHistoryOfDriverParams < T1 : cornucopia_async::StringSql,> { pub driver : i32,pub system : T1,}#[derive( Debug)] pub struct InsertRatingHistoryParams < T1 : cornucopia_async::StringSql,> { pub driver : i32,pub session : i32,pub system : T1,pub rating_before : f64,pub uncertainty_before : f64,pub volatility_before : Option<f64>,pub rating_after : f64,pub uncertainty_after : f64,pub volatility_after : Option<f64>,}#[derive( Debug, Clone, PartialEq, )] pub struct DriverRatingChange
+{ pub heat_id : String,pub start_date : time::PrimitiveDateTime,pub rating_before : f64,pub uncertainty_before : f64,pub rating_after : f64,pub uncertainty_after : f64,}pub struct DriverRatingChangeBorrowed < 'a >
+{ pub heat_id : &'a str,pub start_date : time::PrimitiveDateTime,pub rating_before : f64,pub uncertainty_before : f64,pub rating_after : f64,pub uncertainty_after : f64,} impl < 'a > From < DriverRatingChangeBorrowed <
+'a >> for DriverRatingChange
+{
+    fn
+    from(DriverRatingChangeBorrowed { heat_id,start_date,rating_before,uncertainty_before,rating_after,uncertainty_after,} : DriverRatingChangeBorrowed < 'a >)
+    -> Self { Self { heat_id: heat_id.into(),start_date,rating_before,uncertainty_before,rating_after,uncertainty_after,} }
+}pub struct DriverRatingChangeQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> DriverRatingChangeBorrowed,
+    mapper : fn(DriverRatingChangeBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > DriverRatingChangeQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(DriverRatingChangeBorrowed) -> R) -> DriverRatingChangeQuery
+    < 'a, C, R, N >
+    {
+        DriverRatingChangeQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, )] pub struct RatingHistory
+{ pub id : i32,pub driver : i32,pub session : i32,pub rating_before : f64,pub uncertainty_before : f64,pub rating_after : f64,pub uncertainty_after : f64,pub system : String,pub volatility_before : Option<f64>,pub volatility_after : Option<f64>,}pub struct RatingHistoryBorrowed < 'a >
+{ pub id : i32,pub driver : i32,pub session : i32,pub rating_before : f64,pub uncertainty_before : f64,pub rating_after : f64,pub uncertainty_after : f64,pub system : &'a str,pub volatility_before : Option<f64>,pub volatility_after : Option<f64>,} impl < 'a > From < RatingHistoryBorrowed <
+'a >> for RatingHistory
+{
+    fn
+    from(RatingHistoryBorrowed { id,driver,session,rating_before,uncertainty_before,rating_after,uncertainty_after,system,volatility_before,volatility_after,} : RatingHistoryBorrowed < 'a >)
+    -> Self { Self { id,driver,session,rating_before,uncertainty_before,rating_after,uncertainty_after,system: system.into(),volatility_before,volatility_after,} }
+}pub struct RatingHistoryQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> RatingHistoryBorrowed,
+    mapper : fn(RatingHistoryBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > RatingHistoryQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(RatingHistoryBorrowed) -> R) -> RatingHistoryQuery
+    < 'a, C, R, N >
+    {
+        RatingHistoryQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub struct I64Query < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i64,
+    mapper : fn(i64) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I64Query < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(i64) -> R) -> I64Query
+    < 'a, C, R, N >
+    {
+        I64Query
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_rating_history_of_driver() -> GetRatingHistoryOfDriverStmt
+{ GetRatingHistoryOfDriverStmt(cornucopia_async :: private :: Stmt :: new("select
+    s.heat_id,
+    s.start_date,
+    r.rating_before,
+    r.uncertainty_before,
+    r.rating_after,
+    r.uncertainty_after
+from public.rating_history r
+         inner join public.session s on s.id = r.session
+where r.driver = $1 and r.system = $2::text
+order by s.start_date")) } pub
+struct GetRatingHistoryOfDriverStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRatingHistoryOfDriverStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,system : & 'a T1,) -> DriverRatingChangeQuery < 'a, C,
+DriverRatingChange, 2 >
+{
+    DriverRatingChangeQuery
+    {
+        client, params : [driver,system,], stmt : & mut self.0, extractor :
+        | row | { DriverRatingChangeBorrowed { heat_id : row.get(0),start_date : row.get(1),rating_before : row.get(2),uncertainty_before : row.get(3),rating_after : row.get(4),uncertainty_after : row.get(5),} }, mapper : | it | { <DriverRatingChange>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, GetRatingHistoryOfDriverParams < T1,>, DriverRatingChangeQuery < 'a,
+C, DriverRatingChange, 2 >, C > for GetRatingHistoryOfDriverStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetRatingHistoryOfDriverParams < T1,>) -> DriverRatingChangeQuery < 'a, C,
+    DriverRatingChange, 2 >
+    { self.bind(client, & params.driver,& params.system,) }
+}pub fn get_rating_history_of_system() -> GetRatingHistoryOfSystemStmt
+{ GetRatingHistoryOfSystemStmt(cornucopia_async :: private :: Stmt :: new("select * from public.rating_history where system = $1::text")) } pub
+struct GetRatingHistoryOfSystemStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRatingHistoryOfSystemStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+system : & 'a T1,) -> RatingHistoryQuery < 'a, C,
+RatingHistory, 1 >
+{
+    RatingHistoryQuery
+    {
+        client, params : [system,], stmt : & mut self.0, extractor :
+        | row | { RatingHistoryBorrowed { id : row.get(0),driver : row.get(1),session : row.get(2),rating_before : row.get(3),uncertainty_before : row.get(4),rating_after : row.get(5),uncertainty_after : row.get(6),system : row.get(7),volatility_before : row.get(8),volatility_after : row.get(9),} }, mapper : | it | { <RatingHistory>::from(it) },
+    }
+} }pub fn insert_rating_history() -> InsertRatingHistoryStmt
+{ InsertRatingHistoryStmt(cornucopia_async :: private :: Stmt :: new("insert into public.rating_history (driver, session, system, rating_before, uncertainty_before, volatility_before, rating_after, uncertainty_after, volatility_after)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+on conflict (driver, session, system) do update set
+    rating_before = excluded.rating_before,
+    uncertainty_before = excluded.uncertainty_before,
+    volatility_before = excluded.volatility_before,
+    rating_after = excluded.rating_after,
+    uncertainty_after = excluded.uncertainty_after,
+    volatility_after = excluded.volatility_after
+returning *")) } pub
+struct InsertRatingHistoryStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertRatingHistoryStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+(& 'a mut self, client : & 'a  C,
+driver : & 'a i32,session : & 'a i32,system : & 'a T1,rating_before : & 'a f64,uncertainty_before : & 'a f64,volatility_before : & 'a Option<f64>,rating_after : & 'a f64,uncertainty_after : & 'a f64,volatility_after : & 'a Option<f64>,) -> RatingHistoryQuery < 'a, C,
+RatingHistory, 9 >
+{
+    RatingHistoryQuery
+    {
+        client, params : [driver,session,system,rating_before,uncertainty_before,volatility_before,rating_after,uncertainty_after,volatility_after,], stmt : & mut self.0, extractor :
+        | row | { RatingHistoryBorrowed { id : row.get(0),driver : row.get(1),session : row.get(2),rating_before : row.get(3),uncertainty_before : row.get(4),rating_after : row.get(5),uncertainty_after : row.get(6),system : row.get(7),volatility_before : row.get(8),volatility_after : row.get(9),} }, mapper : | it | { <RatingHistory>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, InsertRatingHistoryParams < T1,>, RatingHistoryQuery < 'a,
+C, RatingHistory, 9 >, C > for InsertRatingHistoryStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertRatingHistoryParams < T1,>) -> RatingHistoryQuery < 'a, C,
+    RatingHistory, 9 >
+    { self.bind(client, & params.driver,& params.session,& params.system,& params.rating_before,& params.uncertainty_before,& params.volatility_before,& params.rating_after,& params.uncertainty_after,& params.volatility_after,) }
+}pub fn delete_rating_history_from() -> DeleteRatingHistoryFromStmt
+{ DeleteRatingHistoryFromStmt(cornucopia_async :: private :: Stmt :: new("with deleted_rows as (
+    delete from public.rating_history r
+    using public.session s
+    where s.id = r.session and ($1::timestamp is null or s.start_date >= $1)
+    returning 1
+) select count(*) from deleted_rows")) } pub
+struct DeleteRatingHistoryFromStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteRatingHistoryFromStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+from_date : & 'a Option<time::PrimitiveDateTime>,) -> I64Query < 'a, C,
+i64, 1 >
+{
+    I64Query
+    {
+        client, params : [from_date,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }}pub mod rating_replays
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive(Clone,Copy, Debug)] pub struct RestartRatingReplayParams < > { pub from_date : Option<time::PrimitiveDateTime>,pub id : i32,}#[derive(Clone,Copy, Debug)] pub struct SetRatingReplayProgressParams < > { pub session : i32,pub id : i32,}#[derive( Debug, Clone, PartialEq, Copy)] pub struct RatingReplay
+{ pub id : i32,pub from_date : Option<time::PrimitiveDateTime>,pub last_start_date : Option<time::PrimitiveDateTime>,pub last_session : Option<i32>,pub created_at : time::PrimitiveDateTime,pub finished_at : Option<time::PrimitiveDateTime>,}pub struct RatingReplayQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> RatingReplay,
+    mapper : fn(RatingReplay) -> T,
+} impl < 'a, C, T : 'a, const N : usize > RatingReplayQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(RatingReplay) -> R) -> RatingReplayQuery
+    < 'a, C, R, N >
+    {
+        RatingReplayQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_unfinished_rating_replay() -> GetUnfinishedRatingReplayStmt
+{ GetUnfinishedRatingReplayStmt(cornucopia_async :: private :: Stmt :: new("select * from public.rating_replays where finished_at is null order by id limit 1 for update")) } pub
+struct GetUnfinishedRatingReplayStmt(cornucopia_async :: private :: Stmt) ; impl
+GetUnfinishedRatingReplayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> RatingReplayQuery < 'a, C,
+RatingReplay, 0 >
+{
+    RatingReplayQuery
+    {
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { RatingReplay { id : row.get(0),from_date : row.get(1),last_start_date : row.get(2),last_session : row.get(3),created_at : row.get(4),finished_at : row.get(5),} }, mapper : | it | { <RatingReplay>::from(it) },
+    }
+} }pub fn create_rating_replay() -> CreateRatingReplayStmt
+{ CreateRatingReplayStmt(cornucopia_async :: private :: Stmt :: new("insert into public.rating_replays (from_date, created_at) values ($1, now()) returning *")) } pub
+struct CreateRatingReplayStmt(cornucopia_async :: private :: Stmt) ; impl
+CreateRatingReplayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+from_date : & 'a Option<time::PrimitiveDateTime>,) -> RatingReplayQuery < 'a, C,
+RatingReplay, 1 >
+{
+    RatingReplayQuery
+    {
+        client, params : [from_date,], stmt : & mut self.0, extractor :
+        | row | { RatingReplay { id : row.get(0),from_date : row.get(1),last_start_date : row.get(2),last_session : row.get(3),created_at : row.get(4),finished_at : row.get(5),} }, mapper : | it | { <RatingReplay>::from(it) },
+    }
+} }pub fn restart_rating_replay() -> RestartRatingReplayStmt
+{ RestartRatingReplayStmt(cornucopia_async :: private :: Stmt :: new("update public.rating_replays
+set
+    from_date = $1,
+    last_start_date = null,
+    last_session = null
+where id = $2
+returning *")) } pub
+struct RestartRatingReplayStmt(cornucopia_async :: private :: Stmt) ; impl
+RestartRatingReplayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+from_date : & 'a Option<time::PrimitiveDateTime>,id : & 'a i32,) -> RatingReplayQuery < 'a, C,
+RatingReplay, 2 >
+{
+    RatingReplayQuery
+    {
+        client, params : [from_date,id,], stmt : & mut self.0, extractor :
+        | row | { RatingReplay { id : row.get(0),from_date : row.get(1),last_start_date : row.get(2),last_session : row.get(3),created_at : row.get(4),finished_at : row.get(5),} }, mapper : | it | { <RatingReplay>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, RestartRatingReplayParams < >, RatingReplayQuery < 'a,
+C, RatingReplay, 2 >, C > for RestartRatingReplayStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    RestartRatingReplayParams < >) -> RatingReplayQuery < 'a, C,
+    RatingReplay, 2 >
+    { self.bind(client, & params.from_date,& params.id,) }
+}pub fn set_rating_replay_progress() -> SetRatingReplayProgressStmt
+{ SetRatingReplayProgressStmt(cornucopia_async :: private :: Stmt :: new("update public.rating_replays
+set
+    last_start_date = (select start_date from public.session where id = $1),
+    last_session = $1
+where id = $2
+returning *")) } pub
+struct SetRatingReplayProgressStmt(cornucopia_async :: private :: Stmt) ; impl
+SetRatingReplayProgressStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+session : & 'a i32,id : & 'a i32,) -> RatingReplayQuery < 'a, C,
+RatingReplay, 2 >
+{
+    RatingReplayQuery
+    {
+        client, params : [session,id,], stmt : & mut self.0, extractor :
+        | row | { RatingReplay { id : row.get(0),from_date : row.get(1),last_start_date : row.get(2),last_session : row.get(3),created_at : row.get(4),finished_at : row.get(5),} }, mapper : | it | { <RatingReplay>::from(it) },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, SetRatingReplayProgressParams < >, RatingReplayQuery < 'a,
+C, RatingReplay, 2 >, C > for SetRatingReplayProgressStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    SetRatingReplayProgressParams < >) -> RatingReplayQuery < 'a, C,
+    RatingReplay, 2 >
+    { self.bind(client, & params.session,& params.id,) }
+}pub fn finish_rating_replay() -> FinishRatingReplayStmt
+{ FinishRatingReplayStmt(cornucopia_async :: private :: Stmt :: new("update public.rating_replays set finished_at = now() where id = $1 returning *")) } pub
+struct FinishRatingReplayStmt(cornucopia_async :: private :: Stmt) ; impl
+FinishRatingReplayStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+id : & 'a i32,) -> RatingReplayQuery < 'a, C,
+RatingReplay, 1 >
+{
+    RatingReplayQuery
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { RatingReplay { id : row.get(0),from_date : row.get(1),last_start_date : row.get(2),last_session : row.get(3),created_at : row.get(4),finished_at : row.get(5),} }, mapper : | it | { <RatingReplay>::from(it) },
+    }
+} }}pub mod records
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct GetCurrentRecordParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub track : i32,pub category : T1,pub scope : T2,}#[derive( Debug)] pub struct InsertRecordParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub track : i32,pub category : T1,pub scope : T2,pub lap : i32,pub set_at : time::PrimitiveDateTime,}#[derive(Clone,Copy, Debug)] pub struct BreakRecordParams < > { pub broken_at : time::PrimitiveDateTime,pub id : i32,}#[derive( Debug, Clone, PartialEq, )] pub struct RecordCandidate
+{ pub lap : i32,pub driver : i32,pub driver_name : String,pub kart : i32,pub heat_id : String,pub heat_type : String,pub start_date : time::PrimitiveDateTime,pub lap_time : f64,}pub struct RecordCandidateBorrowed < 'a >
+{ pub lap : i32,pub driver : i32,pub driver_name : &'a str,pub kart : i32,pub heat_id : &'a str,pub heat_type : &'a str,pub start_date : time::PrimitiveDateTime,pub lap_time : f64,} impl < 'a > From < RecordCandidateBorrowed <
+'a >> for RecordCandidate
+{
+    fn
+    from(RecordCandidateBorrowed { lap,driver,driver_name,kart,heat_id,heat_type,start_date,lap_time,} : RecordCandidateBorrowed < 'a >)
+    -> Self { Self { lap,driver,driver_name: driver_name.into(),kart,heat_id: heat_id.into(),heat_type: heat_type.into(),start_date,lap_time,} }
+}pub struct RecordCandidateQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> RecordCandidateBorrowed,
+    mapper : fn(RecordCandidateBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > RecordCandidateQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(RecordCandidateBorrowed) -> R) -> RecordCandidateQuery
+    < 'a, C, R, N >
+    {
+        RecordCandidateQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub struct I64Query < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i64,
+    mapper : fn(i64) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I64Query < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(i64) -> R) -> I64Query
+    < 'a, C, R, N >
+    {
+        I64Query
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, )] pub struct Record
+{ pub id : i32,pub track : i32,pub category : String,pub scope : String,pub driver_name : String,pub heat_id : String,pub kart : i32,pub lap_time : f64,pub set_at : time::PrimitiveDateTime,pub broken_at : Option<time::PrimitiveDateTime>,}pub struct RecordBorrowed < 'a >
+{ pub id : i32,pub track : i32,pub category : &'a str,pub scope : &'a str,pub driver_name : &'a str,pub heat_id : &'a str,pub kart : i32,pub lap_time : f64,pub set_at : time::PrimitiveDateTime,pub broken_at : Option<time::PrimitiveDateTime>,} impl < 'a > From < RecordBorrowed <
+'a >> for Record
+{
+    fn
+    from(RecordBorrowed { id,track,category,scope,driver_name,heat_id,kart,lap_time,set_at,broken_at,} : RecordBorrowed < 'a >)
+    -> Self { Self { id,track,category: category.into(),scope: scope.into(),driver_name: driver_name.into(),heat_id: heat_id.into(),kart,lap_time,set_at,broken_at,} }
+}pub struct RecordQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> RecordBorrowed,
+    mapper : fn(RecordBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > RecordQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(RecordBorrowed) -> R) -> RecordQuery
+    < 'a, C, R, N >
+    {
+        RecordQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub struct I32Query < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> i32,
+    mapper : fn(i32) -> T,
+} impl < 'a, C, T : 'a, const N : usize > I32Query < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(i32) -> R) -> I32Query
+    < 'a, C, R, N >
+    {
+        I32Query
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_record_candidates() -> GetRecordCandidatesStmt
+{ GetRecordCandidatesStmt(cornucopia_async :: private :: Stmt :: new("select
+    l.id as lap,
+    l.driver,
+    d.name as driver_name,
+    k.number as kart,
+    s.heat_id,
+    s.heat_type,
+    s.start_date,
+    l.lap_time
+from public.laps l
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where l.heat = $1 and l.classification = 'clean'")) } pub
+struct GetRecordCandidatesStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRecordCandidatesStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+heat : & 'a i32,) -> RecordCandidateQuery < 'a, C,
+RecordCandidate, 1 >
+{
+    RecordCandidateQuery
+    {
+        client, params : [heat,], stmt : & mut self.0, extractor :
+        | row | { RecordCandidateBorrowed { lap : row.get(0),driver : row.get(1),driver_name : row.get(2),kart : row.get(3),heat_id : row.get(4),heat_type : row.get(5),start_date : row.get(6),lap_time : row.get(7),} }, mapper : | it | { <RecordCandidate>::from(it) },
+    }
+} }pub fn count_scheduled_record_rebuilds() -> CountScheduledRecordRebuildsStmt
+{ CountScheduledRecordRebuildsStmt(cornucopia_async :: private :: Stmt :: new("select count(*) from public.record_rebuilds where finished_at is null")) } pub
+struct CountScheduledRecordRebuildsStmt(cornucopia_async :: private :: Stmt) ; impl
+CountScheduledRecordRebuildsStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> I64Query < 'a, C,
+i64, 0 >
+{
+    I64Query
+    {
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }pub fn get_current_record() -> GetCurrentRecordStmt
+{ GetCurrentRecordStmt(cornucopia_async :: private :: Stmt :: new("select
+    r.id,
+    r.track,
+    r.category,
+    r.scope,
+    d.name as driver_name,
+    s.heat_id,
+    k.number as kart,
+    l.lap_time,
+    r.set_at,
+    r.broken_at
+from public.records r
+         inner join public.laps l on l.id = r.lap
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where r.track = $1 and r.category = $2 and r.scope = $3 and r.broken_at is null")) } pub
+struct GetCurrentRecordStmt(cornucopia_async :: private :: Stmt) ; impl
+GetCurrentRecordStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-kart_number : & 'a i32,) -> KartWithStatsQuery < 'a, C,
-KartWithStats, 1 >
+track : & 'a i32,category : & 'a T1,scope : & 'a T2,) -> RecordQuery < 'a, C,
+Record, 3 >
 {
-    KartWithStatsQuery
+    RecordQuery
     {
-        client, params : [kart_number,], stmt : & mut self.0, extractor :
-        | row | { KartWithStatsBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),lap_count : row.get(6),driver_count : row.get(7),} }, mapper : | it | { <KartWithStats>::from(it) },
+        client, params : [track,category,scope,], stmt : & mut self.0, extractor :
+        | row | { RecordBorrowed { id : row.get(0),track : row.get(1),category : row.get(2),scope : row.get(3),driver_name : row.get(4),heat_id : row.get(5),kart : row.get(6),lap_time : row.get(7),set_at : row.get(8),broken_at : row.get(9),} }, mapper : | it | { <Record>::from(it) },
     }
-} }pub fn get_all_karts_with_stats() -> GetAllKartsWithStatsStmt
-{ GetAllKartsWithStatsStmt(cornucopia_async :: private :: Stmt :: new("select
-    k.*,
-    CAST(count(l.id) AS INT) as lap_count,
-    CAST(count(DISTINCT l.driver) AS INT) as driver_count
-from public.cars k
-         inner join public.laps l on k.id = l.kart_id
-group by k.id
-order by $1")) } pub
-struct GetAllKartsWithStatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetAllKartsWithStatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,>
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, GetCurrentRecordParams < T1,T2,>, RecordQuery < 'a,
+C, Record, 3 >, C > for GetCurrentRecordStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    GetCurrentRecordParams < T1,T2,>) -> RecordQuery < 'a, C,
+    Record, 3 >
+    { self.bind(client, & params.track,& params.category,& params.scope,) }
+}pub fn get_records_of_track() -> GetRecordsOfTrackStmt
+{ GetRecordsOfTrackStmt(cornucopia_async :: private :: Stmt :: new("select
+    r.id,
+    r.track,
+    r.category,
+    r.scope,
+    d.name as driver_name,
+    s.heat_id,
+    k.number as kart,
+    l.lap_time,
+    r.set_at,
+    r.broken_at
+from public.records r
+         inner join public.laps l on l.id = r.lap
+         inner join public.session s on s.id = l.heat
+         inner join public.drivers d on d.id = l.driver
+         inner join public.cars k on k.id = l.kart_id
+where r.track = $1
+order by r.category, r.scope, r.set_at desc, r.id desc")) } pub
+struct GetRecordsOfTrackStmt(cornucopia_async :: private :: Stmt) ; impl
+GetRecordsOfTrackStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-order_column : & 'a T1,) -> KartWithStatsQuery < 'a, C,
-KartWithStats, 1 >
+track : & 'a i32,) -> RecordQuery < 'a, C,
+Record, 1 >
 {
-    KartWithStatsQuery
+    RecordQuery
     {
-        client, params : [order_column,], stmt : & mut self.0, extractor :
-        | row | { KartWithStatsBorrowed { id : row.get(0),number : row.get(1),brand : row.get(2),model : row.get(3),horsepower : row.get(4),modified : row.get(5),lap_count : row.get(6),driver_count : row.get(7),} }, mapper : | it | { <KartWithStats>::from(it) },
+        client, params : [track,], stmt : & mut self.0, extractor :
+        | row | { RecordBorrowed { id : row.get(0),track : row.get(1),category : row.get(2),scope : row.get(3),driver_name : row.get(4),heat_id : row.get(5),kart : row.get(6),lap_time : row.get(7),set_at : row.get(8),broken_at : row.get(9),} }, mapper : | it | { <Record>::from(it) },
     }
-} }pub fn create_kart() -> CreateKartStmt
-{ CreateKartStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO public.cars (number, brand, model, horsepower, modified) values ($1, $2, $3, $4, $5) RETURNING id")) } pub
-struct CreateKartStmt(cornucopia_async :: private :: Stmt) ; impl
-CreateKartStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
+} }pub fn insert_record() -> InsertRecordStmt
+{ InsertRecordStmt(cornucopia_async :: private :: Stmt :: new("insert into public.records (track, category, scope, lap, set_at)
+values ($1, $2, $3, $4, $5)
+returning id")) } pub
+struct InsertRecordStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertRecordStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-number : & 'a i32,brand : & 'a T1,model : & 'a T2,horsepower : & 'a i32,modified : & 'a bool,) -> I32Query < 'a, C,
+track : & 'a i32,category : & 'a T1,scope : & 'a T2,lap : & 'a i32,set_at : & 'a time::PrimitiveDateTime,) -> I32Query < 'a, C,
 i32, 5 >
 {
     I32Query
     {
-        client, params : [number,brand,model,horsepower,modified,], stmt : & mut self.0, extractor :
+        client, params : [track,category,scope,lap,set_at,], stmt : & mut self.0, extractor :
         | row | { row.get(0) }, mapper : | it | { it },
     }
 } }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
-Params < 'a, CreateKartParams < T1,T2,>, I32Query < 'a,
-C, i32, 5 >, C > for CreateKartStmt
+Params < 'a, InsertRecordParams < T1,T2,>, I32Query < 'a,
+C, i32, 5 >, C > for InsertRecordStmt
 {
     fn
     params(& 'a mut self, client : & 'a  C, params : & 'a
-    CreateKartParams < T1,T2,>) -> I32Query < 'a, C,
+    InsertRecordParams < T1,T2,>) -> I32Query < 'a, C,
     i32, 5 >
-    { self.bind(client, & params.number,& params.brand,& params.model,& params.horsepower,& params.modified,) }
-}}pub mod laps
-{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct InsertLapsBulkParams < T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,> { pub heats : T1,pub drivers : T2,pub laps_in_heat : T3,pub lap_times : T4,pub karts : T5,}#[derive(Clone,Copy, Debug)] pub struct InsertLapParams < > { pub heat : i32,pub driver : i32,pub lap_in_heat : i32,pub laptime : f64,pub kart_id : i32,}#[derive( Debug, Clone, PartialEq, Copy)] pub struct Lap
-{ pub id : i32,pub heat : i32,pub driver : i32,pub lap_in_heat : i32,pub lap_time : f64,pub kart_id : i32,}pub struct LapQuery < 'a, C : GenericClient, T, const N : usize >
+    { self.bind(client, & params.track,& params.category,& params.scope,& params.lap,& params.set_at,) }
+}pub fn break_record() -> BreakRecordStmt
+{ BreakRecordStmt(cornucopia_async :: private :: Stmt :: new("update public.records set broken_at = $1 where id = $2
+returning id")) } pub
+struct BreakRecordStmt(cornucopia_async :: private :: Stmt) ; impl
+BreakRecordStmt { pub fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+broken_at : & 'a time::PrimitiveDateTime,id : & 'a i32,) -> I32Query < 'a, C,
+i32, 2 >
+{
+    I32Query
+    {
+        client, params : [broken_at,id,], stmt : & mut self.0, extractor :
+        | row | { row.get(0) }, mapper : | it | { it },
+    }
+} }impl < 'a, C : GenericClient, > cornucopia_async ::
+Params < 'a, BreakRecordParams < >, I32Query < 'a,
+C, i32, 2 >, C > for BreakRecordStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    BreakRecordParams < >) -> I32Query < 'a, C,
+    i32, 2 >
+    { self.bind(client, & params.broken_at,& params.id,) }
+}pub fn delete_all_records() -> DeleteAllRecordsStmt
+{ DeleteAllRecordsStmt(cornucopia_async :: private :: Stmt :: new("delete from public.records")) } pub
+struct DeleteAllRecordsStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteAllRecordsStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }pub fn schedule_record_rebuild() -> ScheduleRecordRebuildStmt
+{ ScheduleRecordRebuildStmt(cornucopia_async :: private :: Stmt :: new("insert into public.record_rebuilds (created_at) values (now())")) } pub
+struct ScheduleRecordRebuildStmt(cornucopia_async :: private :: Stmt) ; impl
+ScheduleRecordRebuildStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }pub fn finish_record_rebuilds() -> FinishRecordRebuildsStmt
+{ FinishRecordRebuildsStmt(cornucopia_async :: private :: Stmt :: new("update public.record_rebuilds set finished_at = now() where finished_at is null")) } pub
+struct FinishRecordRebuildsStmt(cornucopia_async :: private :: Stmt) ; impl
+FinishRecordRebuildsStmt { pub async fn bind < 'a, C : GenericClient, >
+(& 'a mut self, client : & 'a  C,
+) -> Result < u64, tokio_postgres :: Error >
+{
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }}pub mod session_conditions
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct InsertSessionConditionsBulkParams < T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = f64>,T3 : cornucopia_async::ArraySql<Item = i32>,> { pub sessions : T1,pub condition_indices : T2,pub amounts_of_drivers : T3,}#[derive( Debug, Clone, PartialEq, Copy)] pub struct ConditionObservation
+{ pub session : i32,pub track : i32,pub driver : i32,pub best_lap_time : f64,}pub struct ConditionObservationQuery < 'a, C : GenericClient, T, const N : usize >
 {
     client : & 'a  C, params :
     [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
-    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> Lap,
-    mapper : fn(Lap) -> T,
-} impl < 'a, C, T : 'a, const N : usize > LapQuery < 'a, C, T, N >
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> ConditionObservation,
+    mapper : fn(ConditionObservation) -> T,
+} impl < 'a, C, T : 'a, const N : usize > ConditionObservationQuery < 'a, C, T, N >
 where C : GenericClient
 {
-    pub fn map < R > (self, mapper : fn(Lap) -> R) -> LapQuery
+    pub fn map < R > (self, mapper : fn(ConditionObservation) -> R) -> ConditionObservationQuery
     < 'a, C, R, N >
     {
-        LapQuery
+        ConditionObservationQuery
         {
             client : self.client, params : self.params, stmt : self.stmt,
             extractor : self.extractor, mapper,
@@ -1117,146 +3004,305 @@ where C : GenericClient
         res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
         Ok(it)
     }
-}pub fn get_lap() -> GetLapStmt
-{ GetLapStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where id = $1")) } pub
-struct GetLapStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapStmt { pub fn bind < 'a, C : GenericClient, >
+}#[derive( Debug, Clone, PartialEq, Copy)] pub struct SessionCondition
+{ pub id : i32,pub session : i32,pub condition_index : f64,pub amount_of_drivers : i32,}pub struct SessionConditionQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> SessionCondition,
+    mapper : fn(SessionCondition) -> T,
+} impl < 'a, C, T : 'a, const N : usize > SessionConditionQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(SessionCondition) -> R) -> SessionConditionQuery
+    < 'a, C, R, N >
+    {
+        SessionConditionQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}#[derive( Debug, Clone, PartialEq, )] pub struct SessionConditionOfTrack
+{ pub heat_id : String,pub start_date : time::PrimitiveDateTime,pub condition_index : f64,pub amount_of_drivers : i32,}pub struct SessionConditionOfTrackBorrowed < 'a >
+{ pub heat_id : &'a str,pub start_date : time::PrimitiveDateTime,pub condition_index : f64,pub amount_of_drivers : i32,} impl < 'a > From < SessionConditionOfTrackBorrowed <
+'a >> for SessionConditionOfTrack
+{
+    fn
+    from(SessionConditionOfTrackBorrowed { heat_id,start_date,condition_index,amount_of_drivers,} : SessionConditionOfTrackBorrowed < 'a >)
+    -> Self { Self { heat_id: heat_id.into(),start_date,condition_index,amount_of_drivers,} }
+}pub struct SessionConditionOfTrackQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> SessionConditionOfTrackBorrowed,
+    mapper : fn(SessionConditionOfTrackBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > SessionConditionOfTrackQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(SessionConditionOfTrackBorrowed) -> R) -> SessionConditionOfTrackQuery
+    < 'a, C, R, N >
+    {
+        SessionConditionOfTrackQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
+    }
+}pub fn get_condition_observations() -> GetConditionObservationsStmt
+{ GetConditionObservationsStmt(cornucopia_async :: private :: Stmt :: new("select
+    s.id as session,
+    s.track,
+    l.driver,
+    min(l.lap_time - coalesce(o.time_offset, 0)) as best_lap_time
+from public.laps l
+         inner join public.session s on s.id = l.heat
+         left join public.kart_offsets o on o.kart = l.kart_id and o.period = date_trunc('month', s.start_date)
+where l.classification = 'clean'
+group by s.id, l.driver")) } pub
+struct GetConditionObservationsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetConditionObservationsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-id : & 'a i32,) -> LapQuery < 'a, C,
-Lap, 1 >
+) -> ConditionObservationQuery < 'a, C,
+ConditionObservation, 0 >
 {
-    LapQuery
+    ConditionObservationQuery
     {
-        client, params : [id,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { ConditionObservation { session : row.get(0),track : row.get(1),driver : row.get(2),best_lap_time : row.get(3),} }, mapper : | it | { <ConditionObservation>::from(it) },
     }
-} }pub fn get_laps_from_driver() -> GetLapsFromDriverStmt
-{ GetLapsFromDriverStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where driver = $1")) } pub
-struct GetLapsFromDriverStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapsFromDriverStmt { pub fn bind < 'a, C : GenericClient, >
+} }pub fn get_session_conditions_of_sessions() -> GetSessionConditionsOfSessionsStmt
+{ GetSessionConditionsOfSessionsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.session_conditions where session = any($1::int[])")) } pub
+struct GetSessionConditionsOfSessionsStmt(cornucopia_async :: private :: Stmt) ; impl
+GetSessionConditionsOfSessionsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
 (& 'a mut self, client : & 'a  C,
-driver : & 'a i32,) -> LapQuery < 'a, C,
-Lap, 1 >
+sessions : & 'a T1,) -> SessionConditionQuery < 'a, C,
+SessionCondition, 1 >
 {
-    LapQuery
+    SessionConditionQuery
     {
-        client, params : [driver,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [sessions,], stmt : & mut self.0, extractor :
+        | row | { SessionCondition { id : row.get(0),session : row.get(1),condition_index : row.get(2),amount_of_drivers : row.get(3),} }, mapper : | it | { <SessionCondition>::from(it) },
     }
-} }pub fn get_laps_from_kart() -> GetLapsFromKartStmt
-{ GetLapsFromKartStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where kart_id = $1")) } pub
-struct GetLapsFromKartStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapsFromKartStmt { pub fn bind < 'a, C : GenericClient, >
+} }pub fn get_session_conditions_of_track() -> GetSessionConditionsOfTrackStmt
+{ GetSessionConditionsOfTrackStmt(cornucopia_async :: private :: Stmt :: new("select
+    s.heat_id,
+    s.start_date,
+    c.condition_index,
+    c.amount_of_drivers
+from public.session_conditions c
+         inner join public.session s on s.id = c.session
+where s.track = $1
+order by s.start_date")) } pub
+struct GetSessionConditionsOfTrackStmt(cornucopia_async :: private :: Stmt) ; impl
+GetSessionConditionsOfTrackStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-kart : & 'a i32,) -> LapQuery < 'a, C,
-Lap, 1 >
+track : & 'a i32,) -> SessionConditionOfTrackQuery < 'a, C,
+SessionConditionOfTrack, 1 >
 {
-    LapQuery
+    SessionConditionOfTrackQuery
     {
-        client, params : [kart,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [track,], stmt : & mut self.0, extractor :
+        | row | { SessionConditionOfTrackBorrowed { heat_id : row.get(0),start_date : row.get(1),condition_index : row.get(2),amount_of_drivers : row.get(3),} }, mapper : | it | { <SessionConditionOfTrack>::from(it) },
     }
-} }pub fn get_laps_from_drivers() -> GetLapsFromDriversStmt
-{ GetLapsFromDriversStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where driver = any($1::int[])")) } pub
-struct GetLapsFromDriversStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapsFromDriversStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+} }pub fn insert_session_conditions_bulk() -> InsertSessionConditionsBulkStmt
+{ InsertSessionConditionsBulkStmt(cornucopia_async :: private :: Stmt :: new("insert into public.session_conditions (session, condition_index, amount_of_drivers)
+select
+    unnest($1::int[]),
+    unnest($2::float[]),
+    unnest($3::int[])
+returning *")) } pub
+struct InsertSessionConditionsBulkStmt(cornucopia_async :: private :: Stmt) ; impl
+InsertSessionConditionsBulkStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = f64>,T3 : cornucopia_async::ArraySql<Item = i32>,>
 (& 'a mut self, client : & 'a  C,
-driver : & 'a T1,) -> LapQuery < 'a, C,
-Lap, 1 >
+sessions : & 'a T1,condition_indices : & 'a T2,amounts_of_drivers : & 'a T3,) -> SessionConditionQuery < 'a, C,
+SessionCondition, 3 >
 {
-    LapQuery
+    SessionConditionQuery
     {
-        client, params : [driver,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [sessions,condition_indices,amounts_of_drivers,], stmt : & mut self.0, extractor :
+        | row | { SessionCondition { id : row.get(0),session : row.get(1),condition_index : row.get(2),amount_of_drivers : row.get(3),} }, mapper : | it | { <SessionCondition>::from(it) },
     }
-} }pub fn get_laps_from_heat() -> GetLapsFromHeatStmt
-{ GetLapsFromHeatStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where heat = $1")) } pub
-struct GetLapsFromHeatStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapsFromHeatStmt { pub fn bind < 'a, C : GenericClient, >
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = f64>,T3 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
+Params < 'a, InsertSessionConditionsBulkParams < T1,T2,T3,>, SessionConditionQuery < 'a,
+C, SessionCondition, 3 >, C > for InsertSessionConditionsBulkStmt
+{
+    fn
+    params(& 'a mut self, client : & 'a  C, params : & 'a
+    InsertSessionConditionsBulkParams < T1,T2,T3,>) -> SessionConditionQuery < 'a, C,
+    SessionCondition, 3 >
+    { self.bind(client, & params.sessions,& params.condition_indices,& params.amounts_of_drivers,) }
+}pub fn delete_all_session_conditions() -> DeleteAllSessionConditionsStmt
+{ DeleteAllSessionConditionsStmt(cornucopia_async :: private :: Stmt :: new("delete from public.session_conditions")) } pub
+struct DeleteAllSessionConditionsStmt(cornucopia_async :: private :: Stmt) ; impl
+DeleteAllSessionConditionsStmt { pub async fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-heat : & 'a i32,) -> LapQuery < 'a, C,
-Lap, 1 >
+) -> Result < u64, tokio_postgres :: Error >
 {
-    LapQuery
+    let stmt = self.0.prepare(client) .await ? ;
+    client.execute(stmt, & []) .await
+} }}pub mod tracks
+{ use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;#[derive( Debug)] pub struct EnsureTrackParams < T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> { pub name : T1,pub layout : T2,}#[derive(Clone,Copy, Debug)] pub struct SetTrackLapThresholdsParams < > { pub min_lap_time : f64,pub outlier_threshold : f64,pub id : i32,}#[derive( Debug, Clone, PartialEq, )] pub struct Track
+{ pub id : i32,pub name : String,pub layout : String,pub min_lap_time : f64,pub outlier_threshold : f64,}pub struct TrackBorrowed < 'a >
+{ pub id : i32,pub name : &'a str,pub layout : &'a str,pub min_lap_time : f64,pub outlier_threshold : f64,} impl < 'a > From < TrackBorrowed <
+'a >> for Track
+{
+    fn
+    from(TrackBorrowed { id,name,layout,min_lap_time,outlier_threshold,} : TrackBorrowed < 'a >)
+    -> Self { Self { id,name: name.into(),layout: layout.into(),min_lap_time,outlier_threshold,} }
+}pub struct TrackQuery < 'a, C : GenericClient, T, const N : usize >
+{
+    client : & 'a  C, params :
+    [& 'a (dyn postgres_types :: ToSql + Sync) ; N], stmt : & 'a mut cornucopia_async
+    :: private :: Stmt, extractor : fn(& tokio_postgres :: Row) -> TrackBorrowed,
+    mapper : fn(TrackBorrowed) -> T,
+} impl < 'a, C, T : 'a, const N : usize > TrackQuery < 'a, C, T, N >
+where C : GenericClient
+{
+    pub fn map < R > (self, mapper : fn(TrackBorrowed) -> R) -> TrackQuery
+    < 'a, C, R, N >
     {
-        client, params : [heat,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        TrackQuery
+        {
+            client : self.client, params : self.params, stmt : self.stmt,
+            extractor : self.extractor, mapper,
+        }
+    } pub async fn one(self) -> Result < T, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let row =
+        self.client.query_one(stmt, & self.params) .await ? ;
+        Ok((self.mapper) ((self.extractor) (& row)))
+    } pub async fn all(self) -> Result < Vec < T >, tokio_postgres :: Error >
+    { self.iter() .await ?.try_collect().await } pub async fn opt(self) -> Result
+    < Option < T >, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ;
+        Ok(self.client.query_opt(stmt, & self.params) .await
+        ?.map(| row | (self.mapper) ((self.extractor) (& row))))
+    } pub async fn iter(self,) -> Result < impl futures::Stream < Item = Result
+    < T, tokio_postgres :: Error >> + 'a, tokio_postgres :: Error >
+    {
+        let stmt = self.stmt.prepare(self.client) .await ? ; let it =
+        self.client.query_raw(stmt, cornucopia_async :: private ::
+        slice_iter(& self.params)) .await ?
+        .map(move | res |
+        res.map(| row | (self.mapper) ((self.extractor) (& row)))) .into_stream() ;
+        Ok(it)
     }
-} }pub fn get_laps_from_heats() -> GetLapsFromHeatsStmt
-{ GetLapsFromHeatsStmt(cornucopia_async :: private :: Stmt :: new("select * from public.laps where heat = any($1::int[])")) } pub
-struct GetLapsFromHeatsStmt(cornucopia_async :: private :: Stmt) ; impl
-GetLapsFromHeatsStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,>
+}pub fn get_all_tracks() -> GetAllTracksStmt
+{ GetAllTracksStmt(cornucopia_async :: private :: Stmt :: new("select * from public.tracks order by id")) } pub
+struct GetAllTracksStmt(cornucopia_async :: private :: Stmt) ; impl
+GetAllTracksStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-heats : & 'a T1,) -> LapQuery < 'a, C,
-Lap, 1 >
+) -> TrackQuery < 'a, C,
+Track, 0 >
 {
-    LapQuery
+    TrackQuery
     {
-        client, params : [heats,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [], stmt : & mut self.0, extractor :
+        | row | { TrackBorrowed { id : row.get(0),name : row.get(1),layout : row.get(2),min_lap_time : row.get(3),outlier_threshold : row.get(4),} }, mapper : | it | { <Track>::from(it) },
     }
-} }pub fn delete_laps_from_heat() -> DeleteLapsFromHeatStmt
-{ DeleteLapsFromHeatStmt(cornucopia_async :: private :: Stmt :: new("delete from public.laps where heat = $1")) } pub
-struct DeleteLapsFromHeatStmt(cornucopia_async :: private :: Stmt) ; impl
-DeleteLapsFromHeatStmt { pub async fn bind < 'a, C : GenericClient, >
+} }pub fn get_track_by_id() -> GetTrackByIdStmt
+{ GetTrackByIdStmt(cornucopia_async :: private :: Stmt :: new("select * from public.tracks where id = $1")) } pub
+struct GetTrackByIdStmt(cornucopia_async :: private :: Stmt) ; impl
+GetTrackByIdStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-heat : & 'a i32,) -> Result < u64, tokio_postgres :: Error >
+id : & 'a i32,) -> TrackQuery < 'a, C,
+Track, 1 >
 {
-    let stmt = self.0.prepare(client) .await ? ;
-    client.execute(stmt, & [heat,]) .await
-} }pub fn insert_laps_bulk() -> InsertLapsBulkStmt
-{ InsertLapsBulkStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO
-    public.laps (heat, driver, lap_in_heat, lap_time, kart_id)
-select
-    unnest($1::int[]),
-    unnest($2::int[]),
-    unnest($3::int[]),
-    unnest($4::float[]),
-    unnest($5::int[])
+    TrackQuery
+    {
+        client, params : [id,], stmt : & mut self.0, extractor :
+        | row | { TrackBorrowed { id : row.get(0),name : row.get(1),layout : row.get(2),min_lap_time : row.get(3),outlier_threshold : row.get(4),} }, mapper : | it | { <Track>::from(it) },
+    }
+} }pub fn ensure_track() -> EnsureTrackStmt
+{ EnsureTrackStmt(cornucopia_async :: private :: Stmt :: new("insert into public.tracks (name, layout) values ($1, $2)
+on conflict (name, layout) do update set name = excluded.name
 returning *")) } pub
-struct InsertLapsBulkStmt(cornucopia_async :: private :: Stmt) ; impl
-InsertLapsBulkStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,>
+struct EnsureTrackStmt(cornucopia_async :: private :: Stmt) ; impl
+EnsureTrackStmt { pub fn bind < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,>
 (& 'a mut self, client : & 'a  C,
-heats : & 'a T1,drivers : & 'a T2,laps_in_heat : & 'a T3,lap_times : & 'a T4,karts : & 'a T5,) -> LapQuery < 'a, C,
-Lap, 5 >
+name : & 'a T1,layout : & 'a T2,) -> TrackQuery < 'a, C,
+Track, 2 >
 {
-    LapQuery
+    TrackQuery
     {
-        client, params : [heats,drivers,laps_in_heat,lap_times,karts,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [name,layout,], stmt : & mut self.0, extractor :
+        | row | { TrackBorrowed { id : row.get(0),name : row.get(1),layout : row.get(2),min_lap_time : row.get(3),outlier_threshold : row.get(4),} }, mapper : | it | { <Track>::from(it) },
     }
-} }impl < 'a, C : GenericClient, T1 : cornucopia_async::ArraySql<Item = i32>,T2 : cornucopia_async::ArraySql<Item = i32>,T3 : cornucopia_async::ArraySql<Item = i32>,T4 : cornucopia_async::ArraySql<Item = f64>,T5 : cornucopia_async::ArraySql<Item = i32>,> cornucopia_async ::
-Params < 'a, InsertLapsBulkParams < T1,T2,T3,T4,T5,>, LapQuery < 'a,
-C, Lap, 5 >, C > for InsertLapsBulkStmt
+} }impl < 'a, C : GenericClient, T1 : cornucopia_async::StringSql,T2 : cornucopia_async::StringSql,> cornucopia_async ::
+Params < 'a, EnsureTrackParams < T1,T2,>, TrackQuery < 'a,
+C, Track, 2 >, C > for EnsureTrackStmt
 {
     fn
     params(& 'a mut self, client : & 'a  C, params : & 'a
-    InsertLapsBulkParams < T1,T2,T3,T4,T5,>) -> LapQuery < 'a, C,
-    Lap, 5 >
-    { self.bind(client, & params.heats,& params.drivers,& params.laps_in_heat,& params.lap_times,& params.karts,) }
-}pub fn insert_lap() -> InsertLapStmt
-{ InsertLapStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO
-    public.laps (heat, driver, lap_in_heat, lap_time, kart_id)
-VALUES ($1, $2, $3, $4, $5)
+    EnsureTrackParams < T1,T2,>) -> TrackQuery < 'a, C,
+    Track, 2 >
+    { self.bind(client, & params.name,& params.layout,) }
+}pub fn set_track_lap_thresholds() -> SetTrackLapThresholdsStmt
+{ SetTrackLapThresholdsStmt(cornucopia_async :: private :: Stmt :: new("update public.tracks set min_lap_time = $1, outlier_threshold = $2
+where id = $3
 returning *")) } pub
-struct InsertLapStmt(cornucopia_async :: private :: Stmt) ; impl
-InsertLapStmt { pub fn bind < 'a, C : GenericClient, >
+struct SetTrackLapThresholdsStmt(cornucopia_async :: private :: Stmt) ; impl
+SetTrackLapThresholdsStmt { pub fn bind < 'a, C : GenericClient, >
 (& 'a mut self, client : & 'a  C,
-heat : & 'a i32,driver : & 'a i32,lap_in_heat : & 'a i32,laptime : & 'a f64,kart_id : & 'a i32,) -> LapQuery < 'a, C,
-Lap, 5 >
+min_lap_time : & 'a f64,outlier_threshold : & 'a f64,id : & 'a i32,) -> TrackQuery < 'a, C,
+Track, 3 >
 {
-    LapQuery
+    TrackQuery
     {
-        client, params : [heat,driver,lap_in_heat,laptime,kart_id,], stmt : & mut self.0, extractor :
-        | row | { Lap { id : row.get(0),heat : row.get(1),driver : row.get(2),lap_in_heat : row.get(3),lap_time : row.get(4),kart_id : row.get(5),} }, mapper : | it | { <Lap>::from(it) },
+        client, params : [min_lap_time,outlier_threshold,id,], stmt : & mut self.0, extractor :
+        | row | { TrackBorrowed { id : row.get(0),name : row.get(1),layout : row.get(2),min_lap_time : row.get(3),outlier_threshold : row.get(4),} }, mapper : | it | { <Track>::from(it) },
     }
 } }impl < 'a, C : GenericClient, > cornucopia_async ::
-Params < 'a, InsertLapParams < >, LapQuery < 'a,
-C, Lap, 5 >, C > for InsertLapStmt
+Params < 'a, SetTrackLapThresholdsParams < >, TrackQuery < 'a,
+C, Track, 3 >, C > for SetTrackLapThresholdsStmt
 {
     fn
     params(& 'a mut self, client : & 'a  C, params : & 'a
-    InsertLapParams < >) -> LapQuery < 'a, C,
-    Lap, 5 >
-    { self.bind(client, & params.heat,& params.driver,& params.lap_in_heat,& params.laptime,& params.kart_id,) }
+    SetTrackLapThresholdsParams < >) -> TrackQuery < 'a, C,
+    Track, 3 >
+    { self.bind(client, & params.min_lap_time,& params.outlier_threshold,& params.id,) }
 }}}
\ No newline at end of file
@@ -4,21 +4,36 @@ use std::time::Duration;
 use tokio::task::JoinSet;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use crate::modules::database::models::general::setup_pool;
+use crate::modules::database::models::rating_replay::RatingReplay;
+use crate::modules::database::models::record::Record;
+use crate::modules::database::models::kart_offset::KartOffset;
+use crate::modules::database::models::session_condition::SessionCondition;
 
-use crate::modules::heat_api::{get_heat_from_api, get_todays_heats_from_api, save_heat};
+use crate::modules::cache_warming::warm_cache;
+use crate::modules::database::models::driver::sanitize_name;
+use crate::modules::heat_api::save_heat;
+use crate::modules::heat_source::{get_heat_source, HeatSource};
 
-pub async fn load_todays_heats() {
-    let heat_list: Vec<String> = get_todays_heats_from_api().await;
+pub async fn load_todays_heats(source: &dyn HeatSource) {
+    let heat_list: Vec<String> = match source.get_todays_heats().await {
+        Ok(heats) => heats,
+        Err(err) => {
+            warn!(target:"cron_jobs:load_todays_heats", "failed loading todays heats. (error: {})", err);
+            return;
+        }
+    };
     // let mut tasks = JoinSet::new();
+    let mut saved_heats = 0;
+    let mut new_drivers = Vec::new();
 
     for heat_id in heat_list {
         // tasks.spawn(async move {
             println!("loading heat: {}", heat_id);
-            let heat = match get_heat_from_api(heat_id).await {
+            let heat = match source.get_heat(&heat_id).await {
                 Ok(heat) => heat,
                 Err(err) => {
-                    warn!(target:"cron_jobs:load_todays_heats", "failed loading heat from api. (heat_id: {})", err);
-                    return;
+                    warn!(target:"cron_jobs:load_todays_heats", "failed loading heat from api. (heat_id: {}, error: {})", heat_id, err);
+                    continue;
                 }
             };
 
@@ -27,6 +42,8 @@ pub async fn load_todays_heats() {
             match save_heat(heat.clone()).await {
                 Ok(heat_id) => {
                     info!(target:"cron_jobs:load_todays_heats", "saved heat: {}", heat_id);
+                    saved_heats += 1;
+                    new_drivers.extend(heat.results.iter().map(|result| sanitize_name(&result.participation.driver_name)));
                 }
                 Err(Error::AlreadyExistsError { .. }) => {
                     info!(target:"cron_jobs:load_todays_heats", "heat already exists: {}", heat.heat.id);
@@ -34,7 +51,9 @@ pub async fn load_todays_heats() {
                 Err(Error::InvalidNameError { .. }) => {
                     warn!(target:"cron_jobs:load_todays_heats", "invalid driver names in heat {}", heat.heat.id);
                 }
-                _ => {}
+                Err(err) => {
+                    warn!(target:"cron_jobs:load_todays_heats", "failed saving heat. (heat_id: {}, error: {})", heat.heat.id, err);
+                }
             };
         // });
     }
@@ -42,6 +61,47 @@ pub async fn load_todays_heats() {
     // while let Some(heat) = tasks.join_next().await {
     //     heat.unwrap();
     // }
+
+    // rate the heats that were saved out of order
+    match RatingReplay::run().await {
+        Ok(0) => {}
+        Ok(rated) => {
+            info!(target:"cron_jobs:load_todays_heats", "replayed the ratings of {} heats", rated);
+        }
+        Err(err) => {
+            warn!(target:"cron_jobs:load_todays_heats", "failed replaying ratings. (error: {})", err);
+        }
+    }
+
+    // retry the rebuilds of the records that failed after saving a heat or merging drivers
+    match Record::rebuild_scheduled().await {
+        Ok(None) => {}
+        Ok(Some(records)) => {
+            info!(target:"cron_jobs:load_todays_heats", "rebuilt {} records", records);
+        }
+        Err(err) => {
+            warn!(target:"cron_jobs:load_todays_heats", "failed rebuilding records. (error: {})", err);
+        }
+    }
+
+    // the new laps change the estimated speed of the karts
+    if saved_heats > 0 {
+        if let Err(err) = KartOffset::fit_all().await {
+            warn!(target:"cron_jobs:load_todays_heats", "failed fitting kart offsets. (error: {})", err);
+        }
+    }
+
+    // the conditions are estimated from the kart-adjusted laps
+    if saved_heats > 0 {
+        if let Err(err) = SessionCondition::compute_all().await {
+            warn!(target:"cron_jobs:load_todays_heats", "failed computing track conditions. (error: {})", err);
+        }
+    }
+
+    // the new heats invalidated the most used pages, generate them before a visitor asks for them
+    if saved_heats > 0 {
+        warm_cache(&new_drivers).await;
+    }
 }
 
 
@@ -53,7 +113,7 @@ pub async fn register_cron_jobs() {
         Duration::from_secs(7200), // 2 hours
         |_uuid, _l| {
             Box::pin(async {
-                load_todays_heats().await;
+                load_todays_heats(get_heat_source().as_ref()).await;
             })
         },
     )
@@ -1,21 +1,114 @@
+use std::io::Cursor;
+
+use log::error;
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
 use snafu::prelude::*;
 
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
 pub enum Error {
-    #[snafu(display(""))]
-    AlreadyExistsError {},
-    #[snafu(display(""))]
-    InvalidNameError {},
-    #[snafu(display(""))]
-    FileDoesNotExistError {},
-    #[snafu(display(""))]
-    NotFoundError {},
-    #[snafu(display(""))]
-    ConnectionError {},
-    #[snafu(display(""))]
-    PermissionDeniedError {},
-    #[snafu(display(""))]
-    DatabaseError {},
+    #[snafu(display("{what} already exists"))]
+    AlreadyExistsError { what: String },
+    #[snafu(display("invalid name: {name}"))]
+    InvalidNameError { name: String },
+    #[snafu(display("could not read {path}: {source}"))]
+    FileDoesNotExistError { path: String, source: std::io::Error },
+    #[snafu(display("{what} not found"))]
+    NotFoundError { what: String },
+    #[snafu(display("could not connect to {url}: {source}"))]
+    ConnectionError { url: String, source: reqwest::Error },
+    #[snafu(display("permission denied: {path}"))]
+    PermissionDeniedError { path: String },
+    #[snafu(display("database error: {source}"))]
+    DatabaseError { source: tokio_postgres::Error },
+    #[snafu(display("could not get a database connection: {source}"))]
+    PoolError { source: deadpool_postgres::PoolError },
+    #[snafu(display("redis error: {source}"))]
+    RedisError { source: redis::RedisError },
+    #[snafu(display("could not get a redis connection: {source}"))]
+    RedisPoolError { source: deadpool_redis::PoolError },
+    #[snafu(display("invalid response: {source}"))]
+    InvalidResponseError { source: serde_json::Error },
+    #[snafu(display("invalid date {date}: {source}"))]
+    InvalidDateError { date: String, source: chrono::ParseError },
+    #[snafu(display("invalid {name}: {value}"))]
+    InvalidArgumentError { name: String, value: String },
 }
 
 pub type CustomResult<T, E = Error> = Result<T, E>;
+
+impl Error {
+    /// # get the http status
+    /// get the http status that belongs to the error
+    ///
+    /// ## Returns
+    /// * `Status` - the status
+    pub fn status(&self) -> Status {
+        match self {
+            Error::NotFoundError { .. } => Status::NotFound,
+            Error::InvalidNameError { .. } | Error::InvalidArgumentError { .. } => Status::BadRequest,
+            Error::AlreadyExistsError { .. } => Status::Conflict,
+            Error::ConnectionError { .. }
+            | Error::DatabaseError { .. }
+            | Error::PoolError { .. }
+            | Error::RedisError { .. }
+            | Error::RedisPoolError { .. } => Status::ServiceUnavailable,
+            // the upstream source sent something we can not read
+            Error::InvalidResponseError { .. } | Error::InvalidDateError { .. } => Status::BadGateway,
+            Error::FileDoesNotExistError { .. } | Error::PermissionDeniedError { .. } => {
+                Status::InternalServerError
+            }
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for Error {
+    fn from(source: tokio_postgres::Error) -> Self {
+        Error::DatabaseError { source }
+    }
+}
+
+impl From<deadpool_postgres::PoolError> for Error {
+    fn from(source: deadpool_postgres::PoolError) -> Self {
+        Error::PoolError { source }
+    }
+}
+
+impl From<redis::RedisError> for Error {
+    fn from(source: redis::RedisError) -> Self {
+        Error::RedisError { source }
+    }
+}
+
+impl From<deadpool_redis::PoolError> for Error {
+    fn from(source: deadpool_redis::PoolError) -> Self {
+        Error::RedisPoolError { source }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Error {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.status();
+        // server errors can contain urls and database details, so they are only logged in full
+        let message = if status.code >= 500 {
+            error!(target:"errors:respond_to", "{} {}: {:?}", req.method(), req.uri(), self);
+            status.reason_lossy().to_lowercase()
+        } else {
+            self.to_string()
+        };
+
+        let json = serde_json::json!({
+            "status": status.code,
+            "error": message,
+        })
+        .to_string();
+
+        Response::build()
+            .status(status)
+            .header(ContentType::JSON)
+            .sized_body(json.len(), Cursor::new(json))
+            .ok()
+    }
+}
@@ -10,16 +10,14 @@ pub mod modules;
 
 pub mod cornucopia;
 
-pub mod macros {
-    pub mod redis;
-    pub mod request_caching;
-}
-
 pub mod routes {
     pub mod api {
+        pub mod conditions;
         pub mod driver;
         pub mod heat;
         pub mod kart;
+        pub mod records;
+        pub mod track;
     }
 }
 
@@ -9,13 +9,11 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let output = quote! {
         impl<'r> Responder<'r, 'r> for #ident {
-            fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'r> {
+            fn respond_to(self, req: &'r Request<'_>) -> response::Result<'r> {
                 let json = serde_json::to_string(&self).unwrap();
 
-                Response::build()
-                    .header(ContentType::new("application", "json"))
-                    .sized_body(json.len(), std::io::Cursor::new(json))
-                    .ok()
+                // adds the etag and answers conditional requests, when the data changed is not known
+                crate::modules::helpers::conditional_get::json_response(req, json, None)
             }
         }
     };
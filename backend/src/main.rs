@@ -8,6 +8,7 @@ use karting_groningen_analytics::modules::helpers::logging::setup_logging;
 use karting_groningen_analytics::routes::api;
 use task_queue;
 use karting_groningen_analytics::modules::database::models::general::setup_pool;
+use karting_groningen_analytics::modules::redis::setup_redis_pool;
 
 #[launch]
 async fn rocket() -> Rocket<Build> {
@@ -15,6 +16,7 @@ async fn rocket() -> Rocket<Build> {
     setup_logging().expect("Failed to setup logging");
 
     setup_pool().await;
+    setup_redis_pool();
     // start the task thread
     task_queue::setup();
 
@@ -30,6 +32,7 @@ async fn rocket() -> Rocket<Build> {
             api::heat::save_one,
             // api::heat::delete,
             api::heat::get_one,
+            api::heat::get_chart,
             api::heat::get_all_ids,
             api::heat::get_one_stats,
             api::heat::search,
@@ -38,11 +41,22 @@ async fn rocket() -> Rocket<Build> {
             api::driver::search_full,
             api::driver::get_one_stats,
             api::driver::get_one,
+            api::driver::get_ratings,
+            api::driver::get_current_ratings,
             api::driver::get_all_ids,
+            api::driver::get_leaderboard,
+            api::driver::compare,
+            api::driver::predict,
             //kart
             api::kart::get_one,
             api::kart::get_all,
             api::kart::get_one_full,
+            //track
+            api::track::get_all,
+            //conditions
+            api::conditions::get_of_track,
+            //records
+            api::records::get_of_track,
         ],
     )
 }
@@ -55,4 +69,3 @@ async fn rocket() -> Rocket<Build> {
 //          add ability to create custom dashboards
 //TODO:: add caching to all db queries
 //TODO:: add ranking to the drivers
-//TODO:: add support for different tracks
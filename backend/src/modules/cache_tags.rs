@@ -0,0 +1,86 @@
+use log::error;
+
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::session::Session;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::models::vehicle::Vehicle;
+use crate::modules::redis::Redis;
+
+/// # something a cached response depends on
+/// every cached response is added to a redis set per tag it depends on. invalidating a tag deletes
+/// the responses in its set, so only the responses that depend on it are removed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheTag {
+    /// a driver, by id
+    Driver(i32),
+    /// a heat, by heat id
+    Heat(String),
+    /// a kart, by number
+    Kart(i32),
+    /// everything ranked or aggregated over the heats of a track, by id
+    Track(i32),
+    /// lists and searches of all drivers
+    Drivers,
+    /// lists and searches of all heats
+    Heats,
+    /// lists of all karts
+    Karts,
+}
+
+impl CacheTag {
+    /// # get the key of the set of the tag
+    ///
+    /// ## Returns
+    /// * `String` - the redis key
+    pub fn key(&self) -> String {
+        match self {
+            CacheTag::Driver(id) => format!("tag:driver:{}", id),
+            CacheTag::Heat(heat_id) => format!("tag:heat:{}", heat_id),
+            CacheTag::Kart(number) => format!("tag:kart:{}", number),
+            CacheTag::Track(id) => format!("tag:track:{}", id),
+            CacheTag::Drivers => "tag:drivers".to_string(),
+            CacheTag::Heats => "tag:heats".to_string(),
+            CacheTag::Karts => "tag:karts".to_string(),
+        }
+    }
+
+    /// # get the tags of drivers
+    pub fn of_drivers(drivers: &[Driver]) -> Vec<CacheTag> {
+        drivers.iter().map(|driver| CacheTag::Driver(driver.id)).collect()
+    }
+
+    /// # get the tags of heats
+    pub fn of_heats(heats: &[Session]) -> Vec<CacheTag> {
+        heats.iter().map(|heat| CacheTag::Heat(heat.heat_id.clone())).collect()
+    }
+
+    /// # get the tags of karts
+    pub fn of_karts(karts: &[Vehicle]) -> Vec<CacheTag> {
+        karts.iter().map(|kart| CacheTag::Kart(kart.number)).collect()
+    }
+
+    /// # get the tags of tracks
+    pub fn of_tracks(tracks: &[Track]) -> Vec<CacheTag> {
+        tracks.iter().map(|track| CacheTag::Track(track.id)).collect()
+    }
+
+    /// # invalidate tags
+    /// delete all cached responses that depend on one of the tags.
+    /// failing to reach redis is only logged
+    ///
+    /// ## Arguments
+    /// * `tags` - the tags
+    pub async fn invalidate(tags: &[CacheTag]) {
+        let keys: Vec<String> = tags.iter().map(|tag| tag.key()).collect();
+        match Redis::connect().await {
+            Ok(mut r_conn) => {
+                if let Err(error) = Redis::delete_tagged(&mut r_conn, &keys).await {
+                    error!(target:"cache_tags:invalidate", "Error while deleting tagged keys: {}", error);
+                }
+            }
+            Err(error) => {
+                error!(target:"cache_tags:invalidate", "Error connecting to redis: {}", error);
+            }
+        }
+    }
+}
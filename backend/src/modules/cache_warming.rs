@@ -0,0 +1,91 @@
+use std::collections::BTreeSet;
+use std::env;
+
+use dotenvy::dotenv;
+use log::{info, warn};
+
+use crate::modules::database::models::driver::Driver;
+use crate::modules::response_cache::caching_enabled;
+
+/// the server to warm the cache of, the api is requested the same way a visitor would
+const DEFAULT_CACHE_WARM_URL: &str = "http://localhost:8089";
+
+/// the pages that are warmed when `CACHE_WARM_PATHS` is not set. the first pages of the searches
+/// are the ones the frontend opens with, the response is cached by its full uri.
+const DEFAULT_CACHE_WARM_PATHS: &str = "/api/drivers/leaderboard,\
+    /api/drivers/leaderboard?window=day,\
+    /api/drivers/leaderboard?metric=rating,\
+    /api/heats/search?q=&page=0&page_size=50&sort_col=start_time&sort_dir=asc,\
+    /api/drivers/search?q=&page=0&page_size=50&sort_col=name&sort_dir=asc,\
+    /api/karts/all?q=&page=0&page_size=50&sort_col=number&sort_dir=asc,\
+    /api/drivers/{driver},\
+    /api/drivers/{driver}/full";
+
+/// # get the paths to warm
+/// the comma separated paths in `CACHE_WARM_PATHS`. `{driver}` is replaced by the name of every
+/// driver that drove in the new heats.
+fn warm_paths() -> Vec<String> {
+    env::var("CACHE_WARM_PATHS")
+        .unwrap_or(DEFAULT_CACHE_WARM_PATHS.to_string())
+        .split(',')
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// # warm the cache
+/// request the most used pages after new heats are saved, so the first visitor does not have to
+/// wait for them to be generated. the pages are requested one after the other to not slow the
+/// server down for the visitors.
+///
+/// does nothing when responses are not cached.
+///
+/// ## Arguments
+/// * `driver_names` - the names of the drivers in the new heats
+///
+/// ## Returns
+/// * `usize` - the amount of pages that were warmed
+pub async fn warm_cache(driver_names: &[String]) -> usize {
+    if !caching_enabled() {
+        return 0;
+    }
+
+    dotenv().ok();
+    let base_url = env::var("CACHE_WARM_URL").unwrap_or(DEFAULT_CACHE_WARM_URL.to_string());
+
+    // the pages of a driver are under their current name, not the name they drove under
+    let mut names = BTreeSet::new();
+    for name in driver_names {
+        match Driver::get_by_name(name).await {
+            Ok(driver) => {
+                names.insert(driver.name);
+            }
+            Err(err) => {
+                warn!(target:"modules/cache_warming:warm_cache", "failed getting driver {}. (error: {})", name, err);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    for path in warm_paths() {
+        if path.contains("{driver}") {
+            paths.extend(names.iter().map(|name| path.replace("{driver}", name)));
+        } else {
+            paths.push(path);
+        }
+    }
+
+    let mut warmed = 0;
+    for path in paths {
+        let url = format!("{}{}", base_url, path);
+        match reqwest::get(&url).await.and_then(|response| response.error_for_status()) {
+            Ok(_) => warmed += 1,
+            Err(err) => {
+                warn!(target:"modules/cache_warming:warm_cache", "failed warming {}. (error: {})", url, err);
+            }
+        }
+    }
+
+    info!(target:"modules/cache_warming:warm_cache", "warmed {} pages", warmed);
+    warmed
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::modules::database::models::lap::Lap;
+use crate::modules::helpers::math::Math;
+use crate::modules::lap_classification::LapClassification;
+
+/// the minimum amount of clean laps needed to say something about the consistency of a driver
+const MIN_CLEAN_LAPS: usize = 3;
+/// the amount of fastest laps the best lap is compared to
+const BEST_LAPS: usize = 5;
+
+/// # consistency of a driver
+/// how much the clean lap times of a driver vary. only clean laps are used, so out-laps and
+/// slowed laps do not make a driver look inconsistent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Consistency {
+    pub amount_of_laps: usize,
+    pub standard_deviation: f64,
+    /// the standard deviation as part of the average lap time, comparable between tracks
+    pub coefficient_of_variation: f64,
+    /// the average of the fastest 5 laps minus the fastest lap
+    pub best_five_gap: f64,
+    /// the seconds the lap time changes per lap over a heat, negative when the driver got faster
+    pub improvement_rate: f64,
+}
+
+/// # consistency of a driver in a heat
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DriverConsistency {
+    pub driver_name: String,
+    pub consistency: Consistency,
+}
+
+impl Consistency {
+    /// # get the consistency of laps
+    /// the laps are expected to be driven by a single driver in a single heat
+    ///
+    /// ## Arguments
+    /// * `laps` - the laps
+    ///
+    /// ## Returns
+    /// * `Option<Consistency>` - the consistency, `None` when there are less than 3 clean laps
+    pub fn of_laps(laps: &[&Lap]) -> Option<Consistency> {
+        let clean_laps: Vec<&Lap> = laps
+            .iter()
+            .filter(|lap| lap.classification == LapClassification::Clean)
+            .copied()
+            .collect();
+        if clean_laps.len() < MIN_CLEAN_LAPS {
+            return None;
+        }
+
+        let mut lap_times: Vec<f64> = clean_laps.iter().map(|lap| lap.lap_time).collect();
+        let standard_deviation = Math::standard_deviation(&lap_times);
+        let mean = Math::mean(&lap_times);
+
+        lap_times.sort_by(|a, b| a.total_cmp(b));
+        let best_laps: Vec<f64> = lap_times.iter().take(BEST_LAPS).copied().collect();
+
+        Some(Consistency {
+            amount_of_laps: clean_laps.len(),
+            standard_deviation,
+            coefficient_of_variation: standard_deviation / mean,
+            best_five_gap: Math::mean(&best_laps) - lap_times[0],
+            improvement_rate: improvement_rate(&clean_laps),
+        })
+    }
+
+    /// # get the consistency of the drivers in a heat
+    ///
+    /// ## Arguments
+    /// * `laps` - the laps driven in the heat
+    ///
+    /// ## Returns
+    /// * `HashMap<i32, Consistency>` - the consistency of every driver with enough clean laps, by driver id
+    pub fn per_driver(laps: &[Lap]) -> HashMap<i32, Consistency> {
+        let mut driver_laps: HashMap<i32, Vec<&Lap>> = HashMap::new();
+        for lap in laps {
+            driver_laps.entry(lap.driver).or_default().push(lap);
+        }
+
+        driver_laps
+            .into_iter()
+            .filter_map(|(driver, laps)| Some((driver, Consistency::of_laps(&laps)?)))
+            .collect()
+    }
+
+    /// # get the consistency of a driver over multiple heats
+    /// the consistency is calculated per heat and then averaged, weighted by the amount of
+    /// clean laps. this way the differences in conditions between days are not counted as
+    /// inconsistency.
+    ///
+    /// ## Arguments
+    /// * `laps` - the laps of the driver
+    ///
+    /// ## Returns
+    /// * `Option<Consistency>` - the consistency, `None` when no heat has enough clean laps
+    pub fn over_heats(laps: &[Lap]) -> Option<Consistency> {
+        let mut heat_laps: HashMap<i32, Vec<&Lap>> = HashMap::new();
+        for lap in laps {
+            heat_laps.entry(lap.heat).or_default().push(lap);
+        }
+
+        let heats: Vec<Consistency> = heat_laps
+            .values()
+            .filter_map(|laps| Consistency::of_laps(laps))
+            .collect();
+
+        let amount_of_laps: usize = heats.iter().map(|heat| heat.amount_of_laps).sum();
+        if amount_of_laps == 0 {
+            return None;
+        }
+
+        let weighted_mean = |value: fn(&Consistency) -> f64| {
+            heats
+                .iter()
+                .map(|heat| value(heat) * heat.amount_of_laps as f64)
+                .sum::<f64>() / amount_of_laps as f64
+        };
+
+        Some(Consistency {
+            amount_of_laps,
+            standard_deviation: weighted_mean(|heat| heat.standard_deviation),
+            coefficient_of_variation: weighted_mean(|heat| heat.coefficient_of_variation),
+            best_five_gap: weighted_mean(|heat| heat.best_five_gap),
+            improvement_rate: weighted_mean(|heat| heat.improvement_rate),
+        })
+    }
+}
+
+/// # get the improvement rate of laps
+/// the slope of the least squares line through the lap times, by the number of the lap in the heat
+///
+/// ## Arguments
+/// * `laps` - the laps, at least 2 different lap numbers
+///
+/// ## Returns
+/// * `f64` - the change in lap time per lap
+fn improvement_rate(laps: &[&Lap]) -> f64 {
+    let lap_numbers: Vec<f64> = laps.iter().map(|lap| lap.lap_in_heat as f64).collect();
+    let lap_times: Vec<f64> = laps.iter().map(|lap| lap.lap_time).collect();
+    let mean_lap_number = Math::mean(&lap_numbers);
+    let mean_lap_time = Math::mean(&lap_times);
+
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for (lap_number, lap_time) in lap_numbers.iter().zip(&lap_times) {
+        covariance += (lap_number - mean_lap_number) * (lap_time - mean_lap_time);
+        variance += (lap_number - mean_lap_number).powi(2);
+    }
+
+    covariance / variance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::helpers::test_data::{laps, laps_in_heat};
+
+    fn of_laps(laps: &[Lap]) -> Option<Consistency> {
+        Consistency::of_laps(&laps.iter().collect::<Vec<&Lap>>())
+    }
+
+    #[test]
+    fn too_few_clean_laps() {
+        let mut laps = laps(1, &[30.0, 30.5, 45.0]);
+        laps[2].classification = LapClassification::InLap;
+
+        assert_eq!(of_laps(&laps), None);
+    }
+
+    #[test]
+    fn identical_lap_times() {
+        let consistency = of_laps(&laps(1, &[30.0; 4])).unwrap();
+
+        assert_eq!(consistency.amount_of_laps, 4);
+        assert_eq!(consistency.standard_deviation, 0.0);
+        assert_eq!(consistency.coefficient_of_variation, 0.0);
+        assert_eq!(consistency.best_five_gap, 0.0);
+        assert_eq!(consistency.improvement_rate, 0.0);
+    }
+
+    #[test]
+    fn improving_driver() {
+        let mut laps = laps(1, &[45.0, 31.0, 30.5, 30.0, 29.5, 29.0, 32.0]);
+        laps[0].classification = LapClassification::OutLap;
+        laps[6].classification = LapClassification::InLap;
+        let consistency = of_laps(&laps).unwrap();
+
+        // only the clean laps count
+        assert_eq!(consistency.amount_of_laps, 5);
+        assert!((consistency.standard_deviation - 0.5_f64.sqrt()).abs() < 0.000_001);
+        assert!((consistency.coefficient_of_variation - consistency.standard_deviation / 30.0).abs() < 0.000_001);
+        assert!((consistency.best_five_gap - 1.0).abs() < 0.000_001);
+        assert!((consistency.improvement_rate + 0.5).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn per_driver() {
+        let laps = [laps(1, &[30.0, 30.0, 30.0]), laps(2, &[31.0, 31.0])].concat();
+        let consistency = Consistency::per_driver(&laps);
+
+        assert_eq!(consistency.len(), 1);
+        assert_eq!(consistency[&1].amount_of_laps, 3);
+    }
+
+    #[test]
+    fn over_heats_is_weighted_by_laps() {
+        // a slower heat is not counted as inconsistency
+        let laps = [
+            laps_in_heat(1, 1, 1, &[30.0, 30.0, 30.0]),
+            laps_in_heat(2, 1, 1, &[40.0, 39.0, 38.0, 37.0, 36.0]),
+            laps_in_heat(3, 1, 1, &[35.0, 35.0]),
+        ].concat();
+        let consistency = Consistency::over_heats(&laps).unwrap();
+
+        assert_eq!(consistency.amount_of_laps, 8);
+        assert!((consistency.improvement_rate + 0.625).abs() < 0.000_001);
+        assert!((consistency.standard_deviation - 5.0 * 2.0_f64.sqrt() / 8.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn over_heats_without_enough_laps() {
+        assert_eq!(Consistency::over_heats(&laps(1, &[30.0, 30.0])), None);
+    }
+}
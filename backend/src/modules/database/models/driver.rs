@@ -8,7 +8,16 @@ use crate::modules::helpers::math::Math;
 use crate::modules::database::models::session::Session;
 use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::Lap;
-use crate::modules::redis::Redis;
+use crate::modules::database::models::rating_history::{RatingChange, RatingHistory};
+use crate::modules::database::models::driver_rating::DriverRating;
+use crate::modules::database::models::driver_alias::DriverAlias;
+use crate::modules::database::models::rating_replay::RatingReplay;
+use crate::modules::database::models::record::Record;
+use crate::modules::database::models::kart_offset::KartOffsets;
+use crate::modules::database::models::session_condition::SessionConditions;
+use crate::modules::lap_classification::LapClassification;
+use crate::modules::consistency::Consistency;
+use crate::modules::cache_tags::CacheTag;
 use crate::modules::traits::as_map::AsMap;
 use crate::modules::traits::has_id::HasIdTrait;
 use crate::{TemplateDataDriver, TemplateDataLap};
@@ -16,24 +25,25 @@ use crate::{TemplateDataDriver, TemplateDataLap};
 use identifiable_derive::HasId;
 use regex::Regex;
 
-use crate::macros::redis::{delete_keys};
 use json_response_derive::JsonResponse;
-use log::error;
 
 
-use rocket::http::ContentType;
 use rocket::response;
 use rocket::response::Responder;
-use rocket::response::Response;
 use rocket::Request;
-use skillratings::weng_lin::WengLinRating;
+use log::error;
+use crate::modules::rating_systems::{get_main_rating_system, SystemRating};
 
 use crate::modules::database::models::general::{get_pool};
 
-use crate::cornucopia::queries::driver::{create_driver, Driver as cDriver, DriverWithStats as cDriverWithStats, get_all_drivers, get_driver_by_id, get_driver_by_name, get_drivers_by_ids, get_driver_with_stats, search_driver_by_name, search_driver_with_stats_paginated, update_driver_rating};
+use crate::cornucopia::queries::driver::{create_driver, create_driver_if_not_exists, Driver as cDriver, DriverWithStats as cDriverWithStats, get_all_drivers, get_driver_by_id, get_driver_by_name, get_driver_by_alias, get_drivers_by_ids, get_driver_with_stats, search_driver_by_name, search_driver_with_stats_paginated, update_driver_rating, reset_driver_ratings, set_driver_ratings_from_system, delete_drivers_without_laps, delete_driver, get_heats_of_drivers, count_shared_heats};
+use crate::cornucopia::queries::laps::move_laps_to_driver;
 use crate::modules::database::query::{get_vec as select_vec_from_db};
 use crate::modules::database::query::get as select_from_db;
-use crate::modules::traits::diff_between_vecs::GetDiff;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use cornucopia_async::GenericClient;
+use snafu::OptionExt;
+use crate::errors::{CustomResult, Error, NotFoundSnafu};
 
 
 trait IdentifiableAsMap {
@@ -56,11 +66,16 @@ impl Into<DriverStats> for cDriverWithStats {
         DriverStats {
             name: self.name,
             fastest_lap_time: self.fastest_lap_time,
+            adjusted_fastest_lap_time: self.adjusted_fastest_lap_time,
+            normalized_fastest_lap_time: self.normalized_fastest_lap_time,
             avg_lap_time: self.avg_lap_time,
             median_lap_time: self.median_lap_time,
             total_laps: self.total_laps,
             total_heats: self.total_heats,
             rating: self.rating,
+            track: self.track,
+            consistency: None,
+            provisional: None,
         }
     }
 }
@@ -95,14 +110,14 @@ impl Driver {
     /// if you want to make sure the driver exists, use the `ensure_exists` function.
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
+    /// * `client` - the database client
     /// * `name` - the name of the driver
     ///
     /// ## Returns
     /// * `Driver` - the driver that was created
-    pub async fn new(name: &str) -> Driver {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(create_driver(), client, &name, &25.0, &(25.0/3.0))
+    pub async fn new<C: GenericClient>(client: &C, name: &str) -> CustomResult<Driver> {
+        let rating = get_main_rating_system().default_rating();
+        Ok(select_from_db!(create_driver(), client, &name, &rating.rating, &rating.uncertainty))
     }
 
     /// # Get all drivers
@@ -113,14 +128,13 @@ impl Driver {
     ///
     /// ## Returns
     /// * `Vec<Driver> - all drivers
-    pub async fn get_all() -> Vec<Driver> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_all_drivers(), client)
+    pub async fn get_all() -> CustomResult<Vec<Driver>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_all_drivers(), client))
     }
 
     /// # Get a driver by id
     /// get a driver by id from the database
-    /// if the driver does not exist, this function will panic
     ///
     /// ## Arguments
     /// * `connection` - the database connection
@@ -128,14 +142,15 @@ impl Driver {
     ///
     /// ## Returns
     /// * `Driver` - the driver
-    pub async fn get_by_id(id: i32) -> Driver {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(get_driver_by_id(), client, &id)
+    pub async fn get_by_id(id: i32) -> CustomResult<Driver> {
+        let client = &get_pool().get().await?;
+        select_opt_from_db!(get_driver_by_id(), client, &id)
+            .context(NotFoundSnafu { what: format!("driver with id {}", id) })
     }
 
-    pub async fn get_by_ids(ids: Vec<i32>) -> Vec<Driver> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_drivers_by_ids(), client, &ids)
+    pub async fn get_by_ids(ids: Vec<i32>) -> CustomResult<Vec<Driver>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_drivers_by_ids(), client, &ids))
     }
 
     pub async fn search_with_stats(
@@ -144,15 +159,19 @@ impl Driver {
         page: u32,
         _sort_col: String,
         _sort_dir: String,
-    ) -> Vec<DriverStats> {
+        track: i32,
+        clean_only: bool,
+    ) -> CustomResult<Vec<DriverStats>> {
         // TODO:: re add ordering
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(
             search_driver_with_stats_paginated(),
             client,
             &driver_name,
+            &track,
+            &clean_only,
             &(page_size as i64),
-            &((page * page_size) as i64))
+            &((page * page_size) as i64)))
     }
 
     /// # Get all drivers with stats
@@ -164,19 +183,22 @@ impl Driver {
     /// this is a very expensive function, and should not be used in a loop.
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
+    /// * `track` - the track to get the stats on
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `Vec<DriverStats> - stats of all drivers
-    pub async fn get_all_with_stats() -> Vec<DriverStats> {
-        let client = &get_pool().get().await.unwrap();
+    pub async fn get_all_with_stats(track: i32, clean_only: bool) -> CustomResult<Vec<DriverStats>> {
+        let client = &get_pool().get().await?;
 
-        select_vec_from_db!(
+        Ok(select_vec_from_db!(
             search_driver_with_stats_paginated(),
             client,
             &"",
+            &track,
+            &clean_only,
             &i64::MAX,
-            &0)
+            &0))
     }
 
     /// # Get driver with stats
@@ -186,100 +208,147 @@ impl Driver {
     /// it also gives you there name
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
     /// * `driver_name` - the name of the driver
+    /// * `track` - the track to get the stats on
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `Vec<DriverStats> - stats of all drivers
-    pub async fn get_driver_with_stats(driver_name: String) -> DriverStats {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(
+    pub async fn get_driver_with_stats(driver_name: String, track: i32, clean_only: bool) -> CustomResult<DriverStats> {
+        let client = &get_pool().get().await?;
+        select_opt_from_db!(
             search_driver_with_stats_paginated(),
             client,
             &driver_name,
+            &track,
+            &clean_only,
             &1,
             &0)
+            .context(NotFoundSnafu { what: format!("driver {}", driver_name) })
     }
 
     /// # check if a driver exists
     /// check if a driver exists in the database
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
+    /// * `client` - the database client
     /// * `name_in` - the name of the driver
     ///
     /// ## Returns
     /// * `bool` - true if the driver exists, false if not
-    pub async fn exists(name: &String) -> bool {
-        let client = &get_pool().get().await.unwrap();
-
-        get_driver_by_name()
+    pub async fn exists<C: GenericClient>(client: &C, name: &String) -> CustomResult<bool> {
+        Ok(get_driver_by_name()
             .bind(client, name)
-            .all()
-            .await
-            .unwrap()
-            .len() > 0
+            .opt()
+            .await?
+            .is_some())
     }
 
     /// # get a driver by name
-    /// get a driver by name. if the driver does not exists it will panic
+    /// get a driver by name. drivers that were merged into another driver are found by
+    /// their old name, see `merge`
     ///
     /// ## Arguments
     /// * `connection` - the database connection
     ///
     /// ## Returns
     /// * `Driver` - the driver
-    pub async fn get_by_name(name: &String) -> Driver {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(get_driver_by_name(), client, name)
+    pub async fn get_by_name(name: &String) -> CustomResult<Driver> {
+        let client = &get_pool().get().await?;
+        let driver: Option<Driver> = match select_opt_from_db!(get_driver_by_name(), client, name) {
+            Some(driver) => Some(driver),
+            None => select_opt_from_db!(get_driver_by_alias(), client, name),
+        };
+
+        driver.context(NotFoundSnafu { what: format!("driver {}", name) })
     }
 
     pub async fn search_by_name(
         name: &String,
         page: i32,
         page_size: i32,
-    ) -> Vec<Driver>{
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(
+    ) -> CustomResult<Vec<Driver>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(
             search_driver_by_name(),
             client,
             &name,
             &(page as i64),
-            &((page_size * page )as i64))
+            &((page_size * page )as i64)))
     }
 
     /// # get the stats of a driver
     /// get the stats of a driver. this function is the same as get_all_with_stats, but only for one driver
-    /// this function can only be called on a driver object. if the driver does not exists it will panic
+    /// this function can only be called on a driver object.
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
+    /// * `track` - the track to get the stats on
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `DriverStats` - the stats of the driver
-    pub async fn get_stats(&self) -> DriverStats {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(get_driver_with_stats(), client, &self.id)
+    pub async fn get_stats(&self, track: i32, clean_only: bool) -> CustomResult<DriverStats> {
+        let client = &get_pool().get().await?;
+        select_opt_from_db!(get_driver_with_stats(), client, &self.id, &track, &clean_only)
+            .context(NotFoundSnafu { what: format!("stats of driver {}", self.name) })
+    }
+
+    /// # get the consistency of a driver
+    /// get how consistent the clean lap times of the driver are on a track. see `Consistency::over_heats`
+    ///
+    /// ## Arguments
+    /// * `track` - the track to get the consistency on
+    ///
+    /// ## Returns
+    /// * `Option<Consistency>` - the consistency, `None` when the driver has no heat with enough clean laps
+    pub async fn get_consistency(&self, track: i32) -> CustomResult<Option<Consistency>> {
+        let laps = self.get_laps().await?;
+        let heats = Session::from_laps(&laps).await?;
+        let track_heats: HashSet<i32> = heats
+            .iter()
+            .filter(|heat| heat.track == track)
+            .map(|heat| heat.id)
+            .collect();
+
+        let track_laps: Vec<Lap> = laps
+            .into_iter()
+            .filter(|lap| track_heats.contains(&lap.heat))
+            .collect();
+
+        Ok(Consistency::over_heats(&track_laps))
     }
 
     /// # ensure a driver exists
     /// ensure a driver exists in the database. if the driver does not exists it will be created
     /// this function is preferred to `new`. this function will not panic if the driver already exists.
     /// if the driver exists they will be returned instead of created.
+    /// when the name is an alias the driver it belongs to is returned.
     ///
     /// if performance is a concern, use `new` instead.
     ///
     /// ## Arguments
-    /// * `connection` - the database connection
+    /// * `client` - the database client
     /// * `name` - the name of the driver
     ///
     /// ## Returns
     /// * `Driver` - the driver
-    pub async fn ensure_exists(name: &String) -> Driver {
-        if !Driver::exists(name).await {
-            Driver::new(name).await
-        } else {
-            Driver::get_by_name(name).await
+    pub async fn ensure_exists<C: GenericClient>(client: &C, name: &String) -> CustomResult<Driver> {
+        let existing: Option<Driver> = select_opt_from_db!(get_driver_by_name(), client, name);
+        if let Some(driver) = existing {
+            return Ok(driver);
+        }
+
+        let aliased: Option<Driver> = select_opt_from_db!(get_driver_by_alias(), client, name);
+        if let Some(driver) = aliased {
+            return Ok(driver);
+        }
+
+        // a heat saved at the same time can create the driver first
+        let rating = get_main_rating_system().default_rating();
+        let created: Option<Driver> = select_opt_from_db!(create_driver_if_not_exists(), client, name, &rating.rating, &rating.uncertainty);
+        match created {
+            Some(driver) => Ok(driver),
+            None => Ok(select_from_db!(get_driver_by_name(), client, name)),
         }
     }
 
@@ -292,7 +361,7 @@ impl Driver {
     ///
     /// ## Returns
     /// * `Driver` - the driver
-    pub async fn from_lap(lap: Lap) -> Driver {
+    pub async fn from_lap(lap: Lap) -> CustomResult<Driver> {
         Driver::get_by_id(lap.driver).await
     }
 
@@ -305,11 +374,11 @@ impl Driver {
     ///
     /// ## Returns
     /// * `Vec<Driver>` - the drivers
-    pub async fn from_laps(laps: &[Lap]) -> Vec<Driver> {
-        let client = &get_pool().get().await.unwrap();
+    pub async fn from_laps(laps: &[Lap]) -> CustomResult<Vec<Driver>> {
+        let client = &get_pool().get().await?;
 
         let driver_ids: Vec<i32> = laps.iter().map(|e| e.driver).collect();
-        select_vec_from_db!(get_drivers_by_ids(), client, &driver_ids)
+        Ok(select_vec_from_db!(get_drivers_by_ids(), client, &driver_ids))
     }
 
 
@@ -379,8 +448,8 @@ impl Driver {
     ///
     /// ## Returns
     /// * `HashMap<Driver, Vec<Lap>>` - the drivers and their laps
-    pub async fn from_laps_into_map(laps: &[Lap]) -> HashMap<Driver, Vec<Lap>> {
-        Driver::map_to_laps(Driver::from_laps(laps).await, laps)
+    pub async fn from_laps_into_map(laps: &[Lap]) -> CustomResult<HashMap<Driver, Vec<Lap>>> {
+        Ok(Driver::map_to_laps(Driver::from_laps(laps).await?, laps))
     }
 
     /// # Get laps of a driver
@@ -391,22 +460,70 @@ impl Driver {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - the laps
-    pub async fn get_laps(&self) -> Vec<Lap> {
+    pub async fn get_laps(&self) -> CustomResult<Vec<Lap>> {
         Lap::from_driver(self).await
     }
 
+    /// # Get the rating history of a driver
+    /// get the rating of the driver before and after every heat they drove
+    ///
+    /// ## Arguments
+    /// * `system` - the name of the rating system
+    ///
+    /// ## Returns
+    /// * `Vec<RatingChange>` - the rating changes, oldest first
+    pub async fn get_rating_history(&self, system: &str) -> CustomResult<Vec<RatingChange>> {
+        RatingHistory::from_driver(self, system).await
+    }
+
+    /// # Get the ratings of a driver
+    /// get the current rating of the driver in every rating system
+    ///
+    /// ## Returns
+    /// * `Vec<DriverRating>` - the ratings
+    pub async fn get_ratings(&self) -> CustomResult<Vec<DriverRating>> {
+        DriverRating::from_driver(self).await
+    }
+
+    /// # Is the rating of the driver provisional
+    /// the rating in the main rating system is provisional when the driver has not been rated
+    /// in enough heats
+    ///
+    /// ## Returns
+    /// * `bool` - whether the rating is provisional, drivers that have not been rated are
+    pub async fn is_provisional(&self) -> CustomResult<bool> {
+        let main_system = get_main_rating_system();
+        let ratings = self.get_ratings().await?;
+
+        Ok(ratings
+            .iter()
+            .find(|rating| rating.system == main_system.name())
+            .is_none_or(|rating| rating.provisional))
+    }
+
     /// # Get stats of a drivers stats for certain laps
     /// this function returns the stats of give laps only for the current driver
+    /// on the given track. laps driven on other tracks are ignored.
     ///
     /// ## Arguments
     /// * `laps` - the laps
+    /// * `heats` - the heats the laps were driven in
+    /// * `track` - the track to get the stats on
+    /// * `offsets` - the offsets of the karts the laps were driven in
+    /// * `conditions` - the track conditions of the heats
     ///
     /// ## Returns
     /// * `DriverStats` - the stats
-    pub fn get_stats_of_laps(&self, laps: &Vec<Lap>) -> Option<DriverStats> {
+    pub fn get_stats_of_laps(&self, laps: &Vec<Lap>, heats: &[Session], track: i32, offsets: &KartOffsets, conditions: &SessionConditions) -> Option<DriverStats> {
+        let track_heats: HashMap<i32, &Session> = heats
+            .iter()
+            .filter(|heat| heat.track == track)
+            .map(|heat| (heat.id, heat))
+            .collect();
+
         let correct_laps: Vec<Lap> = laps
             .iter()
-            .filter(|lap| lap.driver == self.id)
+            .filter(|lap| lap.driver == self.id && track_heats.contains_key(&lap.heat))
             .map(|e| e.to_owned())
             .collect();
 
@@ -416,15 +533,28 @@ impl Driver {
 
         let lap_stats = Lap::get_stats_of_laps(&correct_laps);
         let heat_count = Session::amount_from_laps(&correct_laps);
+        let adjusted_fastest_lap_time = correct_laps
+            .iter()
+            .map(|lap| offsets.adjust(lap, track_heats[&lap.heat].start_date))
+            .fold(f64::MAX, f64::min);
+        let normalized_fastest_lap_time = correct_laps
+            .iter()
+            .map(|lap| conditions.normalize(offsets.adjust(lap, track_heats[&lap.heat].start_date), lap.heat))
+            .fold(f64::MAX, f64::min);
 
         Some(DriverStats {
             name: self.name.clone(),
             fastest_lap_time: lap_stats.fastest_lap_time,
+            adjusted_fastest_lap_time,
+            normalized_fastest_lap_time,
             avg_lap_time: lap_stats.avg_lap_time,
             median_lap_time: lap_stats.median_lap_time,
             total_laps: correct_laps.len() as i32,
             total_heats: heat_count as i32,
             rating: self.rating,
+            track,
+            consistency: Consistency::over_heats(&correct_laps),
+            provisional: None,
         })
     }
 
@@ -446,6 +576,8 @@ impl Driver {
 
         // get all laps that belong to this driver
         let mut laps_of_driver: Vec<TemplateDataLap> = Vec::new();
+        let mut outliers: Vec<TemplateDataLap> = Vec::new();
+        let mut normal_laps: Vec<TemplateDataLap> = Vec::new();
         let mut _lap_of_driver: &Lap = &Lap {
             id: 0,
             heat: 0,
@@ -453,6 +585,7 @@ impl Driver {
             lap_in_heat: 0,
             lap_time: 0.0,
             kart_id: 0,
+            classification: LapClassification::Clean,
         };
 
         let mut fastest_lap: TemplateDataLap = TemplateDataLap {
@@ -474,13 +607,19 @@ impl Driver {
                     fastest_lap = lap_data.clone();
                 }
 
+                // separate the normal and abnormal laps
+                if lap.classification == LapClassification::Clean {
+                    normal_laps.push(lap_data.clone());
+                } else {
+                    outliers.push(lap_data.clone());
+                }
                 laps_of_driver.push(lap_data);
             }
         }
 
         let kart = match Vehicle::get_by_id(_lap_of_driver.kart_id).await {
-            Some(k) => k,
-            None => {
+            Ok(k) => k,
+            Err(_) => {
                 Vehicle {
                     id: -1,
                     number: -1,
@@ -492,9 +631,6 @@ impl Driver {
             }
         };
 
-        // separate the normal and abnormal laps
-        let outliers: Vec<TemplateDataLap> = Lap::get_outlier_laps(&laps_of_driver);
-        let normal_laps: Vec<TemplateDataLap> = laps_of_driver.diff(&outliers);
         TemplateDataDriver {
             driver_name: self.name.clone(),
             fastest_lap,
@@ -528,81 +664,175 @@ impl Driver {
     }
 
     /// # clear the cache of the driver
-    /// clear the cache of the driver
+    /// delete the cached responses that depend on the driver and the lists of drivers, see `CacheTag`
     ///
-    /// ## Arguments
-    /// * `rconn` - the redis connection
-    pub fn clear_cache(&self, rconn: &mut redis::Connection) {
-        // get all keys
-        // uri encode the name of the driver
-        let encoded_name = self.name.replace(" ", "%20");
-        let mut keys: Vec<String> = match Redis::keys(rconn, encoded_name) {
-            Ok(keys) => keys,
-            Err(error) => {
-                error!(target:"models/driver:clear_cache", "error while getting keys from redis: {}", error);
-                return;
-            }
-        };
-
-        // search queries
-        match Redis::get_keys(rconn, "*/search/*") {
-            Ok(keys_) => {
-                keys_.iter().for_each(|key| {
-                    keys.push(key.to_string());
-                });
-            }
-            Err(error) => {
-                error!(target:"models/driver:clear_cache", "error while getting search keys from redis: {}", error);
-                return;
-            }
-        }
-
-        keys.append(&mut vec![
-            "/api/drivers/all".to_string(),
-            "/api/drivers/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/drivers/all".to_string(),
-        ]);
-
-        // delete all keys
-        delete_keys!(rconn, keys, "models/driver:clear_cache");
+    pub async fn clear_cache(&self) {
+        CacheTag::invalidate(&[CacheTag::Driver(self.id), CacheTag::Drivers]).await;
     }
 
     /// # set the rating of a player to a new value
     /// this function sets the rating of a player to a new value
     /// the player that is being updated is the player whose id is given
+    /// the rating is the rating in the main rating system
     ///
     /// ## Arguments
-    /// * `conn` - the database connection
+    /// * `client` - the database client
     /// * `driver_id` - the id of the driver
     /// * `new_rating` - the new rating
-    pub async fn set_rating_id(
+    pub async fn set_rating_id<C: GenericClient>(
+        client: &C,
         driver_id: i32,
-        new_rating: WengLinRating,
-    ) -> u64 {
-        let client = &get_pool().get().await.unwrap();
-
-        update_driver_rating()
+        new_rating: &SystemRating,
+    ) -> CustomResult<u64> {
+        Ok(update_driver_rating()
             .bind(client, &new_rating.rating, &new_rating.uncertainty, &driver_id)
             .one()
-            .await
-            .unwrap() as u64
+            .await? as u64)
     }
 
     /// # set new skill ratings for the current player
     /// calls the fuction `set_rating_id` with the current driver
     ///
     /// ## Arguments
-    /// * `conn` - the database connection
+    /// * `client` - the database client
     /// * `new_rating` - the new rating
-    pub async fn set_rating(
+    pub async fn set_rating<C: GenericClient>(
         &self,
-        new_rating: WengLinRating,
-    ) -> u64 {
-        Driver::set_rating_id(self.id, new_rating).await
+        client: &C,
+        new_rating: &SystemRating,
+    ) -> CustomResult<u64> {
+        Driver::set_rating_id(client, self.id, new_rating).await
+    }
+
+    /// # reset the ratings of all drivers
+    /// set the rating of every driver back to the rating new drivers start with
+    /// and remove their ratings in all rating systems
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of drivers that were reset
+    pub async fn reset_ratings<C: GenericClient>(client: &C) -> CustomResult<u64> {
+        DriverRating::delete_all(client).await?;
+
+        let rating = get_main_rating_system().default_rating();
+        Ok(reset_driver_ratings()
+            .bind(client, &rating.rating, &rating.uncertainty)
+            .one()
+            .await? as u64)
+    }
+
+    /// # set the ratings of all drivers from a rating system
+    /// copy the ratings of a rating system to the rating of the drivers.
+    /// drivers that are not rated in the system keep their current rating
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `system` - the name of the rating system
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of updated drivers
+    pub async fn set_ratings_from_system<C: GenericClient>(client: &C, system: &str) -> CustomResult<u64> {
+        Ok(set_driver_ratings_from_system()
+            .bind(client, &system)
+            .await?)
+    }
+
+    /// # delete drivers without laps
+    /// delete all drivers that have not driven any laps
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of deleted drivers
+    pub async fn delete_without_laps<C: GenericClient>(client: &C) -> CustomResult<u64> {
+        Ok(delete_drivers_without_laps()
+            .bind(client)
+            .await?)
+    }
+
+    /// # get the heats of all drivers
+    ///
+    /// ## Returns
+    /// * `HashMap<i32, HashSet<i32>>` - the ids of the heats every driver drove in, by the id of the driver
+    pub async fn get_heat_ids_of_all() -> CustomResult<HashMap<i32, HashSet<i32>>> {
+        let client = &get_pool().get().await?;
+        let driver_heats = get_heats_of_drivers()
+            .bind(client)
+            .all()
+            .await?;
+
+        let mut heats: HashMap<i32, HashSet<i32>> = HashMap::new();
+        for driver_heat in driver_heats {
+            heats.entry(driver_heat.driver).or_default().insert(driver_heat.heat);
+        }
+
+        Ok(heats)
+    }
+
+    /// # merge a driver into this driver
+    /// the laps and aliases of the other driver are moved to this driver and its name becomes an
+    /// alias, so heats saved under that name later are added to this driver. the other driver is
+    /// deleted and the ratings are replayed from its first heat, see `RatingReplay::run`.
+    /// the records are rebuilt, personal bests are kept per driver.
+    ///
+    /// drivers that drove in the same heat are different people and can not be merged.
+    ///
+    /// ## Arguments
+    /// * `merged` - the driver to merge into this driver
+    pub async fn merge(&self, merged: &Driver) -> CustomResult<()> {
+        if self == merged {
+            return Err(Error::InvalidArgumentError { name: "merged driver".to_string(), value: merged.name.clone() });
+        }
+
+        let laps = merged.get_laps().await?;
+        let heats = Session::from_laps(&laps).await?;
+
+        let mut client = get_pool().get().await?;
+        let transaction = client.transaction().await?;
+
+        let shared_heats = count_shared_heats()
+            .bind(&transaction, &self.id, &merged.id)
+            .one()
+            .await?;
+        if shared_heats > 0 {
+            return Err(Error::InvalidArgumentError {
+                name: "merged driver".to_string(),
+                value: format!("{} drove in {} heats with {}", merged.name, shared_heats, self.name),
+            });
+        }
+
+        move_laps_to_driver()
+            .bind(&transaction, &self.id, &merged.id)
+            .await?;
+        // the aliases have to be moved before the driver is deleted, they are deleted with it
+        DriverAlias::move_all(&transaction, merged, self).await?;
+        delete_driver()
+            .bind(&transaction, &merged.id)
+            .await?;
+        DriverAlias::add(&transaction, self, &merged.name).await?;
+
+        if let Some(first_heat) = heats.iter().map(|heat| heat.start_date).min() {
+            RatingReplay::schedule(&transaction, Some(first_heat)).await?;
+        }
+        Record::schedule_rebuild(&transaction).await?;
+        transaction.commit().await?;
+
+        // the merge is done, a failed rebuild stays scheduled and is retried by the cron job
+        if let Err(error) = Record::rebuild_all().await {
+            error!(target:"models/driver:merge", "Error rebuilding the records: {}", error);
+        }
+
+        self.clear_cache().await;
+        merged.clear_cache().await;
+        // the heats now show this driver instead of the merged one
+        for heat in &heats {
+            heat.clear_cache().await;
+        }
+
+        Ok(())
     }
 }
 
@@ -635,9 +865,18 @@ pub fn sanitize_name(name: &str) -> String {
 pub struct DriverStats {
     pub name: String,
     pub fastest_lap_time: f64,
+    /// the fastest lap time without the offset of the kart it was driven in
+    pub adjusted_fastest_lap_time: f64,
+    /// the fastest kart-adjusted lap time in usual track conditions
+    pub normalized_fastest_lap_time: f64,
     pub avg_lap_time: f64,
     pub median_lap_time: f64,
     pub total_laps: i32,
     pub total_heats: i32,
     pub rating: f64,
+    pub track: i32,
+    /// only given for a single driver, see `Driver::get_consistency`
+    pub consistency: Option<Consistency>,
+    /// only given for a single driver, see `Driver::is_provisional`
+    pub provisional: Option<bool>,
 }
@@ -0,0 +1,74 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::driver_aliases::{get_aliases_of_driver, insert_driver_alias, move_driver_aliases, DriverAlias as cDriverAlias};
+use crate::errors::CustomResult;
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+
+impl Into<DriverAlias> for cDriverAlias {
+    fn into(self) -> DriverAlias {
+        DriverAlias {
+            id: self.id,
+            alias: self.alias,
+            driver: self.driver,
+            created_at: self.created_at.to_naive_date(),
+        }
+    }
+}
+
+/// # alias of a driver
+/// another name a driver is known by. heats with laps under the alias are added to the driver.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DriverAlias {
+    pub id: i32,
+    pub alias: String,
+    pub driver: i32,
+    pub created_at: NaiveDateTime,
+}
+
+impl DriverAlias {
+    /// # get the aliases of a driver
+    ///
+    /// ## Arguments
+    /// * `driver` - the driver
+    ///
+    /// ## Returns
+    /// * `Vec<DriverAlias>` - the aliases, oldest first
+    pub async fn of_driver(driver: &Driver) -> CustomResult<Vec<DriverAlias>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_aliases_of_driver(), client, &driver.id))
+    }
+
+    /// # add an alias to a driver
+    /// an alias that already belongs to another driver is moved to the driver.
+    /// the alias should be sanitized, see `sanitize_name`.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `driver` - the driver
+    /// * `alias` - the alias
+    ///
+    /// ## Returns
+    /// * `DriverAlias` - the alias
+    pub async fn add<C: GenericClient>(client: &C, driver: &Driver, alias: &str) -> CustomResult<DriverAlias> {
+        Ok(select_from_db!(insert_driver_alias(), client, &alias, &driver.id))
+    }
+
+    /// # move the aliases of a driver
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `from` - the driver the aliases belong to
+    /// * `to` - the driver to move the aliases to
+    pub async fn move_all<C: GenericClient>(client: &C, from: &Driver, to: &Driver) -> CustomResult<()> {
+        move_driver_aliases()
+            .bind(client, &to.id, &from.id)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::driver_ratings::{delete_all_driver_ratings, get_ratings_of_driver, restore_driver_ratings_from_history, get_ratings_of_drivers_in_system, set_driver_rating, DriverRating as cDriverRating};
+use crate::errors::CustomResult;
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::rating_systems::{decay_uncertainty, provisional_sessions, RatingSystem, SystemRating};
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+
+impl Into<DriverRating> for cDriverRating {
+    fn into(self) -> DriverRating {
+        DriverRating {
+            id: self.id,
+            driver: self.driver,
+            system: self.system,
+            rating: SystemRating {
+                rating: self.rating,
+                uncertainty: self.uncertainty,
+                volatility: self.volatility,
+            },
+            last_rated: self.last_rated.map(|date| date.to_naive_date()),
+            amount_of_sessions: self.amount_of_sessions,
+            provisional: self.amount_of_sessions < provisional_sessions(),
+        }
+    }
+}
+
+/// # rating of a driver in a rating system
+/// every driver has a rating in each of the configured rating systems
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DriverRating {
+    pub id: i32,
+    pub driver: i32,
+    pub system: String,
+    pub rating: SystemRating,
+    /// the start of the last heat the driver was rated in
+    pub last_rated: Option<NaiveDateTime>,
+    pub amount_of_sessions: i32,
+    /// the driver has not been rated in enough heats to trust the rating, see `provisional_sessions`
+    pub provisional: bool,
+}
+
+impl DriverRating {
+    /// # set the rating of a driver
+    /// set the rating of a driver in a rating system, the rating is created when the driver
+    /// has no rating in the system yet
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `driver` - the id of the driver
+    /// * `system` - the name of the rating system
+    /// * `rating` - the new rating
+    /// * `rated_at` - the start of the heat the driver was rated in
+    ///
+    /// ## Returns
+    /// * `DriverRating` - the stored rating
+    pub async fn set<C: GenericClient>(
+        client: &C,
+        driver: i32,
+        system: &str,
+        rating: &SystemRating,
+        rated_at: NaiveDateTime,
+    ) -> CustomResult<DriverRating> {
+        Ok(select_from_db!(
+            set_driver_rating(),
+            client,
+            &driver,
+            &system,
+            &rating.rating,
+            &rating.uncertainty,
+            &rating.volatility,
+            &rated_at.to_primitive_date()))
+    }
+
+    /// # get the ratings of drivers in a system
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `system` - the name of the rating system
+    /// * `drivers` - the ids of the drivers
+    ///
+    /// ## Returns
+    /// * `HashMap<i32, DriverRating>` - the ratings by driver id. drivers that have not been
+    ///   rated in the system are missing
+    pub async fn get_of_drivers<C: GenericClient>(
+        client: &C,
+        system: &str,
+        drivers: &[i32],
+    ) -> CustomResult<HashMap<i32, DriverRating>> {
+        let ratings: Vec<DriverRating> = select_vec_from_db!(get_ratings_of_drivers_in_system(), client, &system, &drivers);
+
+        Ok(ratings
+            .into_iter()
+            .map(|rating| (rating.driver, rating))
+            .collect())
+    }
+
+    /// # get the rating on a date
+    /// the rating with the uncertainty grown for the time since the driver was last rated,
+    /// see `decay_uncertainty`
+    ///
+    /// ## Arguments
+    /// * `system` - the rating system of the rating
+    /// * `date` - the date
+    ///
+    /// ## Returns
+    /// * `SystemRating` - the rating
+    pub fn rating_at(&self, system: &dyn RatingSystem, date: NaiveDateTime) -> SystemRating {
+        match self.last_rated {
+            Some(last_rated) => {
+                let days_inactive = (date - last_rated).num_seconds() as f64 / 86400.0;
+                decay_uncertainty(system, &self.rating, days_inactive)
+            }
+            None => self.rating,
+        }
+    }
+
+    /// # get all ratings of a driver
+    ///
+    /// ## Arguments
+    /// * `driver` - the driver
+    ///
+    /// ## Returns
+    /// * `Vec<DriverRating>` - the rating of the driver in every system they have been rated in
+    pub async fn from_driver(driver: &Driver) -> CustomResult<Vec<DriverRating>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_ratings_of_driver(), client, &driver.id))
+    }
+
+    /// # delete all ratings
+    /// delete the ratings of all drivers in all systems
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of deleted ratings
+    pub async fn delete_all<C: GenericClient>(client: &C) -> CustomResult<u64> {
+        Ok(delete_all_driver_ratings()
+            .bind(client)
+            .await?)
+    }
+
+    /// # restore the ratings from the rating history
+    /// set the rating of every driver in every system to the rating after the last heat
+    /// in the rating history
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of restored ratings
+    pub async fn restore_from_history<C: GenericClient>(client: &C) -> CustomResult<u64> {
+        Ok(restore_driver_ratings_from_history()
+            .bind(client)
+            .await?)
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::heat_payloads::{get_latest_heat_payload, get_latest_heat_payloads, insert_heat_payload_if_changed, HeatPayload as cHeatPayload};
+use crate::errors::CustomResult;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+
+impl Into<HeatPayload> for cHeatPayload {
+    fn into(self) -> HeatPayload {
+        HeatPayload {
+            id: self.id,
+            heat_id: self.heat_id,
+            fetched_at: self.fetched_at.to_naive_date(),
+            payload: self.payload,
+        }
+    }
+}
+
+/// # raw heat payload
+/// the response of the venue for a heat exactly as it was fetched.
+/// a heat can be fetched multiple times, every fetch is kept.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HeatPayload {
+    pub id: i32,
+    pub heat_id: String,
+    pub fetched_at: NaiveDateTime,
+    pub payload: String,
+}
+
+impl HeatPayload {
+    /// # archive a payload
+    /// store the payload of a heat with the current time as fetch time, unless it is the same as
+    /// the latest archived payload of the heat. refetching a heat that did not change adds nothing.
+    ///
+    /// ## Arguments
+    /// * `heat_id` - the id of the heat
+    /// * `payload` - the raw payload
+    ///
+    /// ## Returns
+    /// * `Option<HeatPayload>` - the archived payload, `None` when it was already archived
+    pub async fn new_if_changed(heat_id: &str, payload: &str) -> CustomResult<Option<HeatPayload>> {
+        let fetched_at = Utc::now().naive_utc().to_primitive_date();
+
+        let client = &get_pool().get().await?;
+        Ok(select_opt_from_db!(insert_heat_payload_if_changed(), client, &heat_id, &fetched_at, &payload))
+    }
+
+    /// # get the latest payload of a heat
+    ///
+    /// ## Arguments
+    /// * `heat_id` - the id of the heat
+    ///
+    /// ## Returns
+    /// * `Option<HeatPayload>` - the most recently fetched payload, `None` if the heat is not archived
+    pub async fn get_latest(heat_id: &str) -> CustomResult<Option<HeatPayload>> {
+        let client = &get_pool().get().await?;
+        Ok(select_opt_from_db!(get_latest_heat_payload(), client, &heat_id))
+    }
+
+    /// # get the latest payload of all heats
+    ///
+    /// ## Returns
+    /// * `Vec<HeatPayload>` - the most recently fetched payload of every archived heat
+    pub async fn get_latest_all() -> CustomResult<Vec<HeatPayload>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_latest_heat_payloads(), client))
+    }
+}
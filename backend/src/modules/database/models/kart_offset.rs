@@ -0,0 +1,179 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::cornucopia::queries::kart_offsets::{delete_all_kart_offsets, get_kart_observations, get_kart_offsets_of_karts, insert_kart_offsets_bulk, KartObservation as cKartObservation, KartOffset as cKartOffset};
+use crate::errors::CustomResult;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::lap::Lap;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::models::vehicle::Vehicle;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::kart_normalization::{fit_kart_offsets, KartObservation};
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+
+impl Into<KartOffset> for cKartOffset {
+    fn into(self) -> KartOffset {
+        KartOffset {
+            id: self.id,
+            kart: self.kart,
+            period: self.period.to_naive_date(),
+            time_offset: self.time_offset,
+            amount_of_laps: self.amount_of_laps,
+        }
+    }
+}
+
+impl Into<KartObservation> for cKartObservation {
+    fn into(self) -> KartObservation {
+        KartObservation {
+            heat: self.heat,
+            driver: self.driver,
+            kart: self.kart,
+            period: self.period.to_naive_date(),
+            lap_time: self.lap_time,
+            amount_of_laps: self.amount_of_laps,
+        }
+    }
+}
+
+/// # offset of a kart
+/// how much slower a kart was than the average kart during a month.
+/// subtracting the offset from a lap time gives the kart-adjusted lap time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KartOffset {
+    pub id: i32,
+    /// the database id of the kart
+    pub kart: i32,
+    /// the first day of the month
+    pub period: NaiveDateTime,
+    pub time_offset: f64,
+    pub amount_of_laps: i32,
+}
+
+/// # the offsets of karts
+/// lookup for the offsets of the karts a set of laps were driven in
+pub struct KartOffsets {
+    offsets: HashMap<(i32, NaiveDateTime), f64>,
+}
+
+impl KartOffset {
+    /// # fit the offsets of all karts
+    /// estimate the offset of every kart in every month from all laps and replace the
+    /// stored offsets. see `fit_kart_offsets`
+    ///
+    /// the adjusted lap times change with the offsets, so the cached responses of all karts, the
+    /// lists and the tracks are invalidated
+    ///
+    /// ## Returns
+    /// * `usize` - the amount of stored offsets
+    pub async fn fit_all() -> CustomResult<usize> {
+        let mut client = get_pool().get().await?;
+
+        let observations: Vec<KartObservation> = select_vec_from_db!(get_kart_observations(), &client);
+        let effects = fit_kart_offsets(&observations);
+
+        let transaction = client.transaction().await?;
+        delete_all_kart_offsets()
+            .bind(&transaction)
+            .await?;
+
+        let mut karts: Vec<i32> = Vec::new();
+        let mut periods: Vec<PrimitiveDateTime> = Vec::new();
+        let mut time_offsets: Vec<f64> = Vec::new();
+        let mut amounts_of_laps: Vec<i32> = Vec::new();
+        for effect in &effects {
+            karts.push(effect.kart);
+            periods.push(effect.period.to_primitive_date());
+            time_offsets.push(effect.time_offset);
+            amounts_of_laps.push(effect.amount_of_laps);
+        }
+
+        insert_kart_offsets_bulk()
+            .bind(&transaction, &karts, &periods, &time_offsets, &amounts_of_laps)
+            .all()
+            .await?;
+        transaction.commit().await?;
+
+        let tags = [
+            vec![CacheTag::Drivers, CacheTag::Heats, CacheTag::Karts],
+            CacheTag::of_karts(&Vehicle::get_all().await?),
+            CacheTag::of_tracks(&Track::get_all().await?),
+        ].concat();
+        CacheTag::invalidate(&tags).await;
+
+        Ok(effects.len())
+    }
+
+    /// # get the period of a date
+    /// the offsets of karts are estimated per month
+    ///
+    /// ## Arguments
+    /// * `date` - the date
+    ///
+    /// ## Returns
+    /// * `NaiveDateTime` - the start of the month of the date
+    pub fn period_of(date: NaiveDateTime) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+}
+
+impl KartOffsets {
+    /// # get the offsets of the karts of laps
+    ///
+    /// ## Arguments
+    /// * `laps` - the laps
+    ///
+    /// ## Returns
+    /// * `KartOffsets` - the offsets of all karts the laps were driven in
+    pub async fn from_laps(laps: &[Lap]) -> CustomResult<KartOffsets> {
+        let karts: Vec<i32> = laps
+            .iter()
+            .map(|lap| lap.kart_id)
+            .collect::<HashSet<i32>>()
+            .into_iter()
+            .collect();
+
+        let client = &get_pool().get().await?;
+        let offsets: Vec<KartOffset> = select_vec_from_db!(get_kart_offsets_of_karts(), client, &karts);
+
+        Ok(KartOffsets {
+            offsets: offsets
+                .into_iter()
+                .map(|offset| ((offset.kart, offset.period), offset.time_offset))
+                .collect(),
+        })
+    }
+
+    /// # get the offset of a kart
+    ///
+    /// ## Arguments
+    /// * `kart` - the database id of the kart
+    /// * `date` - the date the kart was driven on
+    ///
+    /// ## Returns
+    /// * `f64` - the offset, 0 when the offset of the kart is unknown
+    pub fn get(&self, kart: i32, date: NaiveDateTime) -> f64 {
+        *self.offsets
+            .get(&(kart, KartOffset::period_of(date)))
+            .unwrap_or(&0.0)
+    }
+
+    /// # get the kart-adjusted time of a lap
+    ///
+    /// ## Arguments
+    /// * `lap` - the lap
+    /// * `date` - the date the lap was driven on
+    ///
+    /// ## Returns
+    /// * `f64` - the lap time without the offset of the kart
+    pub fn adjust(&self, lap: &Lap, date: NaiveDateTime) -> f64 {
+        lap.lap_time - self.get(lap.kart_id, date)
+    }
+}
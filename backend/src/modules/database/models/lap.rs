@@ -4,18 +4,23 @@ use std::collections::{HashMap};
 use identifiable_derive::HasId;
 use serde::{Deserialize, Serialize};
 
-use crate::cornucopia::queries::laps::{get_lap, get_laps_from_driver, insert_laps_bulk, get_laps_from_kart, get_laps_from_drivers, get_laps_from_heat, get_laps_from_heats};
+use crate::cornucopia::queries::laps::{get_lap, get_laps_from_driver, insert_laps_bulk, get_laps_from_kart, get_laps_from_drivers, get_laps_from_heat, get_laps_from_heats, update_laps_classification};
 
 use crate::modules::helpers::math::Math;
 use crate::modules::database::models::driver::Driver;
 use crate::modules::database::models::general::get_pool;
 use crate::modules::database::models::session::Session;
+use crate::modules::database::models::track::Track;
 use crate::modules::database::models::vehicle::Vehicle;
+use crate::modules::lap_classification::{classify_laps, LapClassification};
 use crate::modules::traits::has_id::HasIdTrait;
 use crate::TemplateDataLap;
 
 use crate::modules::database::query::get_vec as select_vec_from_db;
-use crate::modules::database::query::get as select_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use cornucopia_async::GenericClient;
+use snafu::OptionExt;
+use crate::errors::{CustomResult, NotFoundSnafu};
 
 
 #[derive( Serialize, Debug, Clone, Deserialize)]
@@ -36,6 +41,7 @@ impl NewLap {
             lap_in_heat: self.lap_in_heat,
             lap_time: self.lap_time,
             kart_id: self.kart_id,
+            classification: LapClassification::Clean,
         }
     }
 }
@@ -48,6 +54,7 @@ pub struct Lap {
     pub lap_in_heat: i32,
     pub lap_time: f64,
     pub kart_id: i32,
+    pub classification: LapClassification,
 }
 
 impl Into<i32> for Lap {
@@ -65,6 +72,7 @@ impl Into<Lap> for crate::cornucopia::queries::laps::Lap {
             lap_in_heat: self.lap_in_heat,
             lap_time: self.lap_time,
             kart_id: self.kart_id,
+            classification: LapClassification::from_name(&self.classification).unwrap_or(LapClassification::Clean),
         }
     }
 }
@@ -82,11 +90,12 @@ impl Lap {
     /// insert multiple laps into the database from a vector of NewLap objects
     ///
     /// ## Arguments
+    /// * `client` - The database client
     /// * `new_laps` - The new laps to insert
     ///
     /// ## Returns
     /// * `Vec<Lap>` - The inserted laps
-    pub async fn insert_bulk(new_laps: &Vec<NewLap>) -> Vec<Lap> {
+    pub async fn insert_bulk<C: GenericClient>(client: &C, new_laps: &Vec<NewLap>) -> CustomResult<Vec<Lap>> {
         let mut heats: Vec<i32> = Vec::new();
         let mut drivers: Vec<i32> = Vec::new();
         let mut laps_in_heat: Vec<i32> = Vec::new();
@@ -101,15 +110,42 @@ impl Lap {
             karts.push(lap.kart_id);
         }
 
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(
+        Ok(select_vec_from_db!(
             insert_laps_bulk(),
             client,
             &heats,
             &drivers,
             &laps_in_heat,
             &lap_times,
-            &karts)
+            &karts))
+    }
+
+    /// # classify the laps of a heat
+    /// classify all laps of a heat with the thresholds of the track it was driven on and store
+    /// the classifications. see `classify_laps`
+    ///
+    /// ## Arguments
+    /// * `client` - The database client
+    /// * `heat` - The heat to classify the laps of
+    ///
+    /// ## Returns
+    /// * `Vec<Lap>` - The classified laps
+    pub async fn classify_heat<C: GenericClient>(client: &C, heat: &Session) -> CustomResult<Vec<Lap>> {
+        let laps: Vec<Lap> = select_vec_from_db!(get_laps_from_heat(), client, &heat.id);
+        let track = Track::from_id(client, heat.track).await?;
+
+        let mut laps_per_classification: HashMap<LapClassification, Vec<i32>> = HashMap::new();
+        for (lap, classification) in classify_laps(&laps, &track) {
+            laps_per_classification.entry(classification).or_default().push(lap);
+        }
+
+        let mut classified_laps = Vec::new();
+        for (classification, laps) in laps_per_classification {
+            let mut updated: Vec<Lap> = select_vec_from_db!(update_laps_classification(), client, &classification.as_str(), &laps);
+            classified_laps.append(&mut updated);
+        }
+
+        Ok(classified_laps)
     }
 
     /************ GETTERS ************/
@@ -121,10 +157,11 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Lap` - The lap with the given id
-    pub async fn from_id(id: i32) -> Lap {
-        let client = &get_pool().get().await.unwrap();
+    pub async fn from_id(id: i32) -> CustomResult<Lap> {
+        let client = &get_pool().get().await?;
 
-        select_from_db!(get_lap(), client, &id)
+        select_opt_from_db!(get_lap(), client, &id)
+            .context(NotFoundSnafu { what: format!("lap {}", id) })
     }
 
     /// # get all laps driven by a kart
@@ -136,9 +173,9 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - All laps driven by the kart
-    pub async fn from_kart(kart: &Vehicle) -> Vec<Lap> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_laps_from_kart(), client, &kart.id)
+    pub async fn from_kart(kart: &Vehicle) -> CustomResult<Vec<Lap>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_laps_from_kart(), client, &kart.id))
     }
 
     pub fn from_kart_offline(all_laps: &[Lap], kart: &Vehicle) -> Vec<Lap> {
@@ -158,9 +195,9 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - All laps driven by the driver
-    pub async fn from_driver(driver: &Driver) -> Vec<Lap> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_laps_from_driver(), client, &driver.id)
+    pub async fn from_driver(driver: &Driver) -> CustomResult<Vec<Lap>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_laps_from_driver(), client, &driver.id))
     }
 
     /// # get all laps driven by a list of drivers
@@ -172,13 +209,13 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - All laps driven by the drivers
-    pub async fn from_drivers(drivers: &[Driver]) -> Vec<Lap> {
-        let client = &get_pool().get().await.unwrap();
+    pub async fn from_drivers(drivers: &[Driver]) -> CustomResult<Vec<Lap>> {
+        let client = &get_pool().get().await?;
 
-        select_vec_from_db!(
+        Ok(select_vec_from_db!(
             get_laps_from_drivers(),
             client,
-            &drivers.iter().map(|e| e.id).collect::<Vec<i32>>())
+            &drivers.iter().map(|e| e.id).collect::<Vec<i32>>()))
     }
 
     /// # get all laps driven by a list of drivers as map
@@ -191,8 +228,8 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Hashmap<Driver, Vec<Lap>>` - All laps driven by the drivers
-    pub async fn from_drivers_as_map(drivers: &[Driver]) -> HashMap<Driver, Vec<Lap>> {
-        let laps = Lap::from_drivers(drivers).await;
+    pub async fn from_drivers_as_map(drivers: &[Driver]) -> CustomResult<HashMap<Driver, Vec<Lap>>> {
+        let laps = Lap::from_drivers(drivers).await?;
 
         let mut heat_lap_map: HashMap<Driver, Vec<Lap>> = HashMap::new();
         for lap in laps {
@@ -209,7 +246,7 @@ impl Lap {
             }
         }
 
-        return heat_lap_map;
+        Ok(heat_lap_map)
     }
 
     /// # get all laps driven in a heat
@@ -221,9 +258,9 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - All laps driven in the heat
-    pub async fn from_heat(heat: &Session) -> Vec<Lap> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_laps_from_heat(), client, &heat.id)
+    pub async fn from_heat(heat: &Session) -> CustomResult<Vec<Lap>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_laps_from_heat(), client, &heat.id))
     }
 
     /// # get all laps driven in a list of heats
@@ -235,11 +272,11 @@ impl Lap {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - All laps driven in the heats
-    pub async fn from_heats(heats: &[Session]) -> Vec<Lap> {
-        let client = &get_pool().get().await.unwrap();
+    pub async fn from_heats(heats: &[Session]) -> CustomResult<Vec<Lap>> {
+        let client = &get_pool().get().await?;
 
         let ids: Vec<i32> = heats.iter().map(|e| e.id).collect();
-        select_vec_from_db!(get_laps_from_heats(), client, &ids)
+        Ok(select_vec_from_db!(get_laps_from_heats(), client, &ids))
     }
 
 
@@ -327,39 +364,6 @@ impl Lap {
         sum / laps.len() as f64
     }
 
-    /// # get the laps that are detemined to be outliers
-    /// get the laps that are detemined to be outliers.
-    /// this is deteremed the exact same way as in `filter_outliers`
-    ///
-    /// ## Arguments
-    /// * `laps` - The laps to get the outliers for
-    ///
-    /// ## Returns
-    /// * `Vec<TemplateDataLap>` - The outliers
-    pub fn get_outlier_laps(laps: &Vec<TemplateDataLap>) -> Vec<TemplateDataLap> {
-        // we expect all drivers to be the same so we only look at the lapstimes
-        let mut outliers: Vec<TemplateDataLap> = Vec::new();
-
-        // get the standard deviation of the laptimes in vec laps
-        let mut lap_times: Vec<f64> = Vec::new();
-        for lap in laps.iter() {
-            lap_times.push(lap.lap_time);
-        }
-        let standard_deviation = Lap::get_standard_deviation_of_laps(laps);
-
-        // get the center of the laptimes
-        let center = Lap::get_mean_of_laps(laps);
-
-        // get the outliers
-        for lap in laps.iter() {
-            if lap.lap_time > center + (standard_deviation * 2.0) || lap.lap_time < 45.0 {
-                outliers.push(lap.clone());
-            }
-        }
-
-        outliers
-    }
-
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -0,0 +1,318 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use json_response_derive::JsonResponse;
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::driver::{get_leaderboard, LeaderboardEntry as cLeaderboardEntry};
+use crate::errors::CustomResult;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::track::Track;
+use crate::modules::rating_systems::{get_main_rating_system, provisional_sessions};
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+
+impl Into<LeaderboardEntry> for cLeaderboardEntry {
+    fn into(self) -> LeaderboardEntry {
+        LeaderboardEntry {
+            position: self.position,
+            driver_name: self.driver_name,
+            fastest_lap_time: self.fastest_lap_time,
+            adjusted_fastest_lap_time: self.adjusted_fastest_lap_time,
+            rating: self.rating,
+            total_laps: self.total_laps,
+            total_heats: self.total_heats,
+            provisional: self.provisional,
+        }
+    }
+}
+
+/// # time window of a leaderboard
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardWindow {
+    Day,
+    /// monday up to and including sunday
+    Week,
+    Month,
+    /// the calendar year
+    Season,
+    /// the given dates, both included
+    Custom,
+}
+
+/// # what a leaderboard is ranked on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// the fastest clean lap, fastest first
+    FastestLap,
+    /// the fastest clean lap corrected for the kart, fastest first
+    AdjustedFastestLap,
+    /// the rating in the main rating system after the last heat in the window, highest first
+    Rating,
+    /// the amount of laps driven, most first
+    LapsDriven,
+}
+
+/// # a driver on a leaderboard
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    /// drivers with the same value share a position
+    pub position: i32,
+    pub driver_name: String,
+    pub fastest_lap_time: f64,
+    pub adjusted_fastest_lap_time: f64,
+    /// `None` when the driver was not rated in the window
+    pub rating: Option<f64>,
+    pub total_laps: i32,
+    pub total_heats: i32,
+    /// the rating of the driver is provisional, see `provisional_sessions`
+    pub provisional: bool,
+}
+
+/// # what a driver needs to be on a leaderboard
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Qualification {
+    /// the amount of clean laps in the window, at least 1
+    pub min_laps: i64,
+    /// whether drivers with a provisional rating are on the leaderboard
+    pub include_provisional: bool,
+}
+
+/// # a page of a leaderboard
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct Leaderboard {
+    pub track: Track,
+    pub window: LeaderboardWindow,
+    pub metric: LeaderboardMetric,
+    /// the first day of the window
+    pub from: NaiveDate,
+    /// the last day of the window
+    pub to: NaiveDate,
+    #[serde(flatten)]
+    pub qualification: Qualification,
+    pub page: u32,
+    pub page_size: u32,
+    /// the amount of drivers on the whole leaderboard, 0 when the page is past the last driver
+    pub total_entries: i32,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl LeaderboardWindow {
+    /// # get a window by its name
+    ///
+    /// ## Arguments
+    /// * `name` - the name of the window
+    ///
+    /// ## Returns
+    /// * `Option<LeaderboardWindow>` - the window, `None` when the name is unknown
+    pub fn from_name(name: &str) -> Option<LeaderboardWindow> {
+        match name {
+            "day" => Some(LeaderboardWindow::Day),
+            "week" => Some(LeaderboardWindow::Week),
+            "month" => Some(LeaderboardWindow::Month),
+            "season" => Some(LeaderboardWindow::Season),
+            "custom" => Some(LeaderboardWindow::Custom),
+            _ => None,
+        }
+    }
+
+    /// # get the days in the window
+    /// custom windows are the given dates, the others are the day, week, month or year `date` is in.
+    ///
+    /// ## Arguments
+    /// * `date` - a day in the window
+    /// * `from` - the first day of a custom window
+    /// * `to` - the last day of a custom window
+    ///
+    /// ## Returns
+    /// * `(NaiveDate, NaiveDate)` - the first and last day of the window
+    pub fn days(&self, date: NaiveDate, from: NaiveDate, to: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            LeaderboardWindow::Day => (date, date),
+            LeaderboardWindow::Week => {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (monday, monday + Duration::days(6))
+            }
+            LeaderboardWindow::Month => {
+                let first = date.with_day(1).unwrap();
+                let next_month = if first.month() == 12 {
+                    NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+                }
+                .unwrap();
+                (first, next_month - Duration::days(1))
+            }
+            LeaderboardWindow::Season => (
+                NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(date.year(), 12, 31).unwrap(),
+            ),
+            LeaderboardWindow::Custom => (from, to),
+        }
+    }
+}
+
+impl LeaderboardMetric {
+    /// # get the name of the metric
+    ///
+    /// ## Returns
+    /// * `&str` - the name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardMetric::FastestLap => "fastest_lap",
+            LeaderboardMetric::AdjustedFastestLap => "adjusted_fastest_lap",
+            LeaderboardMetric::Rating => "rating",
+            LeaderboardMetric::LapsDriven => "laps_driven",
+        }
+    }
+
+    /// # get a metric by its name
+    ///
+    /// ## Arguments
+    /// * `name` - the name of the metric
+    ///
+    /// ## Returns
+    /// * `Option<LeaderboardMetric>` - the metric, `None` when the name is unknown
+    pub fn from_name(name: &str) -> Option<LeaderboardMetric> {
+        match name {
+            "fastest_lap" => Some(LeaderboardMetric::FastestLap),
+            "adjusted_fastest_lap" => Some(LeaderboardMetric::AdjustedFastestLap),
+            "rating" => Some(LeaderboardMetric::Rating),
+            "laps_driven" => Some(LeaderboardMetric::LapsDriven),
+            _ => None,
+        }
+    }
+}
+
+impl Leaderboard {
+    /// # get a page of a leaderboard
+    /// only drivers that meet the qualification are on the leaderboard. on the rating leaderboard
+    /// only drivers that were rated in the window are.
+    ///
+    /// ## Arguments
+    /// * `track` - the track
+    /// * `window` - the time window
+    /// * `days` - the first and last day of the window
+    /// * `metric` - what to rank the drivers on
+    /// * `qualification` - what a driver needs to be on the leaderboard
+    /// * `page` - the page, starting at 0
+    /// * `page_size` - the amount of drivers on a page
+    ///
+    /// ## Returns
+    /// * `Leaderboard` - the page of the leaderboard
+    pub async fn get(
+        track: Track,
+        window: LeaderboardWindow,
+        days: (NaiveDate, NaiveDate),
+        metric: LeaderboardMetric,
+        qualification: Qualification,
+        page: u32,
+        page_size: u32,
+    ) -> CustomResult<Leaderboard> {
+        let client = &get_pool().get().await?;
+
+        let (from, to) = days;
+        let qualification = Qualification {
+            min_laps: qualification.min_laps.max(1),
+            ..qualification
+        };
+        let from_date: NaiveDateTime = from.and_hms_opt(0, 0, 0).unwrap();
+        let to_date: NaiveDateTime = to.and_hms_opt(0, 0, 0).unwrap() + Duration::days(1);
+
+        let entries: Vec<cLeaderboardEntry> = get_leaderboard()
+            .bind(
+                client,
+                &get_main_rating_system().name(),
+                &track.id,
+                &from_date.to_primitive_date(),
+                &to_date.to_primitive_date(),
+                &provisional_sessions(),
+                &qualification.min_laps,
+                &metric.as_str(),
+                &qualification.include_provisional,
+                &(page_size as i64),
+                &((page * page_size) as i64),
+            )
+            .all()
+            .await?;
+        let total_entries = entries.first().map_or(0, |entry| entry.total_entries);
+
+        Ok(Leaderboard {
+            track,
+            window,
+            metric,
+            from,
+            to,
+            qualification,
+            page,
+            page_size,
+            total_entries,
+            entries: entries.into_iter().map(|entry| entry.into()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn days(window: LeaderboardWindow, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        window.days(day, date(2000, 1, 1), date(2000, 1, 2))
+    }
+
+    #[test]
+    fn day() {
+        assert_eq!(days(LeaderboardWindow::Day, date(2024, 5, 15)), (date(2024, 5, 15), date(2024, 5, 15)));
+    }
+
+    #[test]
+    fn week_is_monday_to_sunday() {
+        let week = (date(2024, 5, 13), date(2024, 5, 19));
+
+        assert_eq!(days(LeaderboardWindow::Week, date(2024, 5, 13)), week);
+        assert_eq!(days(LeaderboardWindow::Week, date(2024, 5, 15)), week);
+        assert_eq!(days(LeaderboardWindow::Week, date(2024, 5, 19)), week);
+        assert_eq!(days(LeaderboardWindow::Week, date(2024, 12, 31)), (date(2024, 12, 30), date(2025, 1, 5)));
+    }
+
+    #[test]
+    fn month() {
+        assert_eq!(days(LeaderboardWindow::Month, date(2024, 2, 10)), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(days(LeaderboardWindow::Month, date(2023, 2, 28)), (date(2023, 2, 1), date(2023, 2, 28)));
+        assert_eq!(days(LeaderboardWindow::Month, date(2024, 12, 31)), (date(2024, 12, 1), date(2024, 12, 31)));
+    }
+
+    #[test]
+    fn season_is_the_calendar_year() {
+        assert_eq!(days(LeaderboardWindow::Season, date(2024, 7, 1)), (date(2024, 1, 1), date(2024, 12, 31)));
+    }
+
+    #[test]
+    fn custom_is_the_given_dates() {
+        assert_eq!(days(LeaderboardWindow::Custom, date(2024, 7, 1)), (date(2000, 1, 1), date(2000, 1, 2)));
+    }
+
+    #[test]
+    fn names() {
+        for name in ["day", "week", "month", "season", "custom"] {
+            assert!(LeaderboardWindow::from_name(name).is_some());
+        }
+        assert_eq!(LeaderboardWindow::from_name("year"), None);
+
+        for metric in [
+            LeaderboardMetric::FastestLap,
+            LeaderboardMetric::AdjustedFastestLap,
+            LeaderboardMetric::Rating,
+            LeaderboardMetric::LapsDriven,
+        ] {
+            assert_eq!(LeaderboardMetric::from_name(metric.as_str()), Some(metric));
+        }
+        assert_eq!(LeaderboardMetric::from_name("wins"), None);
+    }
+}
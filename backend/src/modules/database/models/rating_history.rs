@@ -0,0 +1,150 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::rating_history::{delete_rating_history_from, get_rating_history_of_driver, get_rating_history_of_system, insert_rating_history, DriverRatingChange as cDriverRatingChange, RatingHistory as cRatingHistory};
+use crate::errors::CustomResult;
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::rating_systems::SystemRating;
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+
+impl Into<RatingHistory> for cRatingHistory {
+    fn into(self) -> RatingHistory {
+        RatingHistory {
+            id: self.id,
+            driver: self.driver,
+            session: self.session,
+            rating_before: self.rating_before,
+            uncertainty_before: self.uncertainty_before,
+            rating_after: self.rating_after,
+            uncertainty_after: self.uncertainty_after,
+            system: self.system,
+            volatility_before: self.volatility_before,
+            volatility_after: self.volatility_after,
+        }
+    }
+}
+
+impl Into<RatingChange> for cDriverRatingChange {
+    fn into(self) -> RatingChange {
+        RatingChange {
+            heat_id: self.heat_id,
+            start_date: self.start_date.to_naive_date(),
+            rating_before: self.rating_before,
+            uncertainty_before: self.uncertainty_before,
+            rating_after: self.rating_after,
+            uncertainty_after: self.uncertainty_after,
+        }
+    }
+}
+
+/// # rating history
+/// the rating of a driver before and after a heat
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RatingHistory {
+    pub id: i32,
+    pub driver: i32,
+    pub session: i32,
+    pub rating_before: f64,
+    pub uncertainty_before: f64,
+    pub rating_after: f64,
+    pub uncertainty_after: f64,
+    pub system: String,
+    pub volatility_before: Option<f64>,
+    pub volatility_after: Option<f64>,
+}
+
+/// # rating change
+/// the change in rating of a driver in a heat, with the heat it happened in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RatingChange {
+    pub heat_id: String,
+    pub start_date: NaiveDateTime,
+    pub rating_before: f64,
+    pub uncertainty_before: f64,
+    pub rating_after: f64,
+    pub uncertainty_after: f64,
+}
+
+impl RatingHistory {
+    /// # record a rating change
+    /// record the rating of a driver before and after a heat.
+    /// when the change was already recorded it is overwritten, so ratings can be replayed.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `driver` - the id of the driver
+    /// * `session` - the database id of the heat
+    /// * `system` - the name of the rating system
+    /// * `before` - the rating before the heat
+    /// * `after` - the rating after the heat
+    ///
+    /// ## Returns
+    /// * `RatingHistory` - the recorded change
+    pub async fn new<C: GenericClient>(
+        client: &C,
+        driver: i32,
+        session: i32,
+        system: &str,
+        before: &SystemRating,
+        after: &SystemRating,
+    ) -> CustomResult<RatingHistory> {
+        Ok(select_from_db!(
+            insert_rating_history(),
+            client,
+            &driver,
+            &session,
+            &system,
+            &before.rating,
+            &before.uncertainty,
+            &before.volatility,
+            &after.rating,
+            &after.uncertainty,
+            &after.volatility))
+    }
+
+    /// # delete the rating history from a date
+    /// delete the rating changes of all heats driven on or after a date
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `from` - the date to delete from, `None` deletes the whole history
+    ///
+    /// ## Returns
+    /// * `u64` - the amount of deleted rating changes
+    pub async fn delete_from<C: GenericClient>(client: &C, from: Option<NaiveDateTime>) -> CustomResult<u64> {
+        let from = from.map(|date| date.to_primitive_date());
+        Ok(delete_rating_history_from()
+            .bind(client, &from)
+            .one()
+            .await? as u64)
+    }
+
+    /// # get the rating history of a driver
+    ///
+    /// ## Arguments
+    /// * `driver` - the driver
+    /// * `system` - the name of the rating system
+    ///
+    /// ## Returns
+    /// * `Vec<RatingChange>` - the rating changes of the driver, oldest first
+    pub async fn from_driver(driver: &Driver, system: &str) -> CustomResult<Vec<RatingChange>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_rating_history_of_driver(), client, &driver.id, &system))
+    }
+
+    /// # get the rating history of a rating system
+    ///
+    /// ## Arguments
+    /// * `system` - the name of the rating system
+    ///
+    /// ## Returns
+    /// * `Vec<RatingHistory>` - every recorded rating change in the system
+    pub async fn from_system(system: &str) -> CustomResult<Vec<RatingHistory>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_rating_history_of_system(), client, &system))
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::rating_replays::{create_rating_replay, finish_rating_replay, get_unfinished_rating_replay, restart_rating_replay, set_rating_replay_progress, RatingReplay as cRatingReplay};
+use crate::errors::CustomResult;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::driver_rating::DriverRating;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::rating_history::RatingHistory;
+use crate::modules::database::models::session::Session;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use crate::modules::rating_systems::get_main_rating_system;
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+
+impl Into<RatingReplay> for cRatingReplay {
+    fn into(self) -> RatingReplay {
+        RatingReplay {
+            id: self.id,
+            from_date: self.from_date.map(|date| date.to_naive_date()),
+            last_start_date: self.last_start_date.map(|date| date.to_naive_date()),
+            last_session: self.last_session,
+            created_at: self.created_at.to_naive_date(),
+            finished_at: self.finished_at.map(|date| date.to_naive_date()),
+        }
+    }
+}
+
+/// # replay of the ratings
+/// the ratings of the heats from a date are recomputed in the order the heats were driven.
+/// the ratings are first restored to what they were before the date, using the rating history,
+/// and every rated heat is recorded as a checkpoint. an interrupted replay continues after the
+/// last checkpoint. there is at most one unfinished replay, all changes to past heats are
+/// merged into it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RatingReplay {
+    pub id: i32,
+    /// the date to replay from, `None` replays all heats
+    pub from_date: Option<NaiveDateTime>,
+    pub last_start_date: Option<NaiveDateTime>,
+    /// the last heat that was rated, `None` when the replay has not started yet
+    pub last_session: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+impl RatingReplay {
+    /// # get the unfinished replay
+    /// the replay is locked until the end of the transaction of the client
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `Option<RatingReplay>` - the unfinished replay, `None` when there is none
+    pub async fn get_unfinished<C: GenericClient>(client: &C) -> CustomResult<Option<RatingReplay>> {
+        Ok(select_opt_from_db!(get_unfinished_rating_replay(), client))
+    }
+
+    /// # schedule a replay
+    /// make sure the ratings are replayed from a date. when a replay is unfinished it is
+    /// restarted if it already rated heats after the date, otherwise it continues as is.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `from` - the date to replay from, `None` replays all heats
+    ///
+    /// ## Returns
+    /// * `RatingReplay` - the replay that will rate the heats
+    pub async fn schedule<C: GenericClient>(client: &C, from: Option<NaiveDateTime>) -> CustomResult<RatingReplay> {
+        let replay = match RatingReplay::get_unfinished(client).await? {
+            Some(replay) => replay,
+            None => {
+                let from = from.map(|date| date.to_primitive_date());
+                return Ok(select_from_db!(create_rating_replay(), client, &from));
+            }
+        };
+
+        let restart_from = match replay.last_start_date {
+            // not started yet, so it can start from the earliest of both dates
+            None => match (replay.from_date, from) {
+                (Some(replay_from), Some(from)) if from < replay_from => Some(Some(from)),
+                (Some(_), None) => Some(None),
+                _ => None,
+            },
+            // the heats before the last checkpoint are rated correctly, start over from the date
+            Some(last_start_date) => match from {
+                Some(from) if from > last_start_date => None,
+                from => Some(from),
+            },
+        };
+
+        match restart_from {
+            Some(from) => {
+                let from = from.map(|date| date.to_primitive_date());
+                Ok(select_from_db!(restart_rating_replay(), client, &from, &replay.id))
+            }
+            None => Ok(replay),
+        }
+    }
+
+    /// # record a checkpoint
+    /// record that a heat has been rated
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `heat` - the heat that was rated
+    ///
+    /// ## Returns
+    /// * `RatingReplay` - the updated replay
+    pub async fn set_progress<C: GenericClient>(&self, client: &C, heat: &Session) -> CustomResult<RatingReplay> {
+        Ok(select_from_db!(set_rating_replay_progress(), client, &heat.id, &self.id))
+    }
+
+    /// # finish the replay
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `RatingReplay` - the finished replay
+    pub async fn finish<C: GenericClient>(&self, client: &C) -> CustomResult<RatingReplay> {
+        Ok(select_from_db!(finish_rating_replay(), client, &self.id))
+    }
+
+    /// # restore the ratings before a date
+    /// undo the ratings of all heats driven on or after a date by restoring the ratings
+    /// from the rating history. drivers without history before the date get the default rating
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `from` - the date to restore to, `None` resets all ratings
+    async fn restore<C: GenericClient>(client: &C, from: Option<NaiveDateTime>) -> CustomResult<()> {
+        RatingHistory::delete_from(client, from).await?;
+        Driver::reset_ratings(client).await?;
+        DriverRating::restore_from_history(client).await?;
+        Driver::set_ratings_from_system(client, get_main_rating_system().name()).await?;
+
+        Ok(())
+    }
+
+    /// # run the unfinished replay
+    /// rate the heats of the unfinished replay one by one. every heat is rated in its own
+    /// transaction together with its checkpoint, so stopping the replay at any point loses
+    /// no more than the heat that was being rated.
+    ///
+    /// the cached responses of the re-rated drivers, the lists of drivers and the tracks are
+    /// invalidated when the replay is done
+    ///
+    /// ## Returns
+    /// * `usize` - the amount of heats that were rated
+    pub async fn run() -> CustomResult<usize> {
+        let mut client = get_pool().get().await?;
+
+        let mut rated = 0;
+        let mut rated_drivers = HashSet::new();
+        loop {
+            let transaction = client.transaction().await?;
+            let replay = match RatingReplay::get_unfinished(&transaction).await? {
+                Some(replay) => replay,
+                None => break,
+            };
+
+            if replay.last_session.is_none() {
+                info!(target:"models/rating_replay:run", "restoring ratings from: {:?}", replay.from_date);
+                RatingReplay::restore(&transaction, replay.from_date).await?;
+            }
+
+            match Session::get_next_to_replay(&transaction, replay.id).await? {
+                Some(heat) => {
+                    rated_drivers.extend(heat.apply_ratings(&transaction).await?);
+                    replay.set_progress(&transaction, &heat).await?;
+                    rated += 1;
+
+                    if rated % 100 == 0 {
+                        info!(target:"models/rating_replay:run", "rated {} heats, last heat: {}", rated, heat.heat_id);
+                    }
+                }
+                None => {
+                    replay.finish(&transaction).await?;
+                    info!(target:"models/rating_replay:run", "finished replay {}, rated {} heats", replay.id, rated);
+                }
+            }
+
+            transaction.commit().await?;
+        }
+
+        if rated > 0 {
+            // the rating leaderboards are per track
+            let mut tags = vec![CacheTag::Drivers];
+            tags.extend(rated_drivers.into_iter().map(CacheTag::Driver));
+            tags.extend(CacheTag::of_tracks(&Track::get_all().await?));
+            CacheTag::invalidate(&tags).await;
+        }
+
+        Ok(rated)
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDateTime};
+use json_response_derive::JsonResponse;
+use log::{info, warn};
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::records::{break_record, count_scheduled_record_rebuilds, delete_all_records, finish_record_rebuilds, get_current_record, get_record_candidates, get_records_of_track, insert_record, schedule_record_rebuild, Record as cRecord, RecordCandidate as cRecordCandidate};
+use crate::errors::CustomResult;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::session::Session;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use crate::modules::redis::Redis;
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+
+/// the redis channel broken records are published on
+pub const RECORDS_CHANNEL: &str = "records";
+
+impl Into<Record> for cRecord {
+    fn into(self) -> Record {
+        Record {
+            id: self.id,
+            track: self.track,
+            category: RecordCategory::from_name(&self.category).unwrap_or(RecordCategory::AllTime),
+            scope: self.scope,
+            driver_name: self.driver_name,
+            heat_id: self.heat_id,
+            kart: self.kart,
+            lap_time: self.lap_time,
+            set_at: self.set_at.to_naive_date(),
+            broken_at: self.broken_at.map(|date| date.to_naive_date()),
+        }
+    }
+}
+
+impl Into<RecordCandidate> for cRecordCandidate {
+    fn into(self) -> RecordCandidate {
+        RecordCandidate {
+            lap: self.lap,
+            driver: self.driver,
+            driver_name: self.driver_name,
+            kart: self.kart,
+            heat_id: self.heat_id,
+            heat_type: self.heat_type,
+            start_date: self.start_date.to_naive_date(),
+            lap_time: self.lap_time,
+        }
+    }
+}
+
+/// # category of a record
+/// the scope of a record depends on its category
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordCategory {
+    /// the fastest lap ever, the scope is empty
+    AllTime,
+    /// the fastest lap of a year, the scope is the year
+    Yearly,
+    /// the fastest lap of a month, the scope is the year and month (`YYYY-MM`)
+    Monthly,
+    /// the fastest lap in a kart, the scope is the number of the kart
+    Kart,
+    /// the fastest lap in a type of heat, the scope is the heat type
+    HeatType,
+    /// the fastest lap of a driver, the scope is the id of the driver
+    PersonalBest,
+}
+
+/// # record
+/// the fastest clean lap on a track within the scope of the record.
+/// a record that is broken is kept with the date it was broken on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Record {
+    pub id: i32,
+    pub track: i32,
+    pub category: RecordCategory,
+    pub scope: String,
+    pub driver_name: String,
+    pub heat_id: String,
+    pub kart: i32,
+    pub lap_time: f64,
+    pub set_at: NaiveDateTime,
+    /// `None` while the record stands
+    pub broken_at: Option<NaiveDateTime>,
+}
+
+/// # a record that was broken
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordBroken {
+    pub record: Record,
+    /// `None` when there was no record yet
+    pub previous: Option<Record>,
+}
+
+/// # the records of a track
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct Records {
+    pub track: Track,
+    pub records: Vec<RecordHolders>,
+}
+
+/// # the holders of a record
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordHolders {
+    pub category: RecordCategory,
+    pub scope: String,
+    pub holder: Record,
+    /// the previous holders, the last one to hold the record first
+    pub previous_holders: Vec<Record>,
+}
+
+/// # a lap that might break a record
+struct RecordCandidate {
+    lap: i32,
+    driver: i32,
+    driver_name: String,
+    kart: i32,
+    heat_id: String,
+    heat_type: String,
+    start_date: NaiveDateTime,
+    lap_time: f64,
+}
+
+impl RecordCategory {
+    /// # get the name of the category
+    /// the name is how the category is stored in the database
+    ///
+    /// ## Returns
+    /// * `&str` - the name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordCategory::AllTime => "all_time",
+            RecordCategory::Yearly => "yearly",
+            RecordCategory::Monthly => "monthly",
+            RecordCategory::Kart => "kart",
+            RecordCategory::HeatType => "heat_type",
+            RecordCategory::PersonalBest => "personal_best",
+        }
+    }
+
+    /// # get a category by its name
+    ///
+    /// ## Arguments
+    /// * `name` - the name of the category, as stored in the database
+    ///
+    /// ## Returns
+    /// * `Option<RecordCategory>` - the category, `None` when the name is unknown
+    pub fn from_name(name: &str) -> Option<RecordCategory> {
+        match name {
+            "all_time" => Some(RecordCategory::AllTime),
+            "yearly" => Some(RecordCategory::Yearly),
+            "monthly" => Some(RecordCategory::Monthly),
+            "kart" => Some(RecordCategory::Kart),
+            "heat_type" => Some(RecordCategory::HeatType),
+            "personal_best" => Some(RecordCategory::PersonalBest),
+            _ => None,
+        }
+    }
+}
+
+impl RecordCandidate {
+    /// # get the records the lap competes for
+    fn scopes(&self) -> Vec<(RecordCategory, String)> {
+        vec![
+            (RecordCategory::AllTime, String::new()),
+            (RecordCategory::Yearly, self.start_date.year().to_string()),
+            (RecordCategory::Monthly, self.start_date.format("%Y-%m").to_string()),
+            (RecordCategory::Kart, self.kart.to_string()),
+            (RecordCategory::HeatType, self.heat_type.clone()),
+            (RecordCategory::PersonalBest, self.driver.to_string()),
+        ]
+    }
+}
+
+impl Record {
+    /// # update the records with a heat
+    /// every record the fastest clean laps of the heat beat is broken. the new records are set
+    /// on the start of the heat. heats have to be added in the order they were driven, for heats
+    /// added later the records have to be rebuilt.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `heat` - the heat
+    ///
+    /// ## Returns
+    /// * `Vec<RecordBroken>` - the broken records
+    pub async fn update_from_heat<C: GenericClient>(client: &C, heat: &Session) -> CustomResult<Vec<RecordBroken>> {
+        let candidates: Vec<RecordCandidate> = select_vec_from_db!(get_record_candidates(), client, &heat.id);
+
+        // only the fastest lap of the heat can break a record
+        let mut fastest: HashMap<(RecordCategory, String), &RecordCandidate> = HashMap::new();
+        for candidate in &candidates {
+            for scope in candidate.scopes() {
+                let best = fastest.entry(scope).or_insert(candidate);
+                if candidate.lap_time < best.lap_time {
+                    *best = candidate;
+                }
+            }
+        }
+        let mut fastest: Vec<((RecordCategory, String), &RecordCandidate)> = fastest.into_iter().collect();
+        fastest.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let set_at = heat.start_date.to_primitive_date();
+        let mut broken = Vec::new();
+        for ((category, scope), candidate) in fastest {
+            let previous: Option<Record> = select_opt_from_db!(get_current_record(), client, &heat.track, &category.as_str(), &scope);
+
+            if let Some(previous) = &previous {
+                if previous.lap_time <= candidate.lap_time {
+                    continue;
+                }
+
+                break_record()
+                    .bind(client, &set_at, &previous.id)
+                    .one()
+                    .await?;
+            }
+
+            let id = insert_record()
+                .bind(client, &heat.track, &category.as_str(), &scope, &candidate.lap, &set_at)
+                .one()
+                .await?;
+
+            broken.push(RecordBroken {
+                record: Record {
+                    id,
+                    track: heat.track,
+                    category,
+                    scope,
+                    driver_name: candidate.driver_name.clone(),
+                    heat_id: candidate.heat_id.clone(),
+                    kart: candidate.kart,
+                    lap_time: candidate.lap_time,
+                    set_at: heat.start_date,
+                    broken_at: None,
+                },
+                previous: previous.map(|previous| Record {
+                    broken_at: Some(heat.start_date),
+                    ..previous
+                }),
+            });
+        }
+
+        Ok(broken)
+    }
+
+    /// # publish broken records
+    /// log the broken records and publish them on the `records` redis channel.
+    /// the records are already stored, so failing to publish them is only logged.
+    ///
+    /// ## Arguments
+    /// * `broken` - the broken records
+    pub async fn publish(broken: &[RecordBroken]) {
+        if broken.is_empty() {
+            return;
+        }
+
+        let mut connection = match Redis::connect().await {
+            Ok(connection) => Some(connection),
+            Err(error) => {
+                warn!(target:"models/record:publish", "Error connecting to redis: {}", error);
+                None
+            }
+        };
+
+        for record in broken {
+            info!(target:"models/record:publish", "{} broke the {} record {} with {} in heat {}",
+                record.record.driver_name, record.record.category.as_str(), record.record.scope,
+                record.record.lap_time, record.record.heat_id);
+
+            if let Some(connection) = &mut connection {
+                if let Err(error) = Redis::publish(connection, RECORDS_CHANNEL, serde_json::to_string(record).unwrap()).await {
+                    warn!(target:"models/record:publish", "Error publishing record: {}", error);
+                }
+            }
+        }
+    }
+
+    /// # schedule a rebuild of all records
+    /// the rebuild is kept until a rebuild that started after the transaction of the client
+    /// was committed succeeds, so a failed rebuild is retried by `rebuild_scheduled`.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    pub async fn schedule_rebuild<C: GenericClient>(client: &C) -> CustomResult<()> {
+        schedule_record_rebuild()
+            .bind(client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// # rebuild the records when a rebuild is scheduled
+    ///
+    /// ## Returns
+    /// * `Option<usize>` - the amount of records set, `None` when no rebuild was scheduled
+    pub async fn rebuild_scheduled() -> CustomResult<Option<usize>> {
+        let client = &get_pool().get().await?;
+        let scheduled = count_scheduled_record_rebuilds()
+            .bind(client)
+            .one()
+            .await?;
+
+        if scheduled == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Record::rebuild_all().await?))
+    }
+
+    /// # rebuild all records
+    /// remove all records and go through all heats in the order they were driven.
+    /// nothing is published. the scheduled rebuilds are finished with it and the cached
+    /// responses of all tracks are invalidated once the records are committed.
+    ///
+    /// ## Returns
+    /// * `usize` - the amount of records set, including the broken ones
+    pub async fn rebuild_all() -> CustomResult<usize> {
+        let tracks = Track::get_all().await?;
+
+        let mut client = get_pool().get().await?;
+        let transaction = client.transaction().await?;
+        // the heats of every rebuild scheduled so far are committed, so they are read below
+        finish_record_rebuilds()
+            .bind(&transaction)
+            .await?;
+        let heats = Session::get_all_chronologicaly().await?;
+
+        delete_all_records()
+            .bind(&transaction)
+            .await?;
+
+        let mut amount = 0;
+        for heat in &heats {
+            amount += Record::update_from_heat(&transaction, heat).await?.len();
+        }
+        transaction.commit().await?;
+        CacheTag::invalidate(&CacheTag::of_tracks(&tracks)).await;
+
+        Ok(amount)
+    }
+
+    /// # get the records of a track
+    ///
+    /// ## Arguments
+    /// * `track` - the track
+    /// * `categories` - the categories to get the records of
+    ///
+    /// ## Returns
+    /// * `Records` - the holders of every record, ordered by category and scope
+    pub async fn of_track(track: Track, categories: &[RecordCategory]) -> CustomResult<Records> {
+        let client = &get_pool().get().await?;
+        let all_records: Vec<Record> = select_vec_from_db!(get_records_of_track(), client, &track.id);
+
+        // the records are ordered by category, scope and newest first
+        let mut records: Vec<RecordHolders> = Vec::new();
+        for record in all_records {
+            if !categories.contains(&record.category) {
+                continue;
+            }
+
+            match records.last_mut() {
+                Some(holders) if holders.category == record.category && holders.scope == record.scope => {
+                    holders.previous_holders.push(record);
+                }
+                _ => records.push(RecordHolders {
+                    category: record.category,
+                    scope: record.scope.clone(),
+                    holder: record,
+                    previous_holders: vec![],
+                }),
+            }
+        }
+        records.sort_by(|a, b| a.category.cmp(&b.category).then(a.scope.cmp(&b.scope)));
+
+        Ok(Records { track, records })
+    }
+}
@@ -7,30 +7,34 @@ use serde::{Deserialize, Serialize};
 use crate::modules::database::models::driver::Driver;
 use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::{Lap, LapsStats};
-use crate::modules::redis::Redis;
+use crate::modules::database::models::rating_history::RatingHistory;
+use crate::modules::database::models::driver_rating::DriverRating;
+use crate::modules::database::models::rating_replay::RatingReplay;
 use crate::modules::traits::as_map::AsMap;
 use crate::modules::traits::has_id::HasIdTrait;
 
-use crate::macros::redis::{delete_keys};
+use crate::modules::cache_tags::CacheTag;
 use json_response_derive::JsonResponse;
-use log::{error};
 
-use rocket::http::ContentType;
 use rocket::response;
 use rocket::response::Responder;
-use rocket::response::Response;
 use rocket::Request;
-use skillratings::weng_lin::{weng_lin_multi_team, WengLinConfig, WengLinRating};
-use skillratings::MultiTeamOutcome;
+use crate::modules::rating_systems::{get_rating_systems, SystemRating};
+use crate::modules::heat_ranking::{RankedDriver, RankingMode};
+use crate::modules::consistency::{Consistency, DriverConsistency};
 
 
 use crate::modules::database::query::get_vec as select_vec_from_db;
 use crate::modules::database::query::get as select_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
 use crate::modules::database::models::general::get_pool;
-use crate::cornucopia::queries::heats::{Heat as cHeat, get_heat_with_stats_paginated, get_all_heats_with_stats, get_heat_with_stats, get_all_heats, get_heats_from_ids, get_heat_from_name, create_new_heat, delete_heat, get_all_chronologicaly};
-use crate::cornucopia::queries::driver::{get_drivers_from_heat_in_order_fastest_lap, GetDriversFromHeatInOrderFastestLap};
-use crate::cornucopia::queries::laps::delete_laps_from_heat;
+use crate::cornucopia::queries::heats::{Heat as cHeat, get_heat_with_stats_paginated, get_all_heats_with_stats, get_heat_with_stats, get_all_heats, get_heats_from_ids, get_heat_from_name, create_new_heat, create_heat_if_not_exists, delete_heat, get_all_chronologicaly, get_next_heat_to_replay, count_heats_driven_after};
+use crate::cornucopia::queries::laps::{delete_laps_from_heat, get_laps_from_heat};
 use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+use cornucopia_async::GenericClient;
+use snafu::{OptionExt, ResultExt};
+use time::PrimitiveDateTime;
+use crate::errors::{CustomResult, Error, InvalidDateSnafu, NotFoundSnafu};
 
 impl Into<Session> for cHeat {
     fn into(self) -> Session {
@@ -39,6 +43,7 @@ impl Into<Session> for cHeat {
             heat_id: self.heat_id,
             heat_type: self.heat_type,
             start_date: self.start_date.to_naive_date(),
+            track: self.track,
         }
     }
 }
@@ -50,6 +55,7 @@ pub struct Session {
     pub heat_id: String,
     pub heat_type: String,
     pub start_date: NaiveDateTime,
+    pub track: i32,
 }
 
 impl Session {
@@ -57,49 +63,89 @@ impl Session {
     /// create a new heat. this function panics if the heat already exists
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `heat_id` - the heat id
     /// * `heat_type` - the heat type
     /// * `start_date` - the start date of the heat
+    /// * `track` - the id of the track the heat was driven on
     ///
     /// ## Returns
     /// * `Heat` - the created heat
     ///
-    pub async fn new(
+    pub async fn new<C: GenericClient>(
+        client: &C,
         heat_id: &str,
         heat_type: &str,
         start_date: &str,
-    ) -> Session {
-        let timestamp =
-            NaiveDateTime::parse_from_str(start_date, "%Y-%m-%dT%H:%M:%S%.f%z").unwrap().to_primitive_date();
+        track: i32,
+    ) -> CustomResult<Session> {
+        let timestamp = Session::parse_start_date(start_date)?;
 
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(create_new_heat(),client, &heat_id, &heat_type, &timestamp)
+        Ok(select_from_db!(create_new_heat(),client, &heat_id, &heat_type, &timestamp, &track))
+    }
+
+    /// # create heat if it does not exist
+    /// create a new heat unless a heat with the same heat id already exists.
+    /// when another transaction is creating the same heat this waits for it to finish,
+    /// so only one of them gets the heat.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `heat_id` - the heat id
+    /// * `heat_type` - the heat type
+    /// * `start_date` - the start date of the heat
+    /// * `track` - the id of the track the heat was driven on
+    ///
+    /// ## Returns
+    /// * `Option<Heat>` - the created heat or `None` if it already existed
+    pub async fn new_if_not_exists<C: GenericClient>(
+        client: &C,
+        heat_id: &str,
+        heat_type: &str,
+        start_date: &str,
+        track: i32,
+    ) -> CustomResult<Option<Session>> {
+        let timestamp = Session::parse_start_date(start_date)?;
+
+        Ok(select_opt_from_db!(create_heat_if_not_exists(), client, &heat_id, &heat_type, &timestamp, &track))
+    }
+
+    /// # parse a start date
+    /// parse the start date of a heat as it is given by the venue
+    ///
+    /// ## Arguments
+    /// * `start_date` - the start date
+    ///
+    /// ## Returns
+    /// * `PrimitiveDateTime` - the parsed date
+    fn parse_start_date(start_date: &str) -> CustomResult<PrimitiveDateTime> {
+        NaiveDateTime::parse_from_str(start_date, "%Y-%m-%dT%H:%M:%S%.f%z")
+            .map(|date| date.to_primitive_date())
+            .context(InvalidDateSnafu { date: start_date })
     }
 
     /// # check if exists
     /// check if a heat exists
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `heat_id` - the heat id
     ///
     /// ## Returns
     /// * `bool` - true if the heat exists
-    pub async fn exists(heat_id: &str) -> bool {
-        let client = &get_pool().get().await.unwrap();
-
-        get_heat_from_name()
+    pub async fn exists<C: GenericClient>(client: &C, heat_id: &str) -> CustomResult<bool> {
+        Ok(get_heat_from_name()
             .bind(client, &heat_id)
-            .all()
-            .await
-            .unwrap()
-            .len() > 0
+            .opt()
+            .await?
+            .is_some())
     }
 
     /// # delete heat
     /// delete a heat
     ///
     /// ## Arguments
-    pub async fn delete(&self) {
+    pub async fn delete(&self) -> CustomResult<()> {
         Session::delete_db_id(self.id).await
     }
 
@@ -109,74 +155,65 @@ impl Session {
     ///
     /// ## Arguments
     /// * `heat_id` - the id of the heat to delete
-    pub async fn delete_id(heat_id: &str) {
-        let heat = Session::get_by_id(heat_id).await;
-        heat.delete().await;
+    pub async fn delete_id(heat_id: &str) -> CustomResult<()> {
+        let heat = Session::get_by_id(heat_id).await?;
+        heat.delete().await
     }
 
     /// # delete heat by db id
     /// delete the heat with the given database id
     /// the given id is the database id not the heat_id
     ///
-    /// this function also deletes all the laps associated with the heat
+    /// this function also deletes all the laps associated with the heat.
+    /// the ratings of the heat are undone by a replay of the ratings from the start of the heat,
+    /// see `RatingReplay::run`
     ///
     /// ## Arguments
     /// * `db_id` - the database id of the heat to delete
-    pub async fn delete_db_id(db_id: i32) {
-        let heat = Session::get_by_db_id(db_id).await;
-        let laps = Lap::from_heat(&heat).await;
-        let drivers = Driver::from_laps(&laps).await;
+    pub async fn delete_db_id(db_id: i32) -> CustomResult<()> {
+        let heat = Session::get_by_db_id(db_id).await?;
+        let laps = Lap::from_heat(&heat).await?;
+        let drivers = Driver::from_laps(&laps).await?;
+
+        let mut client = get_pool().get().await?;
+        let transaction = client.transaction().await?;
+        heat.delete_with(&transaction).await?;
+        transaction.commit().await?;
+
+        for driver in &drivers {
+            driver.clear_cache().await;
+        }
+        heat.clear_cache().await;
 
-        let client = &get_pool().get().await.unwrap();
+        Ok(())
+    }
 
+    /// # delete heat with a client
+    /// delete the heat and its laps, so the delete can be part of a transaction. the cache is not
+    /// cleared, do that after committing. a replay of the ratings from the start of the heat is
+    /// scheduled
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    pub async fn delete_with<C: GenericClient>(&self, client: &C) -> CustomResult<()> {
         delete_laps_from_heat()
-            .bind(client, &heat.id)
-            .await
-            .unwrap();
+            .bind(client, &self.id)
+            .await?;
 
         delete_heat()
-            .bind(client, &heat.id)
+            .bind(client, &self.id)
             .one()
-            .await
-            .unwrap();
+            .await?;
 
-        task_queue::add_task(Box::new(move || {
-            match &mut Redis::connect() {
-                Ok(r_conn) => {
-                    for driver in &drivers {
-                        driver.clear_cache(r_conn);
-                    }
-
-                    heat.clear_cache(r_conn);
-                }
-                Err(err) => {
-                    error!(target:"models/heat:delete_db_id", "Error connecting to redis: {}", err);
-                }
-            };
-        })).unwrap();
+        RatingReplay::schedule(client, Some(self.start_date)).await?;
+        Ok(())
     }
 
-    pub fn clear_cache(&self, r_conn: &mut redis::Connection) {
-        let mut keys: Vec<String> = match Redis::keys(r_conn, &self.heat_id) {
-            Ok(keys) => keys,
-            Err(error) => {
-                error!(target:"models/driver:clear_cache", "error while getting keys from redis: {}", error);
-                return;
-            }
-        };
-
-        keys.append(&mut vec![
-            "/api/drivers/all".to_string(),
-            "/api/drivers/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/heats/all".to_string(),
-        ]);
-
-        // delete all keys
-        delete_keys!(r_conn, keys, "models/heat:clear_cache");
+    /// # clear the cache of the heat
+    /// delete the cached responses that depend on the heat, the lists of heats and the track
+    /// of the heat, see `CacheTag`
+    pub async fn clear_cache(&self) {
+        CacheTag::invalidate(&[CacheTag::Heat(self.heat_id.clone()), CacheTag::Heats, CacheTag::Track(self.track)]).await;
     }
 
     /// # get heat by id
@@ -188,8 +225,11 @@ impl Session {
     ///
     /// ## Returns
     /// * `Heat` - the heat
-    pub async fn get_by_db_id(db_id: i32) -> Session {
-        Session::get_from_db_ids(&[db_id]).await.pop().unwrap()
+    pub async fn get_by_db_id(db_id: i32) -> CustomResult<Session> {
+        Session::get_from_db_ids(&[db_id])
+            .await?
+            .pop()
+            .context(NotFoundSnafu { what: format!("heat with database id {}", db_id) })
     }
 
     /// # get from db ids
@@ -201,10 +241,10 @@ impl Session {
     ///
     /// ## Returns
     /// * `Vec<Heat>` - the heats with the given database ids
-    pub async fn get_from_db_ids(ids: &[i32]) -> Vec<Session> {
-        let client = &get_pool().get().await.unwrap();
-        
-        select_vec_from_db!(get_heats_from_ids(), client, &ids)
+    pub async fn get_from_db_ids(ids: &[i32]) -> CustomResult<Vec<Session>> {
+        let client = &get_pool().get().await?;
+
+        Ok(select_vec_from_db!(get_heats_from_ids(), client, &ids))
     }
 
     /// # get the heats from a list of laps
@@ -215,7 +255,7 @@ impl Session {
     ///
     /// ## Returns
     /// * `Vec<Heat>` - the heats
-    pub async fn from_laps(laps: &[Lap]) -> Vec<Session> {
+    pub async fn from_laps(laps: &[Lap]) -> CustomResult<Vec<Session>> {
         let heat_ids = laps.iter().map(|e| e.heat).collect::<Vec<i32>>();
 
         Session::get_from_db_ids(&heat_ids).await
@@ -249,9 +289,10 @@ impl Session {
     ///
     /// ## Returns
     /// * `Heat` - the heat
-    pub async fn get_by_id(heat_id: &str) -> Session {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(get_heat_from_name(), client, &heat_id)
+    pub async fn get_by_id(heat_id: &str) -> CustomResult<Session> {
+        let client = &get_pool().get().await?;
+        select_opt_from_db!(get_heat_from_name(), client, &heat_id)
+            .context(NotFoundSnafu { what: format!("heat {}", heat_id) })
     }
 
     /// # get all heats
@@ -260,9 +301,9 @@ impl Session {
     ///
     /// ## Returns
     /// * `Vec<Heat>` - all the heats
-    pub async fn get_all() -> Vec<Session> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_all_heats(), client)
+    pub async fn get_all() -> CustomResult<Vec<Session>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_all_heats(), client))
     }
 
     /// # get the laps of the heat
@@ -272,7 +313,7 @@ impl Session {
     ///
     /// ## Returns
     /// * `Vec<Lap>` - the laps of the heat
-    pub async fn get_laps(&self) -> Vec<Lap> {
+    pub async fn get_laps(&self) -> CustomResult<Vec<Lap>> {
         Lap::from_heat(self).await
     }
 
@@ -281,12 +322,14 @@ impl Session {
     /// the stats given are the number of laps and the number of drivers
     ///
     /// ## Arguments
+    /// * `track` - only get the heats driven on this track, all heats when `None`
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `Vec<HeatStats>` - all the heats with stats
-    pub async fn get_all_with_stats() -> Vec<HeatStats> {
-       let client= &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_all_heats_with_stats(), client )
+    pub async fn get_all_with_stats(track: Option<i32>, clean_only: bool) -> CustomResult<Vec<HeatStats>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_all_heats_with_stats(), client, &track, &clean_only))
     }
     // q: String, page: Option<i64>, page_size: Option<i64>
 
@@ -295,14 +338,18 @@ impl Session {
         page_size: i64,
         _sort_dir: String,
         _sort_col: String,
-    ) -> Vec<HeatStats> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(
+        track: Option<i32>,
+        clean_only: bool,
+    ) -> CustomResult<Vec<HeatStats>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(
             get_heat_with_stats_paginated(),
             client,
+            &track,
+            &clean_only,
             &page_size,
             &(page_size*page)
-        )
+        ))
     }
 
     /// # get a single heat with stats
@@ -311,12 +358,14 @@ impl Session {
     ///
     /// ## Arguments
     /// * `heat_id` - the id of heat to search
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `HeatStats` - heat and its stats
-    pub async fn get_with_stats(heat_id: String) -> HeatStats {
-        let client = &get_pool().get().await.unwrap();
-        select_from_db!(get_heat_with_stats(), client, &heat_id)
+    pub async fn get_with_stats(heat_id: String, clean_only: bool) -> CustomResult<HeatStats> {
+        let client = &get_pool().get().await?;
+        select_opt_from_db!(get_heat_with_stats(), client, &heat_id, &clean_only)
+            .context(NotFoundSnafu { what: format!("heat {}", heat_id) })
     }
 
     /// # get all heats sorted by date
@@ -325,9 +374,36 @@ impl Session {
     ///
     /// ## Returns
     /// * `Vec<Heat>` - all the heats sorted by date
-    pub async fn get_all_chronologicaly() -> Vec<Session> {
-        let client = &get_pool().get().await.unwrap();
-        select_vec_from_db!(get_all_chronologicaly(), client)
+    pub async fn get_all_chronologicaly() -> CustomResult<Vec<Session>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_all_chronologicaly(), client))
+    }
+
+    /// # get the next heat to replay
+    /// get the first heat a replay of the ratings has not rated yet
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `replay` - the id of the replay
+    ///
+    /// ## Returns
+    /// * `Option<Session>` - the heat, `None` when all heats have been rated
+    pub async fn get_next_to_replay<C: GenericClient>(client: &C, replay: i32) -> CustomResult<Option<Session>> {
+        Ok(select_opt_from_db!(get_next_heat_to_replay(), client, &replay))
+    }
+
+    /// # check if heats were driven after this heat
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `bool` - if there are heats that started later than this heat
+    pub async fn has_later_heats<C: GenericClient>(&self, client: &C) -> CustomResult<bool> {
+        Ok(count_heats_driven_after()
+            .bind(client, &self.id)
+            .one()
+            .await? > 0)
     }
 
     /// # ensure a heat exists
@@ -337,21 +413,25 @@ impl Session {
     /// if the heat already exists
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `heat_id` - the id of the heat
     /// * `heat_type` - the type of the heat
     /// * `start_date` - the start date of the heat
+    /// * `track` - the id of the track the heat was driven on
     ///
     /// ## Returns
     /// * `Heat` - the heat
-    pub async fn ensure_exists(
+    pub async fn ensure_exists<C: GenericClient>(
+        client: &C,
         heat_id: &str,
         heat_type: &str,
         start_time: &str,
-    ) -> Session {
-        if !Session::exists(heat_id).await {
-            Session::new(heat_id, heat_type, start_time).await
+        track: i32,
+    ) -> CustomResult<Session> {
+        if !Session::exists(client, heat_id).await? {
+            Session::new(client, heat_id, heat_type, start_time, track).await
         } else {
-           Session::get_by_id(heat_id).await
+            Ok(select_from_db!(get_heat_from_name(), client, &heat_id))
         }
     }
 
@@ -364,25 +444,25 @@ impl Session {
     ///
     /// ## Returns
     /// * `HashMap<String, Vec<Lap>>` - the laps per driver
-    pub async fn laps_per_driver(&self) -> HashMap<Driver, Vec<Lap>> {
-        let v_laps = Lap::from_heat(self).await;
-        let drivers = Driver::from_laps(&v_laps).await;
+    pub async fn laps_per_driver(&self) -> CustomResult<HashMap<Driver, Vec<Lap>>> {
+        let v_laps = Lap::from_heat(self).await?;
+        let drivers = Driver::from_laps(&v_laps).await?;
 
-        Session::parse_laps_and_drivers_into_map(&v_laps, &drivers)
+        Ok(Session::parse_laps_and_drivers_into_map(&v_laps, &drivers))
     }
 
     /// # get driver stats
     /// get the stats of all drivers in the heat
     /// the function returns a hashmap that uses the driver as key and the stats as value
-    pub async fn get_driver_stats(&self) -> HashMap<Driver, LapsStats> {
-        let laps_per_driver = self.laps_per_driver().await;
+    pub async fn get_driver_stats(&self) -> CustomResult<HashMap<Driver, LapsStats>> {
+        let laps_per_driver = self.laps_per_driver().await?;
         let mut driver_stats = HashMap::new();
         for (driver, laps) in laps_per_driver {
             driver_stats
                 .insert(driver.to_owned(), Lap::get_stats_of_laps(&laps.to_owned()));
         }
 
-        driver_stats
+        Ok(driver_stats)
     }
 
 
@@ -424,15 +504,14 @@ impl Session {
         heat_laps
     }
 
-    pub async fn get_full_info(&self) -> FullHeatInfo {
+    pub async fn get_full_info(&self) -> CustomResult<FullHeatInfo> {
 
-        let laps = Lap::from_heat(self).await;
-        let drivers: Vec<Driver> = Driver::from_laps(&laps).await;
-        let karts: Vec<Vehicle> = Vehicle::from_laps(&laps).await;
+        let laps = Lap::from_heat(self).await?;
+        let drivers: Vec<Driver> = Driver::from_laps(&laps).await?;
+        let karts: Vec<Vehicle> = Vehicle::from_laps(&laps).await?;
 
         if karts.is_empty() {
-            error!(target:"models/heat:get_full_info", "Error getting karts from laps");
-            panic!("models/heat:get_full_info >> Error getting karts from laps")
+            return Err(Error::NotFoundError { what: format!("karts of heat {}", self.heat_id) });
         };
 
         let laps_per_driver = Session::parse_laps_and_drivers_into_map(&laps, &drivers);
@@ -458,7 +537,7 @@ impl Session {
             });
         }
 
-        full_heat_info
+        Ok(full_heat_info)
     }
 
     /// # parse laps and drivers into map
@@ -521,35 +600,96 @@ impl Session {
         heat_laps_map
     }
 
-    pub async fn apply_ratings(&self) {
-        let client = &get_pool().get().await.unwrap();
+    /// # get the ranking mode of the heat
+    /// races are ranked on finishing position, other heats on fastest lap
+    pub fn ranking_mode(&self) -> RankingMode {
+        RankingMode::for_heat_type(&self.heat_type)
+    }
 
-        // get the order the drivers finished in the heat
-        let drivers: Vec<GetDriversFromHeatInOrderFastestLap> = select_vec_from_db!(get_drivers_from_heat_in_order_fastest_lap(), client, &self.id);
+    /// # get the result of the heat
+    /// rank the drivers of the heat with the ranking mode of its heat type
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `Vec<RankedDriver>` - the drivers in the order they finished, the winner first
+    pub async fn get_ranking<C: GenericClient>(&self, client: &C) -> CustomResult<Vec<RankedDriver>> {
+        let laps: Vec<Lap> = select_vec_from_db!(get_laps_from_heat(), client, &self.id);
+        Ok(self.ranking_mode().rank(&laps))
+    }
 
-        let teams: Vec<Vec<WengLinRating>> = drivers
-            .iter()
-            .map(|driver| {
-                return vec![WengLinRating {
-                    rating: driver.rating,
-                    uncertainty: driver.uncertainty,
-                }];
+    /// # get the consistency of the drivers in the heat
+    /// see `Consistency::of_laps`
+    ///
+    /// ## Returns
+    /// * `Vec<DriverConsistency>` - the consistency of every driver with enough clean laps, the most consistent first
+    pub async fn get_consistency(&self) -> CustomResult<Vec<DriverConsistency>> {
+        let laps = self.get_laps().await?;
+        let drivers = Driver::from_laps(&laps).await?;
+
+        let mut consistency: Vec<DriverConsistency> = Consistency::per_driver(&laps)
+            .into_iter()
+            .filter_map(|(driver, consistency)| {
+                let driver = drivers.iter().find(|d| d.id == driver)?;
+                Some(DriverConsistency {
+                    driver_name: driver.name.clone(),
+                    consistency,
+                })
             })
             .collect();
+        consistency.sort_by(|a, b| a.consistency.coefficient_of_variation.total_cmp(&b.consistency.coefficient_of_variation));
 
-        // create the ratingh groups
-        let mut rating_groups = Vec::new();
-        for (position, _) in drivers.iter().enumerate() {
-            let result = MultiTeamOutcome::new(position + 1);
+        Ok(consistency)
+    }
 
-            rating_groups.push((&(teams[position])[..], result));
+    /// # apply the ratings of the heat
+    /// update the ratings of all drivers in the heat based on the order they finished in.
+    /// the order depends on the ranking mode of the heat type.
+    /// every configured rating system is applied, the main system is also stored on the driver.
+    /// the rating before the heat, with the uncertainty grown for the time the driver was away,
+    /// and the rating after the heat are kept in the rating history.
+    /// the ratings are read and written with the given client so this can be done in the
+    /// same transaction as the rest of the heat.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `Vec<i32>` - the ids of the rated drivers
+    pub async fn apply_ratings<C: GenericClient>(&self, client: &C) -> CustomResult<Vec<i32>> {
+        let drivers = self.get_ranking(client).await?;
+        let driver_ids: Vec<i32> = drivers.iter().map(|driver| driver.driver).collect();
+
+        for (index, system) in get_rating_systems().iter().enumerate() {
+            let ratings = DriverRating::get_of_drivers(client, system.name(), &driver_ids).await?;
+
+            // drivers that are new to the system start at its default rating,
+            // the uncertainty of the others grew since their last heat
+            let results: Vec<(SystemRating, usize)> = drivers
+                .iter()
+                .map(|driver| {
+                    let rating = match ratings.get(&driver.driver) {
+                        Some(rating) => rating.rating_at(system.as_ref(), self.start_date),
+                        None => system.default_rating(),
+                    };
+                    (rating, driver.position)
+                })
+                .collect();
+
+            let new_ratings = system.rate(&results);
+            for (position, driver) in drivers.iter().enumerate() {
+                let new_rating = &new_ratings[position];
+                DriverRating::set(client, driver.driver, system.name(), new_rating, self.start_date).await?;
+                RatingHistory::new(client, driver.driver, self.id, system.name(), &results[position].0, new_rating).await?;
+
+                if index == 0 {
+                    Driver::set_rating_id(client, driver.driver, new_rating).await?;
+                }
+            }
         }
 
-        let new_ratings = weng_lin_multi_team(&rating_groups[..], &WengLinConfig::default());
-        for (position, driver) in drivers.iter().enumerate() {
-            let new_rating = &new_ratings[position];
-            Driver::set_rating_id(driver.id, new_rating[0]).await;
-        }
+        Ok(driver_ids)
     }
 }
 
@@ -558,10 +698,13 @@ pub struct HeatStats {
     pub heat_id: String,
     pub heat_type: String,
     pub start_time: NaiveDateTime,
+    pub track: i32,
     pub amount_of_laps: i32,
     pub amount_of_drivers: i32,
     pub fastest_lap_time: f64,
     pub average_lap_time: f64,
+    /// only given for a single heat, see `Session::get_consistency`
+    pub consistency: Option<Vec<DriverConsistency>>,
 }
 
 #[derive(Debug)]
@@ -587,10 +730,12 @@ impl Into<HeatStats> for crate::cornucopia::queries::heats::GetHeatWithStats {
             heat_id: self.heat_id,
             heat_type: self.heat_type,
             start_time: self.start_time.to_naive_date(),
+            track: self.track,
             amount_of_laps: self.amount_of_laps,
             amount_of_drivers: self.amount_of_drivers,
             fastest_lap_time: self.fastest_lap_time,
             average_lap_time: self.average_lap_time,
+            consistency: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use json_response_derive::JsonResponse;
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::session_conditions::{delete_all_session_conditions, get_condition_observations, get_session_conditions_of_sessions, get_session_conditions_of_track, insert_session_conditions_bulk, ConditionObservation as cConditionObservation, SessionCondition as cSessionCondition, SessionConditionOfTrack as cSessionConditionOfTrack};
+use crate::errors::CustomResult;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::session::Session;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::track_conditions::{estimate_conditions, ConditionObservation};
+use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
+
+impl Into<SessionCondition> for cSessionCondition {
+    fn into(self) -> SessionCondition {
+        SessionCondition {
+            id: self.id,
+            session: self.session,
+            condition_index: self.condition_index,
+            amount_of_drivers: self.amount_of_drivers,
+        }
+    }
+}
+
+impl Into<ConditionObservation> for cConditionObservation {
+    fn into(self) -> ConditionObservation {
+        ConditionObservation {
+            session: self.session,
+            track: self.track,
+            driver: self.driver,
+            best_lap_time: self.best_lap_time,
+        }
+    }
+}
+
+impl Into<HeatConditions> for cSessionConditionOfTrack {
+    fn into(self) -> HeatConditions {
+        HeatConditions {
+            heat_id: self.heat_id,
+            start_date: self.start_date.to_naive_date(),
+            condition_index: self.condition_index,
+            amount_of_drivers: self.amount_of_drivers,
+        }
+    }
+}
+
+/// # track conditions during a heat
+/// the condition index compares how fast the regulars were in a heat to how fast they usually are.
+/// 1.0 are usual conditions, above 1.0 the track was slower.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionCondition {
+    pub id: i32,
+    /// the database id of the heat
+    pub session: i32,
+    pub condition_index: f64,
+    /// the amount of regulars the index is based on
+    pub amount_of_drivers: i32,
+}
+
+/// # the conditions of a track over time
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct TrackConditions {
+    pub track: Track,
+    pub days: Vec<DayConditions>,
+}
+
+/// # the conditions of a day
+/// the index of the day is the mean of the indices of its heats, weighted by their amount of regulars
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DayConditions {
+    pub date: NaiveDate,
+    pub condition_index: f64,
+    pub amount_of_drivers: i32,
+    pub heats: Vec<HeatConditions>,
+}
+
+/// # the conditions of a heat
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HeatConditions {
+    pub heat_id: String,
+    pub start_date: NaiveDateTime,
+    pub condition_index: f64,
+    pub amount_of_drivers: i32,
+}
+
+/// # the conditions of heats
+/// lookup for the condition indices of a set of heats
+pub struct SessionConditions {
+    indices: HashMap<i32, f64>,
+}
+
+impl SessionCondition {
+    /// # compute the conditions of all heats
+    /// estimate the condition index of every heat from the kart-adjusted best laps of the
+    /// regulars and replace the stored indices. see `estimate_conditions`
+    ///
+    /// the normalized lap times of every heat change with the indices, so the cached responses of
+    /// all heats, the lists and the tracks are invalidated
+    ///
+    /// ## Returns
+    /// * `usize` - the amount of heats with an index
+    pub async fn compute_all() -> CustomResult<usize> {
+        let mut client = get_pool().get().await?;
+
+        let observations: Vec<ConditionObservation> = select_vec_from_db!(get_condition_observations(), &client);
+        let estimates = estimate_conditions(&observations);
+
+        let transaction = client.transaction().await?;
+        delete_all_session_conditions()
+            .bind(&transaction)
+            .await?;
+
+        let sessions: Vec<i32> = estimates.iter().map(|estimate| estimate.session).collect();
+        let condition_indices: Vec<f64> = estimates.iter().map(|estimate| estimate.condition_index).collect();
+        let amounts_of_drivers: Vec<i32> = estimates.iter().map(|estimate| estimate.amount_of_drivers).collect();
+        insert_session_conditions_bulk()
+            .bind(&transaction, &sessions, &condition_indices, &amounts_of_drivers)
+            .all()
+            .await?;
+        transaction.commit().await?;
+
+        let tags = [
+            vec![CacheTag::Drivers, CacheTag::Heats],
+            CacheTag::of_heats(&Session::get_all().await?),
+            CacheTag::of_tracks(&Track::get_all().await?),
+        ].concat();
+        CacheTag::invalidate(&tags).await;
+
+        Ok(estimates.len())
+    }
+
+    /// # get the conditions of a track
+    /// get the condition index of every heat on a track, grouped per day
+    ///
+    /// ## Arguments
+    /// * `track` - the track
+    ///
+    /// ## Returns
+    /// * `TrackConditions` - the conditions per day, oldest first
+    pub async fn of_track(track: Track) -> CustomResult<TrackConditions> {
+        let client = &get_pool().get().await?;
+        let heats: Vec<HeatConditions> = select_vec_from_db!(get_session_conditions_of_track(), client, &track.id);
+
+        let mut days: Vec<DayConditions> = Vec::new();
+        for heat in heats {
+            let date = heat.start_date.date();
+            match days.last_mut() {
+                Some(day) if day.date == date => day.heats.push(heat),
+                _ => days.push(DayConditions {
+                    date,
+                    condition_index: 0.0,
+                    amount_of_drivers: 0,
+                    heats: vec![heat],
+                }),
+            }
+        }
+
+        for day in &mut days {
+            day.amount_of_drivers = day.heats.iter().map(|heat| heat.amount_of_drivers).sum();
+            day.condition_index = day.heats
+                .iter()
+                .map(|heat| heat.condition_index * heat.amount_of_drivers as f64)
+                .sum::<f64>() / day.amount_of_drivers as f64;
+        }
+
+        Ok(TrackConditions { track, days })
+    }
+}
+
+impl SessionConditions {
+    /// # get the conditions of heats
+    ///
+    /// ## Arguments
+    /// * `heats` - the heats
+    ///
+    /// ## Returns
+    /// * `SessionConditions` - the condition indices of the heats that have one
+    pub async fn from_sessions(heats: &[Session]) -> CustomResult<SessionConditions> {
+        let ids: Vec<i32> = heats.iter().map(|heat| heat.id).collect();
+
+        let client = &get_pool().get().await?;
+        let conditions: Vec<SessionCondition> = select_vec_from_db!(get_session_conditions_of_sessions(), client, &ids);
+
+        Ok(SessionConditions {
+            indices: conditions
+                .into_iter()
+                .map(|condition| (condition.session, condition.condition_index))
+                .collect(),
+        })
+    }
+
+    /// # get the condition index of a heat
+    ///
+    /// ## Arguments
+    /// * `heat` - the database id of the heat
+    ///
+    /// ## Returns
+    /// * `Option<f64>` - the index, `None` when no regulars drove in the heat
+    pub fn get(&self, heat: i32) -> Option<f64> {
+        self.indices.get(&heat).copied()
+    }
+
+    /// # normalize a lap time
+    /// scale a lap time to what it would have been in usual conditions
+    ///
+    /// ## Arguments
+    /// * `lap_time` - the lap time
+    /// * `heat` - the database id of the heat the lap was driven in
+    ///
+    /// ## Returns
+    /// * `f64` - the normalized lap time, the lap time itself when the heat has no index
+    pub fn normalize(&self, lap_time: f64, heat: i32) -> f64 {
+        lap_time / self.get(heat).unwrap_or(1.0)
+    }
+}
@@ -0,0 +1,145 @@
+use std::env;
+
+use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
+
+use crate::cornucopia::queries::tracks::{ensure_track, get_all_tracks, get_track_by_id, set_track_lap_thresholds, Track as cTrack};
+use crate::errors::{CustomResult, NotFoundSnafu};
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
+use cornucopia_async::GenericClient;
+use snafu::OptionExt;
+
+/// the track new heats are driven on when `TRACK_NAME` is not set
+pub const DEFAULT_TRACK_NAME: &str = "KartbaanGroningen";
+/// the layout new heats are driven on when `TRACK_LAYOUT` is not set
+pub const DEFAULT_TRACK_LAYOUT: &str = "LONG BEACH";
+
+impl Into<Track> for cTrack {
+    fn into(self) -> Track {
+        Track {
+            id: self.id,
+            name: self.name,
+            layout: self.layout,
+            min_lap_time: self.min_lap_time,
+            outlier_threshold: self.outlier_threshold,
+        }
+    }
+}
+
+/// # track
+/// a layout of a track. laps driven on different layouts can not be compared,
+/// so every layout is its own track.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Track {
+    pub id: i32,
+    pub name: String,
+    pub layout: String,
+    /// laps faster than this can not be driven on the track
+    pub min_lap_time: f64,
+    /// the amount of spreads a lap can be slower than usual before it is not clean
+    pub outlier_threshold: f64,
+}
+
+impl Track {
+    /// # ensure a track exists
+    /// get the track with the given name and layout. if it does not exist it will be created
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `name` - the name of the track
+    /// * `layout` - the layout of the track
+    ///
+    /// ## Returns
+    /// * `Track` - the track
+    pub async fn ensure_exists<C: GenericClient>(client: &C, name: &str, layout: &str) -> CustomResult<Track> {
+        Ok(select_from_db!(ensure_track(), client, &name, &layout))
+    }
+
+    /// # get the current track
+    /// get the track that is currently being driven on. this is the track new heats are saved on.
+    /// it is configured with `TRACK_NAME` and `TRACK_LAYOUT`.
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    ///
+    /// ## Returns
+    /// * `Track` - the current track
+    pub async fn current<C: GenericClient>(client: &C) -> CustomResult<Track> {
+        dotenv().ok();
+
+        let name = env::var("TRACK_NAME").unwrap_or(DEFAULT_TRACK_NAME.to_string());
+        let layout = env::var("TRACK_LAYOUT").unwrap_or(DEFAULT_TRACK_LAYOUT.to_string());
+
+        Track::ensure_exists(client, &name, &layout).await
+    }
+
+    /// # get a track by id
+    ///
+    /// ## Arguments
+    /// * `id` - the id of the track
+    ///
+    /// ## Returns
+    /// * `Track` - the track
+    pub async fn get_by_id(id: i32) -> CustomResult<Track> {
+        let client = &get_pool().get().await?;
+        Track::from_id(client, id).await
+    }
+
+    /// # get a track by id
+    /// the same as `get_by_id`, but using the given client
+    ///
+    /// ## Arguments
+    /// * `client` - the database client
+    /// * `id` - the id of the track
+    ///
+    /// ## Returns
+    /// * `Track` - the track
+    pub async fn from_id<C: GenericClient>(client: &C, id: i32) -> CustomResult<Track> {
+        select_opt_from_db!(get_track_by_id(), client, &id)
+            .context(NotFoundSnafu { what: format!("track {}", id) })
+    }
+
+    /// # get the requested track
+    /// get the track with the given id, or the current track when no id is given
+    ///
+    /// ## Arguments
+    /// * `id` - the id of the track
+    ///
+    /// ## Returns
+    /// * `Track` - the track
+    pub async fn get_or_current(id: Option<i32>) -> CustomResult<Track> {
+        match id {
+            Some(id) => Track::get_by_id(id).await,
+            None => {
+                let client = &get_pool().get().await?;
+                Track::current(client).await
+            }
+        }
+    }
+
+    /// # get all tracks
+    ///
+    /// ## Returns
+    /// * `Vec<Track>` - all tracks
+    pub async fn get_all() -> CustomResult<Vec<Track>> {
+        let client = &get_pool().get().await?;
+        Ok(select_vec_from_db!(get_all_tracks(), client))
+    }
+
+    /// # set the lap thresholds of the track
+    /// the laps driven on the track have to be classified again to use the new thresholds
+    ///
+    /// ## Arguments
+    /// * `min_lap_time` - laps faster than this are timing glitches
+    /// * `outlier_threshold` - the amount of spreads a lap can be slower than usual and still be clean
+    ///
+    /// ## Returns
+    /// * `Track` - the updated track
+    pub async fn set_lap_thresholds(&self, min_lap_time: f64, outlier_threshold: f64) -> CustomResult<Track> {
+        let client = &get_pool().get().await?;
+        Ok(select_from_db!(set_track_lap_thresholds(), client, &min_lap_time, &outlier_threshold, &self.id))
+    }
+}
@@ -10,24 +10,26 @@ use crate::modules::helpers::math::Math;
 use crate::modules::database::models::session::Session;
 use crate::modules::database::models::lap::Lap;
 
-use crate::macros::redis::delete_keys;
-use crate::modules::redis::Redis;
+use crate::modules::cache_tags::CacheTag;
 use json_response_derive::JsonResponse;
-use log::error;
 
-use rocket::http::ContentType;
 use rocket::response;
 use rocket::response::Responder;
-use rocket::response::Response;
 use rocket::Request;
 
 
 
 use crate::modules::traits::has_id::HasIdTrait;
 
-use crate::cornucopia::queries::kart::{create_kart, get_all_karts, get_all_karts_with_stats, get_kart_by_id, get_kart_by_number, get_kart_from_lap, get_kart_with_stats, get_karts_by_ids, get_karts_by_numbers, get_karts_from_laps, get_karts_stats_per_day};
+use crate::cornucopia::queries::kart::{create_kart, create_kart_if_not_exists, get_all_karts, get_all_karts_with_stats, get_kart_by_id, get_kart_by_number, get_kart_from_lap, get_kart_with_stats, get_karts_by_ids, get_karts_by_numbers, get_karts_from_laps, get_karts_stats_per_day};
 use crate::modules::traits::primitive_to_naive_date::ChangeDateType;
 use crate::modules::database::models::general::{get_pool};
+use cornucopia_async::GenericClient;
+use snafu::OptionExt;
+use crate::errors::{CustomResult, NotFoundSnafu};
+use crate::modules::database::query::get_vec as select_vec_from_db;
+use crate::modules::database::query::get as select_from_db;
+use crate::modules::database::query::get_opt as select_opt_from_db;
 
 #[derive(Serialize, PartialEq, Debug, Clone, Eq, Hash, HasId)]
 pub struct Vehicle {
@@ -44,37 +46,31 @@ impl Vehicle {
     /// # insert a new lap into the database
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `number_in` - the number of the kart
     ///
     /// ## Returns
     /// * `Kart` - the inserted kart
-    pub async fn new(
+    pub async fn new<C: GenericClient>(
+        client: &C,
         number: i32,
         brand: String,
         model: String,
         horsepower: i32,
-        modified: bool) -> Vehicle {
+        modified: bool) -> CustomResult<Vehicle> {
 
-        let client = get_pool().get().await.unwrap();
+        let id = create_kart()
+            .bind(client, &number, &brand, &model, &horsepower, &modified)
+            .one().await?;
 
-        let id = match create_kart()
-            .bind(&client, &number, &brand, &model, &horsepower, &modified)
-            .one().await {
-            Ok(id) => id,
-            Err(error) => {
-                panic!("error getting kart {:#?}", error)
-            }
-        };
-
-        return Vehicle {
+        Ok(Vehicle {
             id,
             number,
             brand,
             model,
             horsepower,
             modified,
-        }
-
+        })
     }
 
     /********** GETTERS **********/
@@ -85,19 +81,11 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Kart` - the kart with the given number
-    pub async fn get_by_number(number: i32) -> Option<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
+    pub async fn get_by_number(number: i32) -> CustomResult<Vehicle> {
+        let client = &get_pool().get().await?;
 
-        match get_kart_by_number()
-            .bind(&client, &number)
-            .one().await {
-            Ok(k) => Some(k.into()),
-            Err(error) => {
-                println!("{}", error);
-                None
-            }
-        }
+        select_opt_from_db!(get_kart_by_number(), client, &number)
+            .context(NotFoundSnafu { what: format!("kart {}", number) })
     }
 
     /// # get the karts by numbers
@@ -108,20 +96,10 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Vec<Kart>` - the karts with the given numbers
-    pub async fn get_by_numbers(numbers: Vec<i32>) -> Vec<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_karts_by_numbers()
-            .bind(&client, &numbers)
-            .all().await {
-            Ok(karts) => {
-                karts.into_iter().map(|k| k.into()).collect()
-            }
-            Err(error) => {
-                println!("{}", error);
-                vec![]
-            }
-        }
+    pub async fn get_by_numbers(numbers: Vec<i32>) -> CustomResult<Vec<Vehicle>> {
+        let client = &get_pool().get().await?;
+
+        Ok(select_vec_from_db!(get_karts_by_numbers(), client, &numbers))
     }
 
     /// # get kart from id
@@ -133,18 +111,11 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Kart` - the kart with the given id
-    pub async fn get_by_id(id: i32) -> Option<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_kart_by_id()
-            .bind(&client, &id)
-            .one().await {
-            Ok(k) => Some(k.into()),
-            Err(error) => {
-                println!("{}", error);
-                None
-            }
-        }
+    pub async fn get_by_id(id: i32) -> CustomResult<Vehicle> {
+        let client = &get_pool().get().await?;
+
+        select_opt_from_db!(get_kart_by_id(), client, &id)
+            .context(NotFoundSnafu { what: format!("kart with id {}", id) })
     }
 
     /// # get karts from ids
@@ -155,20 +126,10 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Vec<Kart>` - the karts with the given ids
-    pub async fn get_by_ids(ids: &[i32]) -> Vec<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_karts_by_ids()
-            .bind(&client, &ids)
-            .all().await {
-            Ok(karts) => {
-                karts.into_iter().map(|k| k.into()).collect()
-            }
-            Err(error) => {
-                println!("{}", error);
-                vec![]
-            }
-        }
+    pub async fn get_by_ids(ids: &[i32]) -> CustomResult<Vec<Vehicle>> {
+        let client = &get_pool().get().await?;
+
+        Ok(select_vec_from_db!(get_karts_by_ids(), client, &ids))
     }
 
     /// # get all karts
@@ -178,20 +139,10 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Vec<Kart>` - all karts in the database
-    pub async fn get_all() -> Vec<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_all_karts()
-            .bind(&client)
-            .all().await {
-            Ok(karts) => {
-                return karts.into_iter().map(|k| k.into()).collect();
-            }
-            Err(error) => {
-                println!("{}", error);
-                return vec![];
-            }
-        }
+    pub async fn get_all() -> CustomResult<Vec<Vehicle>> {
+        let client = &get_pool().get().await?;
+
+        Ok(select_vec_from_db!(get_all_karts(), client))
     }
 
     /// # get the kart of a lap
@@ -202,20 +153,11 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Kart` - the kart of the lap
-    pub async fn from_lap(lap: Lap) -> Option<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_kart_from_lap()
-            .bind(&client, &lap.id)
-            .one().await {
-            Ok(k) => {
-                return Some(k.into());
-            }
-            Err(error) => {
-                println!("{}", error);
-                return None;
-            }
-        }
+    pub async fn from_lap(lap: Lap) -> CustomResult<Vehicle> {
+        let client = &get_pool().get().await?;
+
+        select_opt_from_db!(get_kart_from_lap(), client, &lap.id)
+            .context(NotFoundSnafu { what: format!("kart of lap {}", lap.id) })
     }
 
     /// # get the karts of laps
@@ -227,21 +169,13 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `Vec<Kart>` - the karts of the laps
-    pub async fn from_laps(laps: &[Lap]) -> Vec<Vehicle> {
-        let client = get_pool().get().await.unwrap();
-
+    pub async fn from_laps(laps: &[Lap]) -> CustomResult<Vec<Vehicle>> {
+        let client = &get_pool().get().await?;
 
-        match get_karts_from_laps()
-            .bind(&client, &laps.into_iter().map(|l| l.into()).collect::<Vec<i32>>())
-            .all().await {
-            Ok(laps) => {
-                return laps.into_iter().map(|k| k.into()).collect();
-            }
-            Err(error) => {
-                println!("{}", error);
-                return vec![];
-            }
-        }
+        Ok(select_vec_from_db!(
+            get_karts_from_laps(),
+            client,
+            &laps.into_iter().map(|l| l.into()).collect::<Vec<i32>>()))
     }
 
     /// # get the karts of laps
@@ -271,11 +205,11 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `HashMap<Date, Vec<Lap>>` - the laps per day
-    pub async fn get_laps_per_day(&self) -> HashMap<NaiveDate, Vec<Lap>> {
-        let v_laps = Lap::from_kart(self).await;
+    pub async fn get_laps_per_day(&self) -> CustomResult<HashMap<NaiveDate, Vec<Lap>>> {
+        let v_laps = Lap::from_kart(self).await?;
 
         let ids = v_laps.iter().map(|lap: &Lap| lap.heat).collect::<Vec<i32>>();
-        let heats: Vec<Session> = Session::get_from_db_ids(&ids).await;
+        let heats: Vec<Session> = Session::get_from_db_ids(&ids).await?;
 
         let mut laps_per_day: HashMap<NaiveDate, Vec<Lap>> = HashMap::new();
         for lap in &v_laps {
@@ -290,7 +224,7 @@ impl Vehicle {
             }
         }
 
-        laps_per_day
+        Ok(laps_per_day)
     }
 
     /// # get laptimes per day
@@ -300,8 +234,8 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `HashMap<Date, Vec<f64>>` - the laptimes per day
-    pub async fn get_laptimes_per_day(&self) -> HashMap<NaiveDate, Vec<f64>> {
-        let laps_per_day = self.get_laps_per_day().await;
+    pub async fn get_laptimes_per_day(&self) -> CustomResult<HashMap<NaiveDate, Vec<f64>>> {
+        let laps_per_day = self.get_laps_per_day().await?;
 
         let mut laptimes_per_day: HashMap<NaiveDate, Vec<f64>> = HashMap::new();
         for (date, laps) in laps_per_day {
@@ -309,7 +243,7 @@ impl Vehicle {
             laptimes_per_day.insert(date, laptimes);
         }
 
-        laptimes_per_day
+        Ok(laptimes_per_day)
     }
 
     /// # get the fastest laptime per day
@@ -321,16 +255,16 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `HashMap<Date, f64>` - the fastest laptime per day
-    pub async fn get_minimum_laptime_per_day(&self) -> HashMap<NaiveDate, f64> {
-        let days = self.get_laptimes_per_day().await;
+    pub async fn get_minimum_laptime_per_day(&self) -> CustomResult<HashMap<NaiveDate, f64>> {
+        let days = self.get_laptimes_per_day().await?;
 
-        days
+        Ok(days
             .iter()
             .map(|(date, laps)| {
                 let minimum = laps.iter().fold(f64::INFINITY, |a, &b| a.min(b));
                 (*date, Math::round_float_to_n_decimals(minimum, 2))
             })
-            .collect()
+            .collect())
     }
 
     /// # get the media laptime per day
@@ -342,16 +276,16 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `HashMap<Date, f64>` - the median laptime per day
-    pub async fn get_median_laptime_per_day(&self) -> HashMap<NaiveDate, f64> {
-        let days = self.get_laptimes_per_day().await;
+    pub async fn get_median_laptime_per_day(&self) -> CustomResult<HashMap<NaiveDate, f64>> {
+        let days = self.get_laptimes_per_day().await?;
 
-        days
+        Ok(days
             .iter()
             .map(|(date, laps)| {
                 let median = Math::median(laps.clone());
                 (*date, Math::round_float_to_n_decimals(median, 2))
             })
-            .collect()
+            .collect())
     }
 
     /// # get the average laptime per day
@@ -363,16 +297,16 @@ impl Vehicle {
     ///
     /// ## Returns
     /// * `HashMap<Date, f64>` - the average laptime per day
-    pub async fn get_average_laptime_per_day(&self) -> HashMap<NaiveDate, f64> {
-        let avg_day = self.get_laptimes_per_day().await ;
+    pub async fn get_average_laptime_per_day(&self) -> CustomResult<HashMap<NaiveDate, f64>> {
+        let avg_day = self.get_laptimes_per_day().await?;
 
-        avg_day
+        Ok(avg_day
             .iter()
             .map(|(date, laps)| {
                 let avg = laps.iter().sum::<f64>() / laps.len() as f64;
                 (*date, Math::round_float_to_n_decimals(avg, 2))
             })
-            .collect()
+            .collect())
     }
 
     /// # get the stats of all karts per day
@@ -381,23 +315,15 @@ impl Vehicle {
     /// the stats are the fastest laptime, the median laptime, the average laptime and the date.
     ///
     /// ## Arguments
+    /// * `track` - the track to get the stats on
+    /// * `clean_only` - only use the clean laps for the stats
     ///
     /// ## Returns
     /// * `HashMap<Kart, Vec<KartStatsPerDay>>` - the stats of all karts per day
-    pub async fn get_stats_per_day_from_db() -> HashMap<Vehicle, Vec<KartStatsPerDay>> {
-        let client = get_pool().get().await.unwrap();
-
-        let kart_stats: Vec<KartStatsPerDay> = match get_karts_stats_per_day()
-            .bind(&client)
-            .all().await {
-            Ok(stats) => {
-                stats.into_iter().map(|s| s.into()).collect()
-            }
-            Err(error) => {
-                println!("{}", error);
-                vec![]
-            }
-        };
+    pub async fn get_stats_per_day_from_db(track: i32, clean_only: bool) -> CustomResult<HashMap<Vehicle, Vec<KartStatsPerDay>>> {
+        let client = &get_pool().get().await?;
+
+        let kart_stats: Vec<KartStatsPerDay> = select_vec_from_db!(get_karts_stats_per_day(), client, &track, &clean_only);
 
         let mut kart_stats_per_day: HashMap<Vehicle, Vec<KartStatsPerDay>> = HashMap::new();
 
@@ -420,47 +346,34 @@ impl Vehicle {
             }
         }
 
-        kart_stats_per_day
+        Ok(kart_stats_per_day)
     }
 
-    pub async fn get_with_stats(kart_number: i32) -> Option<KartStats> {
-        let client = get_pool().get().await.unwrap();
+    pub async fn get_with_stats(kart_number: i32, track: Option<i32>, clean_only: bool) -> CustomResult<KartStats> {
+        let client = &get_pool().get().await?;
 
-        return match get_kart_with_stats()
-            .bind(&client, &kart_number)
-            .one().await {
-            Ok(stat) => Some(stat.into()),
-            Err(error) => {
-                println!("{}", error);
-                None
-            }
-        };
+        select_opt_from_db!(get_kart_with_stats(), client, &kart_number, &track, &clean_only)
+            .context(NotFoundSnafu { what: format!("kart {}", kart_number) })
     }
 
     /// # get all karts and some basic info
     /// get the number, total laps, total drivers and if the kart is a child kart of all karts
     ///
     /// ## Arguments
+    /// * `track` - only count the laps driven on this track, all laps when `None`
+    /// * `clean_only` - only count the clean laps
     ///
     /// ## Returns
     /// * `Vec<KartStats>` - the info of all karts
     pub async fn get_all_with_stats(
         sort_col: String,
         _sort_dir: String,
-    ) -> Vec<KartStats> {
-        let client = get_pool().get().await.unwrap();
-
-        match get_all_karts_with_stats()
-            .bind(&client, &sort_col)
-            .all().await {
-            Ok(stats) => {
-                stats.into_iter().map(|s| s.into()).collect()
-            }
-            Err(error) => {
-                println!("{}", error);
-                vec![]
-            }
-        }
+        track: Option<i32>,
+        clean_only: bool,
+    ) -> CustomResult<Vec<KartStats>> {
+        let client = &get_pool().get().await?;
+
+        Ok(select_vec_from_db!(get_all_karts_with_stats(), client, &track, &clean_only, &sort_col))
     }
 
     /// # ensure kart exists
@@ -469,24 +382,30 @@ impl Vehicle {
     /// this is the prefered method to create a kart because it wont panic if the kart already exists
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `number` - the number of the kart
     /// * `is_child_kart` - if the kart is a child kart
     ///
     /// ## Returns
     /// * `Kart` - the kart
-    pub async fn ensure_exists(
+    pub async fn ensure_exists<C: GenericClient>(
+        client: &C,
         number: i32,
         brand: String,
         model: String,
         horsepower: i32,
         modified: bool
-    ) -> Vehicle {
-        if !Vehicle::exists(number).await {
-            Vehicle::new(
-                number,
-                brand, model, horsepower, modified).await
-        } else {
-            Vehicle::get_by_number(number).await.unwrap()
+    ) -> CustomResult<Vehicle> {
+        let existing: Option<Vehicle> = select_opt_from_db!(get_kart_by_number(), client, &number);
+        if let Some(kart) = existing {
+            return Ok(kart);
+        }
+
+        // a heat saved at the same time can create the kart first
+        let created: Option<Vehicle> = select_opt_from_db!(create_kart_if_not_exists(), client, &number, &brand, &model, &horsepower, &modified);
+        match created {
+            Some(kart) => Ok(kart),
+            None => Ok(select_from_db!(get_kart_by_number(), client, &number)),
         }
     }
 
@@ -518,42 +437,23 @@ impl Vehicle {
     /// check if a kart exists in the database
     ///
     /// ## Arguments
+    /// * `client` - the database client
     /// * `number` - the number of the kart
     ///
     /// ## Returns
     /// * `bool` - if the kart exists
-    pub async fn exists(number: i32) -> bool {
-        let client = &get_pool().get().await.unwrap();
-
-        get_kart_by_number()
+    pub async fn exists<C: GenericClient>(client: &C, number: i32) -> CustomResult<bool> {
+        Ok(get_kart_by_number()
             .bind(client, &number)
-            .all()
-            .await
-            .unwrap()
-            .len() > 0
+            .opt()
+            .await?
+            .is_some())
     }
 
-    pub fn clear_cache(&self, r_conn: &mut redis::Connection) {
-        // get all keys
-        let mut keys = match Redis::get_keys(r_conn, &self.number.to_string()) {
-            Ok(keys) => keys,
-            Err(error) => {
-                error!(target:"model/kart:clear_cache", "Error getting keys: {}", error);
-                return;
-            }
-        };
-
-        keys.append(&mut vec![
-            "/api/drivers/all".to_string(),
-            "/api/drivers/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/api/heats/all".to_string(),
-            "/api/heats/all/full".to_string(),
-            "/karts/all".to_string(),
-        ]);
-
-        delete_keys!(r_conn, keys, "models/kart:clear_cache");
+    /// # clear the cache of the kart
+    /// delete the cached responses that depend on the kart and the lists of karts, see `CacheTag`
+    pub async fn clear_cache(&self) {
+        CacheTag::invalidate(&[CacheTag::Kart(self.number), CacheTag::Karts]).await;
     }
 }
 
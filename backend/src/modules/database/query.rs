@@ -2,8 +2,7 @@ macro_rules! get_vec {
     ($func:expr, $( $x:expr ),+) => {
         $func.bind($($x,)*)
             .all()
-            .await
-            .unwrap()
+            .await?
             .into_iter()
             .map(|e| e.into())
             .collect()
@@ -15,11 +14,21 @@ macro_rules! get {
     ($func:expr, $( $x:expr ),+) => {
         $func.bind($($x,)*)
             .one()
-            .await
-            .unwrap()
+            .await?
             .into()
     }
 }
 
+
+macro_rules! get_opt {
+    ($func:expr, $( $x:expr ),+) => {
+        $func.bind($($x,)*)
+            .opt()
+            .await?
+            .map(|e| e.into())
+    }
+}
+
 pub(crate) use get_vec;
 pub(crate) use get;
+pub(crate) use get_opt;
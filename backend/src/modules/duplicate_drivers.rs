@@ -0,0 +1,165 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CustomResult;
+use crate::modules::database::models::driver::Driver;
+
+/// # two drivers that might be the same person
+/// drivers with nearly the same name that never drove in the same heat, most likely the same
+/// person with a typo in their name. they can be merged with `Driver::merge`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PossibleDuplicate {
+    pub driver_name: String,
+    pub other_name: String,
+    /// the amount of characters that have to be changed to get from one name to the other
+    pub distance: usize,
+    pub amount_of_heats: usize,
+    pub other_amount_of_heats: usize,
+}
+
+impl PossibleDuplicate {
+    /// # find drivers that might be the same person
+    /// spaces and dashes are left out of the names, so "jan de vries" and "jan-devries" are the same name.
+    ///
+    /// ## Arguments
+    /// * `max_distance` - the amount of characters the names may differ in
+    ///
+    /// ## Returns
+    /// * `Vec<PossibleDuplicate>` - the pairs of drivers, the closest names first
+    pub async fn find(max_distance: usize) -> CustomResult<Vec<PossibleDuplicate>> {
+        let drivers = Driver::get_all().await?;
+        let heats = Driver::get_heat_ids_of_all().await?;
+
+        Ok(PossibleDuplicate::find_in(&drivers, &heats, max_distance))
+    }
+
+    /// # find drivers that might be the same person in the given drivers
+    ///
+    /// ## Arguments
+    /// * `drivers` - the drivers
+    /// * `heats` - the ids of the heats every driver drove in, by the id of the driver
+    /// * `max_distance` - the amount of characters the names may differ in
+    ///
+    /// ## Returns
+    /// * `Vec<PossibleDuplicate>` - the pairs of drivers, the closest names first
+    fn find_in(drivers: &[Driver], heats: &HashMap<i32, HashSet<i32>>, max_distance: usize) -> Vec<PossibleDuplicate> {
+        let names: Vec<Vec<char>> = drivers
+            .iter()
+            .map(|driver| driver.name.chars().filter(|c| *c != ' ' && *c != '-').collect())
+            .collect();
+
+        let mut duplicates = Vec::new();
+        for (index, driver) in drivers.iter().enumerate() {
+            for (other_index, other) in drivers.iter().enumerate().skip(index + 1) {
+                if names[index].len().abs_diff(names[other_index].len()) > max_distance {
+                    continue;
+                }
+
+                let distance = edit_distance(&names[index], &names[other_index]);
+                if distance > max_distance {
+                    continue;
+                }
+
+                let (Some(driver_heats), Some(other_heats)) = (heats.get(&driver.id), heats.get(&other.id)) else {
+                    continue;
+                };
+                if !driver_heats.is_disjoint(other_heats) {
+                    continue;
+                }
+
+                duplicates.push(PossibleDuplicate {
+                    driver_name: driver.name.clone(),
+                    other_name: other.name.clone(),
+                    distance,
+                    amount_of_heats: driver_heats.len(),
+                    other_amount_of_heats: other_heats.len(),
+                });
+            }
+        }
+        duplicates.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.driver_name.cmp(&b.driver_name)));
+
+        duplicates
+    }
+}
+
+/// # levenshtein distance
+/// the amount of insertions, deletions and substitutions needed to turn one name into the other
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::helpers::test_data::driver;
+
+    fn chars(name: &str) -> Vec<char> {
+        name.chars().collect()
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(edit_distance(&chars("jan"), &chars("jan")), 0);
+        assert_eq!(edit_distance(&chars("jan"), &chars("jam")), 1);
+        assert_eq!(edit_distance(&chars("jan"), &chars("jans")), 1);
+        assert_eq!(edit_distance(&chars("jans"), &chars("jan")), 1);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars(""), &chars("jan")), 3);
+        assert_eq!(edit_distance(&chars("jan"), &chars("")), 3);
+    }
+
+    #[test]
+    fn finds_typos() {
+        let drivers = [driver(1, "jan de vries"), driver(2, "jan-devries"), driver(3, "jan de vris"), driver(4, "piet")];
+        let heats = HashMap::from([
+            (1, HashSet::from([1, 2])),
+            (2, HashSet::from([3])),
+            (3, HashSet::from([4, 5, 6])),
+            (4, HashSet::from([7])),
+        ]);
+
+        let duplicates = PossibleDuplicate::find_in(&drivers, &heats, 1);
+        let pairs: Vec<(&str, &str, usize)> = duplicates
+            .iter()
+            .map(|duplicate| (duplicate.driver_name.as_str(), duplicate.other_name.as_str(), duplicate.distance))
+            .collect();
+
+        // spaces and dashes do not count, the closest names come first
+        assert_eq!(pairs, vec![
+            ("jan de vries", "jan-devries", 0),
+            ("jan de vries", "jan de vris", 1),
+            ("jan-devries", "jan de vris", 1),
+        ]);
+        assert_eq!(duplicates[1].amount_of_heats, 2);
+        assert_eq!(duplicates[1].other_amount_of_heats, 3);
+    }
+
+    #[test]
+    fn drivers_in_the_same_heat_are_different() {
+        let drivers = [driver(1, "jan"), driver(2, "jam")];
+        let heats = HashMap::from([(1, HashSet::from([1, 2])), (2, HashSet::from([2]))]);
+
+        assert!(PossibleDuplicate::find_in(&drivers, &heats, 1).is_empty());
+    }
+
+    #[test]
+    fn drivers_without_heats_are_skipped() {
+        let drivers = [driver(1, "jan"), driver(2, "jam")];
+        let heats = HashMap::from([(1, HashSet::from([1]))]);
+
+        assert!(PossibleDuplicate::find_in(&drivers, &heats, 1).is_empty());
+    }
+}
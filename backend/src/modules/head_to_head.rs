@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDateTime;
+use json_response_derive::JsonResponse;
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::lap::Lap;
+use crate::modules::database::models::session::Session;
+use crate::modules::database::models::vehicle::Vehicle;
+use crate::modules::heat_ranking::RankingMode;
+use crate::modules::helpers::math::Math;
+use crate::modules::lap_classification::LapClassification;
+use crate::modules::rating_systems::{get_main_rating_system, SystemRating};
+
+/// # head to head
+/// two drivers compared on the heats they both drove in. everything is seen from the first driver,
+/// a win means the first driver was ahead and a negative delta means the first driver was faster.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct HeadToHead {
+    pub driver_name: String,
+    pub opponent_name: String,
+    /// the results when the drivers are ranked on the laps they completed and their total time
+    pub finishing_order: WinLossRecord,
+    /// the results when the drivers are ranked on their fastest lap
+    pub fastest_lap: WinLossRecord,
+    /// the average difference between the fastest laps in the shared heats
+    pub average_fastest_lap_delta: Option<f64>,
+    /// the chance the first driver finishes ahead, according to their current ratings
+    pub win_probability: f64,
+    /// the heats both drivers drove in, the latest first
+    pub heats: Vec<SharedHeat>,
+    /// the karts both drivers drove in, in any heat
+    pub karts: Vec<SharedKart>,
+}
+
+/// # the amount of heats won, lost and tied
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct WinLossRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+}
+
+/// # the result of a heat
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadToHeadResult {
+    Win,
+    Loss,
+    Tie,
+}
+
+/// # a heat both drivers drove in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SharedHeat {
+    pub heat_id: String,
+    pub start_date: NaiveDateTime,
+    pub kart: i32,
+    pub opponent_kart: i32,
+    pub finishing_order: HeadToHeadResult,
+    pub fastest_lap: HeadToHeadResult,
+    pub fastest_lap_time: f64,
+    pub opponent_fastest_lap_time: f64,
+    pub fastest_lap_delta: f64,
+    /// the difference between the average clean laps, `None` when a driver has no clean laps
+    pub average_lap_delta: Option<f64>,
+}
+
+/// # a kart both drivers drove in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SharedKart {
+    pub number: i32,
+    pub amount_of_laps: usize,
+    pub opponent_amount_of_laps: usize,
+    pub fastest_lap_time: f64,
+    pub opponent_fastest_lap_time: f64,
+}
+
+impl WinLossRecord {
+    fn add(&mut self, result: HeadToHeadResult) {
+        match result {
+            HeadToHeadResult::Win => self.wins += 1,
+            HeadToHeadResult::Loss => self.losses += 1,
+            HeadToHeadResult::Tie => self.ties += 1,
+        }
+    }
+}
+
+impl HeadToHead {
+    /// # compare two drivers
+    ///
+    /// ## Arguments
+    /// * `driver` - the first driver
+    /// * `opponent` - the second driver
+    /// * `laps` - the laps of the first driver
+    /// * `opponent_laps` - the laps of the second driver
+    /// * `heats` - the heats the laps were driven in
+    /// * `karts` - the karts the laps were driven in
+    ///
+    /// ## Returns
+    /// * `HeadToHead` - the comparison
+    pub fn new(
+        driver: &Driver,
+        opponent: &Driver,
+        laps: &[Lap],
+        opponent_laps: &[Lap],
+        heats: &[Session],
+        karts: &[Vehicle],
+    ) -> HeadToHead {
+        let heat_laps = laps_per_heat(laps);
+        let opponent_heat_laps = laps_per_heat(opponent_laps);
+        let kart_numbers: HashMap<i32, i32> = karts.iter().map(|kart| (kart.id, kart.number)).collect();
+
+        let mut finishing_order = WinLossRecord::default();
+        let mut fastest_lap = WinLossRecord::default();
+        let mut shared_heats = Vec::new();
+        for heat in heats {
+            let (Some(laps), Some(opponent_laps)) = (heat_laps.get(&heat.id), opponent_heat_laps.get(&heat.id)) else {
+                continue;
+            };
+
+            let shared_heat = SharedHeat::new(heat, laps, opponent_laps, &kart_numbers);
+            finishing_order.add(shared_heat.finishing_order);
+            fastest_lap.add(shared_heat.fastest_lap);
+            shared_heats.push(shared_heat);
+        }
+        shared_heats.sort_by_key(|heat| std::cmp::Reverse(heat.start_date));
+
+        let deltas: Vec<f64> = shared_heats.iter().map(|heat| heat.fastest_lap_delta).collect();
+        let average_fastest_lap_delta = if deltas.is_empty() { None } else { Some(Math::mean(&deltas)) };
+
+        let rating_system = get_main_rating_system();
+        let win_probability = rating_system.win_probability(
+            &SystemRating { rating: driver.rating, uncertainty: driver.uncertainty, volatility: None },
+            &SystemRating { rating: opponent.rating, uncertainty: opponent.uncertainty, volatility: None },
+        );
+
+        HeadToHead {
+            driver_name: driver.name.clone(),
+            opponent_name: opponent.name.clone(),
+            finishing_order,
+            fastest_lap,
+            average_fastest_lap_delta,
+            win_probability,
+            heats: shared_heats,
+            karts: SharedKart::of_laps(laps, opponent_laps, &kart_numbers),
+        }
+    }
+}
+
+impl SharedHeat {
+    fn new(heat: &Session, laps: &[Lap], opponent_laps: &[Lap], kart_numbers: &HashMap<i32, i32>) -> SharedHeat {
+        let both: Vec<Lap> = laps.iter().chain(opponent_laps).cloned().collect();
+        let driver = laps[0].driver;
+
+        let fastest_lap_time = fastest(laps);
+        let opponent_fastest_lap_time = fastest(opponent_laps);
+
+        SharedHeat {
+            heat_id: heat.heat_id.clone(),
+            start_date: heat.start_date,
+            kart: kart_numbers.get(&laps[0].kart_id).copied().unwrap_or_default(),
+            opponent_kart: kart_numbers.get(&opponent_laps[0].kart_id).copied().unwrap_or_default(),
+            finishing_order: result(&RankingMode::FinishPosition, &both, driver),
+            fastest_lap: result(&RankingMode::FastestLap, &both, driver),
+            fastest_lap_time,
+            opponent_fastest_lap_time,
+            fastest_lap_delta: fastest_lap_time - opponent_fastest_lap_time,
+            average_lap_delta: average_clean(laps).zip(average_clean(opponent_laps)).map(|(average, opponent_average)| average - opponent_average),
+        }
+    }
+}
+
+impl SharedKart {
+    fn of_laps(laps: &[Lap], opponent_laps: &[Lap], kart_numbers: &HashMap<i32, i32>) -> Vec<SharedKart> {
+        let by_kart = |laps: &[Lap]| {
+            let mut karts: BTreeMap<i32, Vec<f64>> = BTreeMap::new();
+            for lap in laps {
+                if let Some(number) = kart_numbers.get(&lap.kart_id) {
+                    karts.entry(*number).or_default().push(lap.lap_time);
+                }
+            }
+            karts
+        };
+        let opponent_karts = by_kart(opponent_laps);
+
+        by_kart(laps)
+            .into_iter()
+            .filter_map(|(number, lap_times)| {
+                let opponent_lap_times = opponent_karts.get(&number)?;
+                Some(SharedKart {
+                    number,
+                    amount_of_laps: lap_times.len(),
+                    opponent_amount_of_laps: opponent_lap_times.len(),
+                    fastest_lap_time: lap_times.iter().copied().fold(f64::MAX, f64::min),
+                    opponent_fastest_lap_time: opponent_lap_times.iter().copied().fold(f64::MAX, f64::min),
+                })
+            })
+            .collect()
+    }
+}
+
+fn laps_per_heat(laps: &[Lap]) -> HashMap<i32, Vec<Lap>> {
+    let mut heats: HashMap<i32, Vec<Lap>> = HashMap::new();
+    for lap in laps {
+        heats.entry(lap.heat).or_default().push(lap.clone());
+    }
+    heats
+}
+
+fn fastest(laps: &[Lap]) -> f64 {
+    laps.iter().map(|lap| lap.lap_time).fold(f64::MAX, f64::min)
+}
+
+fn average_clean(laps: &[Lap]) -> Option<f64> {
+    let lap_times: Vec<f64> = laps
+        .iter()
+        .filter(|lap| lap.classification == LapClassification::Clean)
+        .map(|lap| lap.lap_time)
+        .collect();
+
+    if lap_times.is_empty() {
+        None
+    } else {
+        Some(Math::mean(&lap_times))
+    }
+}
+
+/// # the result of the first driver when the two drivers are ranked
+fn result(mode: &RankingMode, laps: &[Lap], driver: i32) -> HeadToHeadResult {
+    let ranking = mode.rank(laps);
+    let position = ranking.iter().find(|ranked| ranked.driver == driver).map(|ranked| ranked.position);
+    let opponent_position = ranking.iter().find(|ranked| ranked.driver != driver).map(|ranked| ranked.position);
+
+    match position.cmp(&opponent_position) {
+        Ordering::Less => HeadToHeadResult::Win,
+        Ordering::Greater => HeadToHeadResult::Loss,
+        Ordering::Equal => HeadToHeadResult::Tie,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::modules::helpers::test_data::{driver, laps_in_heat};
+
+    fn heat(id: i32) -> Session {
+        Session {
+            id,
+            heat_id: format!("heat {}", id),
+            heat_type: "race".to_string(),
+            start_date: NaiveDate::from_ymd_opt(2024, 5, id as u32).unwrap().and_hms_opt(12, 0, 0).unwrap(),
+            track: 1,
+        }
+    }
+
+    fn kart(id: i32, number: i32) -> Vehicle {
+        Vehicle {
+            id,
+            number,
+            brand: String::new(),
+            model: String::new(),
+            horsepower: 0,
+            modified: false,
+        }
+    }
+
+    fn head_to_head(laps: &[Lap], opponent_laps: &[Lap]) -> HeadToHead {
+        HeadToHead::new(
+            &driver(1, "a"),
+            &driver(2, "b"),
+            laps,
+            opponent_laps,
+            &[heat(1), heat(2), heat(3), heat(4)],
+            &[kart(1, 10), kart(2, 20)],
+        )
+    }
+
+    #[test]
+    fn wins_losses_and_ties() {
+        let laps = [
+            laps_in_heat(1, 1, 1, &[30.0, 30.0, 30.0]),
+            laps_in_heat(2, 1, 2, &[32.0, 32.0]),
+            laps_in_heat(3, 1, 1, &[30.0, 30.0]),
+            laps_in_heat(4, 1, 1, &[29.0, 29.0]),
+        ].concat();
+        let opponent_laps = [
+            laps_in_heat(1, 2, 1, &[31.0, 31.0, 31.0]),
+            laps_in_heat(2, 2, 1, &[31.0, 31.0, 31.0]),
+            laps_in_heat(3, 2, 2, &[30.0, 30.0]),
+        ].concat();
+        let head_to_head = head_to_head(&laps, &opponent_laps);
+
+        let record = WinLossRecord { wins: 1, losses: 1, ties: 1 };
+        assert_eq!(head_to_head.finishing_order, record);
+        assert_eq!(head_to_head.fastest_lap, record);
+        assert_eq!(head_to_head.average_fastest_lap_delta, Some(0.0));
+
+        // only the shared heats, the latest first
+        let heats: Vec<(&str, HeadToHeadResult)> = head_to_head.heats.iter().map(|heat| (heat.heat_id.as_str(), heat.finishing_order)).collect();
+        assert_eq!(heats, vec![
+            ("heat 3", HeadToHeadResult::Tie),
+            ("heat 2", HeadToHeadResult::Loss),
+            ("heat 1", HeadToHeadResult::Win),
+        ]);
+        assert_eq!((head_to_head.heats[0].kart, head_to_head.heats[0].opponent_kart), (10, 20));
+        assert_eq!(head_to_head.heats[2].fastest_lap_delta, -1.0);
+    }
+
+    #[test]
+    fn shared_karts() {
+        let laps = [laps_in_heat(1, 1, 1, &[30.0, 29.0]), laps_in_heat(2, 1, 2, &[32.0])].concat();
+        let opponent_laps = laps_in_heat(1, 2, 1, &[31.0, 31.5, 30.5]);
+        let head_to_head = head_to_head(&laps, &opponent_laps);
+
+        assert_eq!(head_to_head.karts.len(), 1);
+        let kart = &head_to_head.karts[0];
+        assert_eq!(kart.number, 10);
+        assert_eq!((kart.amount_of_laps, kart.opponent_amount_of_laps), (2, 3));
+        assert_eq!((kart.fastest_lap_time, kart.opponent_fastest_lap_time), (29.0, 30.5));
+    }
+
+    #[test]
+    fn average_lap_delta_needs_clean_laps() {
+        let laps = laps_in_heat(1, 1, 1, &[30.0, 31.0]);
+        let mut opponent_laps = laps_in_heat(1, 2, 1, &[32.0, 32.0]);
+        assert_eq!(head_to_head(&laps, &opponent_laps).heats[0].average_lap_delta, Some(-1.5));
+
+        for lap in &mut opponent_laps {
+            lap.classification = LapClassification::Slowed;
+        }
+        assert_eq!(head_to_head(&laps, &opponent_laps).heats[0].average_lap_delta, None);
+    }
+
+    #[test]
+    fn no_shared_heats() {
+        let head_to_head = head_to_head(&laps_in_heat(1, 1, 1, &[30.0]), &laps_in_heat(2, 2, 1, &[30.0]));
+
+        assert_eq!(head_to_head.finishing_order, WinLossRecord::default());
+        assert!(head_to_head.heats.is_empty());
+        assert_eq!(head_to_head.average_fastest_lap_delta, None);
+        assert!((head_to_head.win_probability - 0.5).abs() < 0.000_001);
+    }
+}
@@ -7,16 +7,34 @@ use crate::modules::database::models::driver::{sanitize_name, Driver};
 use crate::modules::database::models::session::Session;
 use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::{Lap, NewLap};
-use log::{info, warn};
-
+use crate::modules::database::models::track::Track;
+use crate::modules::database::models::rating_replay::RatingReplay;
+use crate::modules::database::models::record::Record;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::heat_source::HeatSource;
+use crate::modules::cache_tags::CacheTag;
+use cornucopia_async::GenericClient;
+use log::warn;
+
+use std::sync::Arc;
 use tokio::task::JoinSet;
 
 
-pub async fn get_heats_from_api(heat_ids: Vec<String>) -> Vec<WebResponse> {
+/// # get multiple heats from a source
+/// get the heats with the given ids concurrently. heats that fail to load are skipped.
+///
+/// ## Arguments
+/// * `source` - the source to get the heats from
+/// * `heat_ids` - the ids of the heats
+///
+/// ## Returns
+/// * `Vec<WebResponse>` - the heats that could be loaded
+pub async fn get_heats_from_api(source: Arc<dyn HeatSource>, heat_ids: Vec<String>) -> Vec<WebResponse> {
     let mut tasks = JoinSet::new();
 
     for heat_id in heat_ids {
-        tasks.spawn(get_heat_from_api(heat_id));
+        let source = source.clone();
+        tasks.spawn(async move { source.get_heat(&heat_id).await });
     }
 
     let mut heats: Vec<WebResponse> = Vec::new();
@@ -34,52 +52,120 @@ pub async fn get_heats_from_api(heat_ids: Vec<String>) -> Vec<WebResponse> {
     heats
 }
 
-pub async fn get_todays_heats_from_api() -> Vec<String> {
-    let mut heats: Vec<String> = Vec::new();
-    let request_url = "http://reserveren.kartbaangroningen.nl/GetHeatResults.ashx";
-    let response = reqwest::get(request_url).await.unwrap();
-
-    let body = response.text().await.unwrap();
-
-    let mut body_cleaned = body.replace('(', "");
-    body_cleaned = body_cleaned.replace(");", "");
+/// # save a heat
+/// save a heat that was driven on the current track.
+/// see `save_heat_on_track`
+///
+/// ## Arguments
+/// * `heat` - the heat to save
+///
+/// ## Returns
+/// * `String` - the id of the saved heat
+pub async fn save_heat(heat: WebResponse) -> CustomResult<String> {
+    save_heat_on_track(heat, None).await
+}
 
-    let json: HeatsList = serde_json::from_str(&body_cleaned).unwrap();
-    for heat in json.heats {
-        heats.push(heat.id);
+/// # save a heat on a track
+/// save a heat with its karts, drivers and laps and apply the new ratings of the drivers.
+/// heats that were driven before already rated heats are rated by the next replay of the ratings.
+/// everything is done in a single transaction. when anything fails nothing of the heat is kept,
+/// so saving the heat again gives the same result as if the first attempt never happened.
+///
+/// ## Arguments
+/// * `heat` - the heat to save
+/// * `track` - the id of the track the heat was driven on, the current track when `None`
+///
+/// ## Returns
+/// * `String` - the id of the saved heat
+pub async fn save_heat_on_track(heat: WebResponse, track: Option<i32>) -> CustomResult<String> {
+    let mut client = get_pool().get().await?;
+    let transaction = client.transaction().await?;
+
+    // dropping the transaction without committing rolls it back
+    let saved = insert_heat(&transaction, heat, track).await?;
+
+    // records of heats driven later would have been set too early, so they are rebuilt instead
+    let broken_records = if saved.has_later_heats {
+        Record::schedule_rebuild(&transaction).await?;
+        vec![]
+    } else {
+        Record::update_from_heat(&transaction, &saved.session).await?
+    };
+    transaction.commit().await?;
+    CacheTag::invalidate(&saved.cache_tags).await;
+
+    if saved.has_later_heats {
+        // the heat is saved, a failed rebuild stays scheduled and is retried by the cron job
+        if let Err(error) = Record::rebuild_all().await {
+            warn!(target:"heat_api:save_heat_on_track", "Error rebuilding the records: {}", error);
+        }
+    } else {
+        Record::publish(&broken_records).await;
     }
 
-    heats
+    Ok(saved.session.heat_id)
 }
 
-pub async fn get_heat_from_api(heat_id: String) -> serde_json::Result<WebResponse> {
-    info!(target: "modules/heat_api:querying_heat", "Getting heat {} from api", heat_id);
-    let request_url =
-        format!("http://reserveren.kartbaangroningen.nl/GetHeatResults.ashx?heat={heat_id}");
-    let response = reqwest::get(&request_url).await.unwrap();
-
-    let body = response.text().await.unwrap();
+/// # replace a heat
+/// save a heat in place of the heat with the same id, which stays on the track it was driven on.
+/// the old heat is deleted in the same transaction, so it is kept when the new heat can not be
+/// saved. the records are not updated, rebuild them once all heats are replaced.
+///
+/// ## Arguments
+/// * `heat` - the heat to save
+///
+/// ## Returns
+/// * `String` - the id of the saved heat
+pub async fn replace_heat(heat: WebResponse) -> CustomResult<String> {
+    let old_session = match Session::get_by_id(&heat.heat.id).await {
+        Ok(session) => Some(session),
+        Err(Error::NotFoundError { .. }) => None,
+        Err(err) => return Err(err),
+    };
+
+    let mut client = get_pool().get().await?;
+    let transaction = client.transaction().await?;
+
+    let mut cache_tags = vec![];
+    let mut track = None;
+    if let Some(old_session) = &old_session {
+        let old_drivers = Driver::from_laps(&old_session.get_laps().await?).await?;
+        cache_tags.extend(CacheTag::of_drivers(&old_drivers));
+        track = Some(old_session.track);
+
+        old_session.delete_with(&transaction).await?;
+    }
 
+    let saved = insert_heat(&transaction, heat, track).await?;
+    transaction.commit().await?;
+    cache_tags.extend(saved.cache_tags);
+    CacheTag::invalidate(&cache_tags).await;
 
-    // clean response string
-    let mut body_cleaned = body.replace('(', "");
-    body_cleaned = body_cleaned.replace(");", "");
-    serde_json::from_str(&body_cleaned)
+    Ok(saved.session.heat_id)
 }
 
-pub async fn save_heat(heat: WebResponse) -> CustomResult<String> {
-    println!("0");
-
-    if Session::exists(&heat.heat.id).await {
-        return Err(Error::AlreadyExistsError {});
-    }
-
-    println!("1");
+/// a heat that was inserted but not committed yet
+struct InsertedHeat {
+    session: Session,
+    cache_tags: Vec<CacheTag>,
+    has_later_heats: bool,
+}
 
+/// # insert a heat
+/// insert a heat with its karts, drivers and laps and rate it, or schedule a replay of the
+/// ratings when it can not be rated yet. see `save_heat_on_track`
+///
+/// ## Arguments
+/// * `client` - the database client, the heat is only saved when its transaction is committed
+/// * `heat` - the heat to insert
+/// * `track` - the id of the track the heat was driven on, the current track when `None`
+///
+/// ## Returns
+/// * `InsertedHeat` - the heat with the cache tags to invalidate after committing
+async fn insert_heat<C: GenericClient>(client: &C, heat: WebResponse, track: Option<i32>) -> CustomResult<InsertedHeat> {
     // cleanup the name
     let mut name = heat.heat.heat_type_name.clone();
     let fullchars = "Grand Prix";
-    println!("2");
     if name.contains("Gran") && !name.ends_with("x") {
         for i in 0..(fullchars.len() - 4) {
             // get the first name.len - 1 letters of the string
@@ -93,31 +179,39 @@ pub async fn save_heat(heat: WebResponse) -> CustomResult<String> {
             }
         }
     }
-    println!("3");
 
     for driver in &heat.results {
         if driver.participation.driver_name.parse::<f64>().is_ok() {
-            return Err(Error::InvalidNameError {});
+            return Err(Error::InvalidNameError { name: driver.participation.driver_name.clone() });
         }
     }
-    println!("4");
 
-    let heat_id = Session::ensure_exists(&heat.heat.id, &*name, &heat.heat.start_time).await;
-    println!("5");
+    let track = match track {
+        Some(track) => track,
+        None => Track::current(client).await?.id,
+    };
 
+    let session = match Session::new_if_not_exists(client, &heat.heat.id, &name, &heat.heat.start_time, track).await? {
+        Some(session) => session,
+        None => return Err(Error::AlreadyExistsError { what: format!("heat {}", heat.heat.id) }),
+    };
+
+    // the new laps change the lists and the track, and everything the drivers and karts are in
+    let mut cache_tags = vec![CacheTag::Heat(session.heat_id.clone()), CacheTag::Heats, CacheTag::Drivers, CacheTag::Karts, CacheTag::Track(track)];
     for driver in heat.results {
         let driver_name = sanitize_name(&driver.participation.driver_name);
 
         let kart = Vehicle::ensure_exists(
+            client,
             driver.result.kart_nr,
             "RiM0".to_string(),
             "ALPHA2".to_string(),
             9,
-            true).await;
+            true).await?;
 
-        println!("5.1");
-        let driver_id = Driver::ensure_exists(&driver_name).await;
-        println!("5.2");
+        let driver_id = Driver::ensure_exists(client, &driver_name).await?;
+        cache_tags.push(CacheTag::Driver(driver_id.id));
+        cache_tags.push(CacheTag::Kart(kart.number));
 
         let mut laps: Vec<NewLap> = Vec::new();
 
@@ -125,7 +219,7 @@ pub async fn save_heat(heat: WebResponse) -> CustomResult<String> {
         for lap in driver.result.lap_times {
             lap_in_heat += 1;
             laps.push(NewLap {
-                heat: heat_id.id,
+                heat: session.id,
                 driver: driver_id.id,
                 kart_id: kart.id,
                 lap_in_heat: lap_in_heat as i32,
@@ -133,30 +227,23 @@ pub async fn save_heat(heat: WebResponse) -> CustomResult<String> {
             });
         }
 
-        let _ = Lap::insert_bulk(&laps).await;
-        println!("5.3");
-
+        Lap::insert_bulk(client, &laps).await?;
     }
-    println!("6");
 
-    heat_id.apply_ratings().await;
-    println!("7");
-
-    Ok(heat_id.heat_id)
-}
+    Lap::classify_heat(client, &session).await?;
 
+    // ratings build on the ratings of earlier heats. when heats that were driven later are
+    // already rated, or a replay is pending, the heat is rated by a replay instead
+    let has_later_heats = session.has_later_heats(client).await?;
+    if has_later_heats || RatingReplay::get_unfinished(client).await?.is_some() {
+        RatingReplay::schedule(client, Some(session.start_date)).await?;
+    } else {
+        session.apply_ratings(client).await?;
+    }
 
-#[derive(Deserialize, Debug)]
-struct HeatsList {
-    #[serde(rename = "Results")]
-    pub heats: Vec<HeatId>,
+    Ok(InsertedHeat { session, cache_tags, has_later_heats })
 }
 
-#[derive(Deserialize, Debug)]
-struct HeatId {
-    #[serde(rename = "Id")]
-    pub id: String,
-}
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct HeatInfo {
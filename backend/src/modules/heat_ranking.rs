@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+
+use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::database::models::lap::Lap;
+
+/// the heat types ranked on finishing position when `FINISH_POSITION_HEAT_TYPES` is not set
+pub const DEFAULT_FINISH_POSITION_HEAT_TYPES: &str = "grand prix,race";
+
+static FINISH_POSITION_HEAT_TYPES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// # get the heat types ranked on finishing position
+/// the lowercase names in `FINISH_POSITION_HEAT_TYPES`, read once
+fn finish_position_heat_types() -> &'static [String] {
+    FINISH_POSITION_HEAT_TYPES.get_or_init(|| {
+        dotenv().ok();
+
+        env::var("FINISH_POSITION_HEAT_TYPES")
+            .unwrap_or(DEFAULT_FINISH_POSITION_HEAT_TYPES.to_string())
+            .split(',')
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect()
+    })
+}
+
+/// # ranking mode
+/// how the result of a heat is decided
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RankingMode {
+    /// the driver with the fastest lap wins. used for practice and qualifying
+    FastestLap,
+    /// the driver that finished first wins. drivers that completed more laps finish ahead,
+    /// on the same amount of laps the lowest total time finishes ahead
+    FinishPosition,
+}
+
+/// # ranked driver
+/// the position a driver finished in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RankedDriver {
+    pub driver: i32,
+    pub position: usize,
+    pub amount_of_laps: usize,
+    pub total_time: f64,
+    pub fastest_lap_time: f64,
+}
+
+impl RankingMode {
+    /// # get the ranking mode of a heat type
+    /// heat types that contain one of the names in `FINISH_POSITION_HEAT_TYPES`, separated by
+    /// commas and compared case-insensitive, are ranked on finishing position. all other heat
+    /// types are ranked on fastest lap.
+    ///
+    /// ## Arguments
+    /// * `heat_type` - the type of the heat
+    ///
+    /// ## Returns
+    /// * `RankingMode` - the ranking mode of the heat type
+    pub fn for_heat_type(heat_type: &str) -> RankingMode {
+        let heat_type = heat_type.to_lowercase();
+        let is_race = finish_position_heat_types()
+            .iter()
+            .any(|name| heat_type.contains(name.as_str()));
+
+        if is_race {
+            RankingMode::FinishPosition
+        } else {
+            RankingMode::FastestLap
+        }
+    }
+
+    /// # rank the drivers of a heat
+    /// drivers that can not be separated share a position, the position after them is skipped
+    ///
+    /// ## Arguments
+    /// * `laps` - all laps driven in the heat
+    ///
+    /// ## Returns
+    /// * `Vec<RankedDriver>` - the drivers in the order they finished, the winner first
+    pub fn rank(&self, laps: &[Lap]) -> Vec<RankedDriver> {
+        let mut drivers: HashMap<i32, RankedDriver> = HashMap::new();
+        for lap in laps {
+            let driver = drivers.entry(lap.driver).or_insert(RankedDriver {
+                driver: lap.driver,
+                position: 0,
+                amount_of_laps: 0,
+                total_time: 0.0,
+                fastest_lap_time: f64::MAX,
+            });
+
+            driver.amount_of_laps += 1;
+            driver.total_time += lap.lap_time;
+            driver.fastest_lap_time = driver.fastest_lap_time.min(lap.lap_time);
+        }
+
+        let mut ranking: Vec<RankedDriver> = drivers.into_values().collect();
+        // sort on driver id first so drivers that tie are always in the same order
+        ranking.sort_by_key(|driver| driver.driver);
+        ranking.sort_by(|a, b| self.compare(a, b));
+
+        for index in 0..ranking.len() {
+            ranking[index].position = if index > 0 && self.compare(&ranking[index - 1], &ranking[index]) == Ordering::Equal {
+                ranking[index - 1].position
+            } else {
+                index + 1
+            };
+        }
+
+        ranking
+    }
+
+    /// # compare two drivers
+    /// the driver that finished ahead is less
+    fn compare(&self, a: &RankedDriver, b: &RankedDriver) -> Ordering {
+        match self {
+            RankingMode::FastestLap => a.fastest_lap_time.total_cmp(&b.fastest_lap_time),
+            RankingMode::FinishPosition => b.amount_of_laps
+                .cmp(&a.amount_of_laps)
+                .then(a.total_time.total_cmp(&b.total_time)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::helpers::test_data::laps;
+
+    fn positions(ranking: &[RankedDriver]) -> Vec<(i32, usize)> {
+        ranking.iter().map(|driver| (driver.driver, driver.position)).collect()
+    }
+
+    #[test]
+    fn fastest_lap_wins() {
+        let practice_laps = [laps(1, &[31.0, 30.0]), laps(2, &[29.5, 40.0]), laps(3, &[30.5])].concat();
+        let ranking = RankingMode::FastestLap.rank(&practice_laps);
+
+        assert_eq!(positions(&ranking), vec![(2, 1), (1, 2), (3, 3)]);
+        assert_eq!(ranking[0].fastest_lap_time, 29.5);
+        assert_eq!(ranking[0].amount_of_laps, 2);
+    }
+
+    #[test]
+    fn more_laps_finish_ahead() {
+        let race_laps = [laps(1, &[30.0, 30.0]), laps(2, &[35.0, 35.0, 35.0]), laps(3, &[29.0, 30.0])].concat();
+        let ranking = RankingMode::FinishPosition.rank(&race_laps);
+
+        assert_eq!(positions(&ranking), vec![(2, 1), (3, 2), (1, 3)]);
+        assert_eq!(ranking[0].total_time, 105.0);
+    }
+
+    #[test]
+    fn ties_share_a_position() {
+        let practice_laps = [laps(3, &[30.0]), laps(1, &[30.0]), laps(2, &[29.0])].concat();
+        let ranking = RankingMode::FastestLap.rank(&practice_laps);
+
+        // the position after the tie is skipped, tied drivers are ordered by id
+        assert_eq!(positions(&ranking), vec![(2, 1), (1, 2), (3, 2)]);
+
+        let race_laps = [laps(1, &[30.0, 30.0]), laps(2, &[30.0, 30.0]), laps(3, &[31.0, 31.0])].concat();
+        let ranking = RankingMode::FinishPosition.rank(&race_laps);
+        assert_eq!(positions(&ranking), vec![(1, 1), (2, 1), (3, 3)]);
+    }
+
+    #[test]
+    fn single_driver() {
+        let ranking = RankingMode::FinishPosition.rank(&laps(1, &[30.0, 31.0]));
+
+        assert_eq!(positions(&ranking), vec![(1, 1)]);
+        assert_eq!(ranking[0].total_time, 61.0);
+        assert_eq!(ranking[0].fastest_lap_time, 30.0);
+    }
+
+    #[test]
+    fn no_laps() {
+        assert!(RankingMode::FastestLap.rank(&[]).is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use dotenvy::dotenv;
+use log::{info, warn};
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+use tokio::fs;
+
+use crate::errors::{ConnectionSnafu, CustomResult, Error, FileDoesNotExistSnafu, InvalidResponseSnafu, NotFoundSnafu};
+use crate::modules::database::models::heat_payload::HeatPayload;
+use crate::modules::heat_api::WebResponse;
+
+/// the results endpoint of kartbaan groningen
+pub const LIVE_URL: &str = "http://reserveren.kartbaangroningen.nl/GetHeatResults.ashx";
+
+/// # source of heat data
+/// a place we can get the results of heats from. this can be the live api of the venue,
+/// a server with the same interface or a directory with saved responses.
+#[rocket::async_trait]
+pub trait HeatSource: Send + Sync {
+    /// # get the heats of today
+    /// get the ids of all heats that have been driven today
+    ///
+    /// ## Returns
+    /// * `Vec<String>` - the heat ids
+    async fn get_todays_heats(&self) -> CustomResult<Vec<String>>;
+
+    /// # get the raw payload of a heat
+    /// get the json payload of a heat exactly as the source returned it
+    ///
+    /// ## Arguments
+    /// * `heat_id` - the id of the heat
+    ///
+    /// ## Returns
+    /// * `String` - the payload
+    async fn get_raw_heat(&self, heat_id: &str) -> CustomResult<String>;
+
+    /// # get a heat
+    /// get a heat and parse it into a `WebResponse`
+    ///
+    /// ## Arguments
+    /// * `heat_id` - the id of the heat
+    ///
+    /// ## Returns
+    /// * `WebResponse` - the parsed heat
+    async fn get_heat(&self, heat_id: &str) -> CustomResult<WebResponse> {
+        let body = self.get_raw_heat(heat_id).await?;
+
+        parse_jsonp(&body)
+    }
+}
+
+/// # heat source over http
+/// gets heats from a server that has the same interface as the venue.
+/// use `HttpHeatSource::live` to get the heats from the venue itself.
+pub struct HttpHeatSource {
+    pub base_url: String,
+}
+
+impl HttpHeatSource {
+    pub fn new(base_url: &str) -> HttpHeatSource {
+        HttpHeatSource {
+            base_url: base_url.to_string(),
+        }
+    }
+
+    /// # source for the live api of kartbaan groningen
+    pub fn live() -> HttpHeatSource {
+        HttpHeatSource::new(LIVE_URL)
+    }
+
+    async fn get_body(&self, request_url: &str) -> CustomResult<String> {
+        reqwest::get(request_url)
+            .await
+            .context(ConnectionSnafu { url: request_url })?
+            .text()
+            .await
+            .context(ConnectionSnafu { url: request_url })
+    }
+}
+
+#[rocket::async_trait]
+impl HeatSource for HttpHeatSource {
+    async fn get_todays_heats(&self) -> CustomResult<Vec<String>> {
+        let body = self.get_body(&self.base_url).await?;
+
+        let json: HeatsList = parse_jsonp(&body)?;
+        Ok(json.heats.into_iter().map(|heat| heat.id).collect())
+    }
+
+    async fn get_raw_heat(&self, heat_id: &str) -> CustomResult<String> {
+        check_heat_id(heat_id)?;
+        info!(target: "modules/heat_source:get_raw_heat", "Getting heat {} from {}", heat_id, self.base_url);
+        let request_url = format!("{}?heat={}", self.base_url, heat_id);
+
+        let body = self.get_body(&request_url).await?;
+
+        // only heats are archived, not the errors of the venue.
+        // a failed archive should not stop the heat from being loaded
+        if parse_jsonp::<WebResponse>(&body).is_ok() {
+            if let Err(error) = HeatPayload::new_if_changed(heat_id, &body).await {
+                warn!(target:"modules/heat_source:get_raw_heat", "Error archiving heat {}: {}", heat_id, error);
+            }
+        }
+
+        Ok(body)
+    }
+}
+
+/// # heat source from a directory
+/// gets heats from a directory with saved responses. every heat is stored in `<heat_id>.json`.
+/// all heats in the directory are seen as the heats of today.
+pub struct DirectoryHeatSource {
+    pub path: PathBuf,
+}
+
+impl DirectoryHeatSource {
+    pub fn new(path: &str) -> DirectoryHeatSource {
+        DirectoryHeatSource {
+            path: PathBuf::from(path),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl HeatSource for DirectoryHeatSource {
+    async fn get_todays_heats(&self) -> CustomResult<Vec<String>> {
+        let mut entries = fs::read_dir(&self.path)
+            .await
+            .context(FileDoesNotExistSnafu { path: self.path.display().to_string() })?;
+
+        let mut heats: Vec<String> = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context(FileDoesNotExistSnafu { path: self.path.display().to_string() })?
+        {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(stem) = path.file_stem() {
+                    heats.push(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+        heats.sort();
+
+        Ok(heats)
+    }
+
+    async fn get_raw_heat(&self, heat_id: &str) -> CustomResult<String> {
+        // the id is part of the path, it can not be allowed to leave the directory
+        check_heat_id(heat_id)?;
+        let path = self.path.join(format!("{}.json", heat_id));
+
+        fs::read_to_string(&path)
+            .await
+            .context(FileDoesNotExistSnafu { path: path.display().to_string() })
+    }
+}
+
+/// # heat source from the archive
+/// gets heats from the payloads archived by `HttpHeatSource`. when a heat has been fetched
+/// multiple times the latest payload is used. all archived heats are seen as the heats of today.
+pub struct ArchiveHeatSource {}
+
+#[rocket::async_trait]
+impl HeatSource for ArchiveHeatSource {
+    async fn get_todays_heats(&self) -> CustomResult<Vec<String>> {
+        Ok(HeatPayload::get_latest_all()
+            .await?
+            .into_iter()
+            .map(|payload| payload.heat_id)
+            .collect())
+    }
+
+    async fn get_raw_heat(&self, heat_id: &str) -> CustomResult<String> {
+        HeatPayload::get_latest(heat_id)
+            .await?
+            .map(|payload| payload.payload)
+            .context(NotFoundSnafu { what: format!("archived heat {}", heat_id) })
+    }
+}
+
+/// # get the configured heat source
+/// get the heat source configured in the environment.
+/// `HEAT_SOURCE_DIR` selects a directory of saved responses, `HEAT_SOURCE_URL` a server with
+/// the same interface as the venue. if neither is set the live api is used.
+///
+/// ## Returns
+/// * `Arc<dyn HeatSource>` - the heat source
+pub fn get_heat_source() -> Arc<dyn HeatSource> {
+    dotenv().ok();
+
+    if let Ok(path) = env::var("HEAT_SOURCE_DIR") {
+        return Arc::new(DirectoryHeatSource::new(&path));
+    }
+
+    match env::var("HEAT_SOURCE_URL") {
+        Ok(url) => Arc::new(HttpHeatSource::new(&url)),
+        Err(_) => Arc::new(HttpHeatSource::live()),
+    }
+}
+
+/// # check a heat id
+/// heat ids are put in file paths and urls, so only ascii letters and digits are allowed
+///
+/// ## Arguments
+/// * `heat_id` - the id of the heat
+fn check_heat_id(heat_id: &str) -> CustomResult<()> {
+    if heat_id.is_empty() || !heat_id.chars().all(|character| character.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidArgumentError { name: "heat id".to_string(), value: heat_id.to_string() });
+    }
+
+    Ok(())
+}
+
+/// # parse a jsonp response
+/// the venue wraps its json in `(...);`. this removes the wrapping when it is there
+/// and parses the json that is left.
+///
+/// ## Arguments
+/// * `body` - the response body
+///
+/// ## Returns
+/// * `T` - the parsed response
+pub fn parse_jsonp<T: for<'de> Deserialize<'de>>(body: &str) -> CustomResult<T> {
+    let mut json = body.trim();
+    if json.starts_with('(') {
+        json = json.trim_start_matches('(');
+        json = json.trim_end_matches(';').trim_end();
+        json = json.strip_suffix(')').unwrap_or(json);
+    }
+
+    serde_json::from_str(json).context(InvalidResponseSnafu)
+}
+
+#[derive(Deserialize, Debug)]
+struct HeatsList {
+    #[serde(rename = "Results")]
+    pub heats: Vec<HeatId>,
+}
+
+#[derive(Deserialize, Debug)]
+struct HeatId {
+    #[serde(rename = "Id")]
+    pub id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heat_ids_are_letters_and_digits() {
+        assert!(check_heat_id("A12bC3").is_ok());
+        assert!(check_heat_id("").is_err());
+        assert!(check_heat_id("../secret").is_err());
+        assert!(check_heat_id("12&heat=34").is_err());
+    }
+}
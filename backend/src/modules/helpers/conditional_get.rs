@@ -0,0 +1,103 @@
+use std::io::Cursor;
+
+use chrono::{DateTime, Utc};
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Response};
+use rocket::Request;
+
+/// offset basis of the 64 bit fnv-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+/// prime of the 64 bit fnv-1a hash
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// # get the etag of a body
+/// the etag is the fnv-1a hash of the body, so it only changes when the body does. the hash is the
+/// same across restarts and builds, so etags stay valid after a deploy.
+fn etag(body: &str) -> String {
+    let hash = body.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    });
+    format!("\"{:016x}\"", hash)
+}
+
+/// # check if the client has the body
+/// `If-None-Match` holds the etags the client has, or `*` for any
+fn client_has(request: &Request<'_>, etag: &str) -> bool {
+    request.headers().get("If-None-Match").any(|value| {
+        value.split(',').map(|tag| tag.trim()).any(|tag| {
+            // weak etags match on the tag only
+            tag == "*" || tag.trim_start_matches("W/") == etag
+        })
+    })
+}
+
+/// # check if the client has the body since it was modified
+/// `If-Modified-Since` holds the date the client got the body, the dates are in whole seconds
+fn client_has_since(request: &Request<'_>, last_modified: DateTime<Utc>) -> bool {
+    request.headers().get_one("If-Modified-Since")
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+/// # format a date as an http date
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// # build a json response
+/// respond with the json, its `ETag` and when it is known its `Last-Modified`. when the client
+/// already has the json only `304 Not Modified` is sent without the body. `If-None-Match` is
+/// checked first, `If-Modified-Since` is only used when the client sends no etags.
+///
+/// the last modified date is when the json was cached, a response can change without a new heat,
+/// for example when drivers are merged or the ratings are replayed, which invalidates the cache.
+///
+/// ## Arguments
+/// * `request` - the request to respond to
+/// * `json` - the body
+/// * `last_modified` - when the json was last changed, `None` when it is not known
+///
+/// ## Returns
+/// * `Response` - the response, more headers can be added to it
+pub fn json_response<'r>(request: &Request<'_>, json: String, last_modified: Option<DateTime<Utc>>) -> response::Result<'r> {
+    let etag = etag(&json);
+
+    let mut response = Response::build();
+    response.header(ContentType::JSON).header(Header::new("ETag", etag.clone()));
+    if let Some(last_modified) = last_modified {
+        response.header(Header::new("Last-Modified", http_date(last_modified)));
+    }
+
+    let not_modified = if request.headers().contains("If-None-Match") {
+        client_has(request, &etag)
+    } else {
+        last_modified.is_some_and(|last_modified| client_has_since(request, last_modified))
+    };
+
+    if not_modified {
+        response.status(Status::NotModified);
+    } else {
+        response.sized_body(json.len(), Cursor::new(json));
+    }
+
+    response.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etag_is_the_fnv_1a_hash() {
+        assert_eq!(etag(""), "\"cbf29ce484222325\"");
+        assert_eq!(etag("a"), "\"af63dc4c8601ec8c\"");
+        assert_eq!(etag("foobar"), "\"85944171f73967e8\"");
+    }
+
+    #[test]
+    fn http_date_round_trips() {
+        let date = DateTime::from_timestamp(1_697_500_000, 0).unwrap();
+        assert_eq!(http_date(date), "Mon, 16 Oct 2023 23:46:40 GMT");
+        assert_eq!(DateTime::parse_from_rfc2822(&http_date(date)).unwrap(), date);
+    }
+}
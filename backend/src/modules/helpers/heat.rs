@@ -25,8 +25,8 @@ impl HeatsHelper {
 
             Err(error) => {
                 return match error.kind() {
-                    PermissionDenied => Err(Error::PermissionDeniedError {}),
-                    _ => Err(Error::FileDoesNotExistError {}),
+                    PermissionDenied => Err(Error::PermissionDeniedError { path: filename.to_string() }),
+                    _ => Err(Error::FileDoesNotExistError { path: filename.to_string(), source: error }),
                 }
             }
         };
@@ -0,0 +1,37 @@
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::lap::Lap;
+use crate::modules::lap_classification::LapClassification;
+
+/// # a driver with a rating of 0
+pub fn driver(id: i32, name: &str) -> Driver {
+    Driver {
+        id,
+        name: name.to_string(),
+        rating: 0.0,
+        uncertainty: 0.0,
+    }
+}
+
+/// # the laps of a driver in heat 1 in kart 1
+/// the laps are numbered from 1 in the given order and are clean
+pub fn laps(driver: i32, lap_times: &[f64]) -> Vec<Lap> {
+    laps_in_heat(1, driver, 1, lap_times)
+}
+
+/// # the laps of a driver in a heat
+/// the laps are numbered from 1 in the given order and are clean
+pub fn laps_in_heat(heat: i32, driver: i32, kart_id: i32, lap_times: &[f64]) -> Vec<Lap> {
+    lap_times
+        .iter()
+        .enumerate()
+        .map(|(index, lap_time)| Lap {
+            id: heat * 10_000 + driver * 100 + index as i32,
+            heat,
+            driver,
+            lap_in_heat: index as i32 + 1,
+            lap_time: *lap_time,
+            kart_id,
+            classification: LapClassification::Clean,
+        })
+        .collect()
+}
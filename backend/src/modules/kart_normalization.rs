@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::env;
+use std::hash::Hash;
+use std::sync::OnceLock;
+
+use chrono::NaiveDateTime;
+use dotenvy::dotenv;
+use log::warn;
+
+/// the amount of laps of evidence a kart offset is shrunk towards 0 with,
+/// when `KART_OFFSET_PRIOR_LAPS` is not set
+pub const DEFAULT_KART_OFFSET_PRIOR_LAPS: f64 = 50.0;
+/// the maximum amount of iterations used to fit the model
+const MAX_ITERATIONS: usize = 200;
+/// the model is fitted when no effect changes more than this between iterations
+const TOLERANCE: f64 = 0.000_1;
+
+/// # observation of a driver in a kart
+/// the median lap time of a driver in a heat
+#[derive(Debug, Clone)]
+pub struct KartObservation {
+    pub heat: i32,
+    pub driver: i32,
+    pub kart: i32,
+    /// the start of the period the heat was driven in
+    pub period: NaiveDateTime,
+    pub lap_time: f64,
+    pub amount_of_laps: i32,
+}
+
+/// # the estimated offset of a kart
+/// the time a kart is slower than the average kart in the same period,
+/// negative for karts that are faster than average
+#[derive(Debug, Clone)]
+pub struct KartEffect {
+    pub kart: i32,
+    pub period: NaiveDateTime,
+    pub time_offset: f64,
+    pub amount_of_laps: i32,
+}
+
+/// # fit the kart offsets
+/// fit a fixed effects model where the lap time is the sum of an effect of the heat
+/// (the conditions), of the driver (their skill) and of the kart in a period. the kart effects
+/// can only be told apart from the driver effects through drivers that drove several karts.
+/// the observations are weighted by their amount of laps and the kart effects are shrunk
+/// towards 0, so karts with few laps do not get extreme offsets.
+///
+/// ## Arguments
+/// * `observations` - the median lap times of the drivers per heat
+///
+/// ## Returns
+/// * `Vec<KartEffect>` - the offset of every kart in every period it was driven in
+pub fn fit_kart_offsets(observations: &[KartObservation]) -> Vec<KartEffect> {
+    let prior_laps = prior_laps();
+
+    let mut driver_effects: HashMap<i32, f64> = HashMap::new();
+    let mut kart_effects: HashMap<(i32, NaiveDateTime), f64> = HashMap::new();
+
+    for _ in 0..MAX_ITERATIONS {
+        let heat_effects = weighted_means(observations, 0.0, |observation| {
+            let residual = observation.lap_time
+                - driver_effects.get(&observation.driver).unwrap_or(&0.0)
+                - kart_effects.get(&(observation.kart, observation.period)).unwrap_or(&0.0);
+            (observation.heat, residual, observation.amount_of_laps as f64)
+        });
+
+        let new_driver_effects = weighted_means(observations, 0.0, |observation| {
+            let residual = observation.lap_time
+                - heat_effects[&observation.heat]
+                - kart_effects.get(&(observation.kart, observation.period)).unwrap_or(&0.0);
+            (observation.driver, residual, observation.amount_of_laps as f64)
+        });
+        driver_effects = new_driver_effects;
+
+        let new_kart_effects = weighted_means(observations, prior_laps, |observation| {
+            let residual = observation.lap_time
+                - heat_effects[&observation.heat]
+                - driver_effects[&observation.driver];
+            ((observation.kart, observation.period), residual, observation.amount_of_laps as f64)
+        });
+
+        let max_change = new_kart_effects
+            .iter()
+            .map(|(key, effect)| (effect - kart_effects.get(key).unwrap_or(&0.0)).abs())
+            .fold(0.0, f64::max);
+        kart_effects = new_kart_effects;
+
+        if max_change < TOLERANCE {
+            break;
+        }
+    }
+
+    // the offsets are relative to the average kart of the period
+    let mut laps_per_kart: HashMap<(i32, NaiveDateTime), i32> = HashMap::new();
+    for observation in observations {
+        *laps_per_kart.entry((observation.kart, observation.period)).or_insert(0) += observation.amount_of_laps;
+    }
+    let karts: Vec<(&(i32, NaiveDateTime), &i32)> = laps_per_kart.iter().collect();
+    let period_means = weighted_means(&karts, 0.0, |(key, laps)| {
+        (key.1, kart_effects[*key], **laps as f64)
+    });
+
+    laps_per_kart
+        .iter()
+        .map(|((kart, period), laps)| KartEffect {
+            kart: *kart,
+            period: *period,
+            time_offset: kart_effects[&(*kart, *period)] - period_means[period],
+            amount_of_laps: *laps,
+        })
+        .collect()
+}
+
+/// # weighted means per group
+///
+/// ## Arguments
+/// * `items` - the items to average
+/// * `prior_weight` - the weight of a prior mean of 0 that is added to every group
+/// * `value` - gets the group, value and weight of an item
+///
+/// ## Returns
+/// * `HashMap<K, f64>` - the mean of every group
+fn weighted_means<T, K, F>(items: &[T], prior_weight: f64, value: F) -> HashMap<K, f64>
+where
+    K: Eq + Hash,
+    F: Fn(&T) -> (K, f64, f64),
+{
+    let mut sums: HashMap<K, (f64, f64)> = HashMap::new();
+    for item in items {
+        let (key, value, weight) = value(item);
+        let sum = sums.entry(key).or_insert((0.0, prior_weight));
+        sum.0 += value * weight;
+        sum.1 += weight;
+    }
+
+    sums.into_iter()
+        .map(|(key, (sum, weight))| (key, sum / weight))
+        .collect()
+}
+
+static PRIOR_LAPS: OnceLock<f64> = OnceLock::new();
+
+/// # get the prior weight of the kart offsets
+/// read once, values that are not a number of at least 0 are replaced by the default
+fn prior_laps() -> f64 {
+    *PRIOR_LAPS.get_or_init(|| {
+        dotenv().ok();
+
+        match env::var("KART_OFFSET_PRIOR_LAPS") {
+            Ok(value) => match value.parse::<f64>() {
+                Ok(prior_laps) if prior_laps >= 0.0 => prior_laps,
+                _ => {
+                    warn!(target:"modules/kart_normalization:prior_laps", "KART_OFFSET_PRIOR_LAPS is not a number of at least 0, using {}", DEFAULT_KART_OFFSET_PRIOR_LAPS);
+                    DEFAULT_KART_OFFSET_PRIOR_LAPS
+                }
+            },
+            Err(_) => DEFAULT_KART_OFFSET_PRIOR_LAPS,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn period(month: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, month, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    /// every driver drives every kart, the lap time is the sum of the effects
+    fn observations(kart_offsets: &[f64], month: u32, amount_of_laps: i32) -> Vec<KartObservation> {
+        let heat_effects = [40.0, 41.5, 39.0, 40.5];
+        let driver_effects = [0.0, 1.0, 2.5, 3.0];
+
+        let mut observations = Vec::new();
+        for (heat, heat_effect) in heat_effects.iter().enumerate() {
+            for (driver, driver_effect) in driver_effects.iter().enumerate() {
+                let kart = (driver + heat) % kart_offsets.len();
+                observations.push(KartObservation {
+                    heat: month as i32 * 100 + heat as i32,
+                    driver: driver as i32,
+                    kart: kart as i32,
+                    period: period(month),
+                    lap_time: heat_effect + driver_effect + kart_offsets[kart],
+                    amount_of_laps,
+                });
+            }
+        }
+        observations
+    }
+
+    fn offset(effects: &[KartEffect], kart: i32, month: u32) -> f64 {
+        effects
+            .iter()
+            .find(|effect| effect.kart == kart && effect.period == period(month))
+            .unwrap()
+            .time_offset
+    }
+
+    #[test]
+    fn recovers_known_offsets() {
+        let effects = fit_kart_offsets(&observations(&[0.5, -0.5], 1, 10_000));
+
+        assert_eq!(effects.len(), 2);
+        assert!((offset(&effects, 0, 1) - 0.5).abs() < 0.01);
+        assert!((offset(&effects, 1, 1) + 0.5).abs() < 0.01);
+        assert!(effects.iter().all(|effect| effect.amount_of_laps == 80_000));
+    }
+
+    #[test]
+    fn offsets_are_relative_to_their_period() {
+        let observations = [observations(&[0.3, -0.3], 1, 10_000), observations(&[1.2, 0.8], 2, 10_000)].concat();
+        let effects = fit_kart_offsets(&observations);
+
+        assert_eq!(effects.len(), 4);
+        assert!((offset(&effects, 0, 2) - 0.2).abs() < 0.01);
+        assert!((offset(&effects, 1, 2) + 0.2).abs() < 0.01);
+        for month in [1, 2] {
+            let mean: f64 = effects.iter().filter(|effect| effect.period == period(month)).map(|effect| effect.time_offset).sum();
+            assert!(mean.abs() < 0.000_1);
+        }
+    }
+
+    #[test]
+    fn few_laps_are_shrunk() {
+        let effects = fit_kart_offsets(&observations(&[0.5, -0.5], 1, 1));
+
+        assert!(offset(&effects, 0, 1) > 0.0);
+        assert!(offset(&effects, 0, 1) < 0.5);
+    }
+
+    #[test]
+    fn identical_karts() {
+        let effects = fit_kart_offsets(&observations(&[0.0, 0.0], 1, 10));
+
+        assert!(effects.iter().all(|effect| effect.time_offset.abs() < 0.000_1));
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::modules::database::models::lap::Lap;
+use crate::modules::database::models::track::Track;
+use crate::modules::helpers::math::Math;
+
+/// scales the median absolute deviation to the standard deviation of normally distributed lap times
+const MAD_SCALE: f64 = 1.4826;
+/// the minimum spread of the lap times of a driver, as part of their median lap time.
+/// without it a driver with a few identical laps would have every other lap flagged
+const MIN_SPREAD_RATIO: f64 = 0.01;
+/// laps this many times the median lap time are two laps where the timing missed the line in between,
+/// laps this many times faster are part of a lap where the timing counted the line twice
+const MISSED_DETECTION_RATIO: f64 = 1.8;
+
+/// # lap classification
+/// why a lap is, or is not, representative of the pace of a driver
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LapClassification {
+    /// a normal racing lap
+    Clean,
+    /// a slow first lap, driven from the pit lane
+    OutLap,
+    /// a slow last lap, driven into the pit lane
+    InLap,
+    /// a slow lap in the middle of the heat, because of a flag, a spin or traffic
+    Slowed,
+    /// a lap that can not have been driven, because the timing missed or double counted the line
+    TimingGlitch,
+}
+
+impl LapClassification {
+    /// # get the name of the classification
+    /// the name is how the classification is stored in the database
+    ///
+    /// ## Returns
+    /// * `&str` - the name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LapClassification::Clean => "clean",
+            LapClassification::OutLap => "out_lap",
+            LapClassification::InLap => "in_lap",
+            LapClassification::Slowed => "slowed",
+            LapClassification::TimingGlitch => "timing_glitch",
+        }
+    }
+
+    /// # get a classification by its name
+    ///
+    /// ## Arguments
+    /// * `name` - the name of the classification, as stored in the database
+    ///
+    /// ## Returns
+    /// * `Option<LapClassification>` - the classification, `None` when the name is unknown
+    pub fn from_name(name: &str) -> Option<LapClassification> {
+        match name {
+            "clean" => Some(LapClassification::Clean),
+            "out_lap" => Some(LapClassification::OutLap),
+            "in_lap" => Some(LapClassification::InLap),
+            "slowed" => Some(LapClassification::Slowed),
+            "timing_glitch" => Some(LapClassification::TimingGlitch),
+            _ => None,
+        }
+    }
+}
+
+/// # classify the laps of a heat
+/// the laps of every driver are compared to their own median lap time, using the median absolute
+/// deviation as spread so the slow laps do not hide each other. laps faster than the minimum lap
+/// time of the track, or so fast that the timing must have counted the line twice, are timing
+/// glitches. other fast laps are clean, a driver can have a good lap. laps more than
+/// the outlier threshold of the track slower than usual are out-laps when they are the first lap
+/// and in-laps when they are the last lap. in the middle of the heat they are slowed, or timing
+/// glitches when they are close to two laps long.
+///
+/// ## Arguments
+/// * `laps` - the laps of the heat
+/// * `track` - the track the heat was driven on
+///
+/// ## Returns
+/// * `HashMap<i32, LapClassification>` - the classification of every lap, by the id of the lap
+pub fn classify_laps(laps: &[Lap], track: &Track) -> HashMap<i32, LapClassification> {
+    let mut driver_laps: HashMap<i32, Vec<&Lap>> = HashMap::new();
+    for lap in laps {
+        driver_laps.entry(lap.driver).or_default().push(lap);
+    }
+
+    let mut classifications = HashMap::new();
+    for laps in driver_laps.values() {
+        let lap_times: Vec<f64> = laps.iter().map(|lap| lap.lap_time).collect();
+        let median = Math::median(lap_times.clone());
+        let deviations = lap_times.iter().map(|lap_time| (lap_time - median).abs()).collect();
+        let spread = (Math::median(deviations) * MAD_SCALE).max(median * MIN_SPREAD_RATIO);
+
+        let first_lap = laps.iter().map(|lap| lap.lap_in_heat).min();
+        let last_lap = laps.iter().map(|lap| lap.lap_in_heat).max();
+
+        for lap in laps {
+            let deviation = (lap.lap_time - median) / spread;
+
+            let classification = if lap.lap_time < track.min_lap_time || lap.lap_time < median / MISSED_DETECTION_RATIO {
+                LapClassification::TimingGlitch
+            } else if deviation <= track.outlier_threshold {
+                LapClassification::Clean
+            } else if Some(lap.lap_in_heat) == first_lap {
+                LapClassification::OutLap
+            } else if Some(lap.lap_in_heat) == last_lap {
+                LapClassification::InLap
+            } else if lap.lap_time > median * MISSED_DETECTION_RATIO {
+                LapClassification::TimingGlitch
+            } else {
+                LapClassification::Slowed
+            };
+
+            classifications.insert(lap.id, classification);
+        }
+    }
+
+    classifications
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::helpers::test_data::laps;
+
+    fn track() -> Track {
+        Track {
+            id: 1,
+            name: "track".to_string(),
+            layout: "layout".to_string(),
+            min_lap_time: 15.0,
+            outlier_threshold: 3.0,
+        }
+    }
+
+    fn classified(laps: &[Lap]) -> Vec<LapClassification> {
+        let classifications = classify_laps(laps, &track());
+        laps.iter().map(|lap| classifications[&lap.id]).collect()
+    }
+
+    #[test]
+    fn slow_laps() {
+        let laps = laps(1, &[45.0, 30.0, 30.2, 29.9, 38.0, 30.1, 30.0, 44.0]);
+
+        assert_eq!(classified(&laps), vec![
+            LapClassification::OutLap,
+            LapClassification::Clean,
+            LapClassification::Clean,
+            LapClassification::Clean,
+            LapClassification::Slowed,
+            LapClassification::Clean,
+            LapClassification::Clean,
+            LapClassification::InLap,
+        ]);
+    }
+
+    #[test]
+    fn timing_glitches() {
+        // below the minimum lap time, two laps in one and the line counted twice
+        let laps = laps(1, &[30.0, 14.0, 30.1, 60.0, 29.9, 15.5, 30.0, 30.2]);
+
+        assert_eq!(classified(&laps), vec![
+            LapClassification::Clean,
+            LapClassification::TimingGlitch,
+            LapClassification::Clean,
+            LapClassification::TimingGlitch,
+            LapClassification::Clean,
+            LapClassification::TimingGlitch,
+            LapClassification::Clean,
+            LapClassification::Clean,
+        ]);
+    }
+
+    #[test]
+    fn fast_laps_are_clean() {
+        let laps = laps(1, &[30.0, 30.1, 29.9, 30.0, 28.5, 30.1]);
+
+        assert_eq!(classified(&laps)[4], LapClassification::Clean);
+    }
+
+    #[test]
+    fn identical_lap_times() {
+        let laps = laps(1, &[30.0; 5]);
+
+        assert!(classified(&laps).iter().all(|classification| *classification == LapClassification::Clean));
+    }
+
+    #[test]
+    fn drivers_are_compared_to_themselves() {
+        let laps = [laps(1, &[30.0, 30.1, 29.9, 30.0]), laps(2, &[40.0, 40.1, 39.9, 40.0])].concat();
+
+        assert!(classified(&laps).iter().all(|classification| *classification == LapClassification::Clean));
+    }
+
+    #[test]
+    fn single_lap() {
+        assert_eq!(classified(&laps(1, &[30.0])), vec![LapClassification::Clean]);
+        assert_eq!(classified(&laps(1, &[14.0])), vec![LapClassification::TimingGlitch]);
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for classification in [
+            LapClassification::Clean,
+            LapClassification::OutLap,
+            LapClassification::InLap,
+            LapClassification::Slowed,
+            LapClassification::TimingGlitch,
+        ] {
+            assert_eq!(LapClassification::from_name(classification.as_str()), Some(classification));
+        }
+        assert_eq!(LapClassification::from_name("unknown"), None);
+    }
+}
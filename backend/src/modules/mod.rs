@@ -1,5 +1,19 @@
+pub mod cache_tags;
+pub mod cache_warming;
+pub mod consistency;
+pub mod duplicate_drivers;
 pub mod heat_api;
+pub mod head_to_head;
+pub mod heat_ranking;
+pub mod kart_normalization;
+pub mod lap_classification;
+pub mod predictions;
+pub mod race_chart;
+pub mod heat_source;
+pub mod rating_systems;
 pub mod redis;
+pub mod response_cache;
+pub mod track_conditions;
 
 pub mod traits {
     pub mod as_map;
@@ -15,16 +29,29 @@ pub mod database {
         pub mod session;
         pub mod vehicle;
         pub mod lap;
+        pub mod heat_payload;
+        pub mod track;
+        pub mod rating_history;
+        pub mod driver_rating;
+        pub mod driver_alias;
+        pub mod rating_replay;
+        pub mod kart_offset;
+        pub mod session_condition;
+        pub mod record;
+        pub mod leaderboard;
 
         pub mod general;
     }
 }
 
 pub mod helpers {
+    pub mod conditional_get;
     pub mod heat;
 
     pub mod math;
     pub mod logging;
+    #[cfg(test)]
+    pub mod test_data;
 
     pub mod rocket_fairings {
         pub mod cors;
@@ -0,0 +1,329 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use json_response_derive::JsonResponse;
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CustomResult;
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::driver_rating::DriverRating;
+use crate::modules::database::models::general::get_pool;
+use crate::modules::database::models::lap::Lap;
+use crate::modules::database::models::rating_history::RatingHistory;
+use crate::modules::database::models::session::Session;
+use crate::modules::rating_systems::{RatingSystem, SystemRating};
+
+/// the amount of buckets the predicted win probabilities are split into for the calibration
+const CALIBRATION_BUCKETS: usize = 10;
+
+/// # predicted result of a heat
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct Prediction {
+    pub system: String,
+    /// the drivers by expected position, the favourite first
+    pub drivers: Vec<PredictedDriver>,
+}
+
+/// # predicted result of a driver
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PredictedDriver {
+    pub driver_name: String,
+    pub rating: SystemRating,
+    pub expected_position: f64,
+    /// the chance of finishing in every position, the first is the chance of winning
+    pub position_probabilities: Vec<f64>,
+}
+
+/// # how well the predictions matched the real heats
+/// every heat is predicted with the ratings the drivers had before it. pairs of drivers that
+/// tied are left out.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BacktestReport {
+    pub system: String,
+    pub amount_of_heats: usize,
+    pub amount_of_pairs: usize,
+    /// the part of the pairs where the favourite finished ahead
+    pub pairwise_accuracy: f64,
+    /// the part of the heats won by the driver with the best expected position
+    pub winner_accuracy: f64,
+    /// the average difference between the expected and the real position
+    pub mean_absolute_position_error: f64,
+    /// the mean squared error of the win probabilities of the pairs, 0.25 is as good as a coin flip
+    pub brier_score: f64,
+    pub calibration: Vec<CalibrationBucket>,
+}
+
+/// # the pairs with a predicted win probability in a range
+/// for well calibrated ratings the observed win rate is close to the predicted win probability
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CalibrationBucket {
+    pub min_probability: f64,
+    pub max_probability: f64,
+    pub amount_of_pairs: usize,
+    pub predicted_win_rate: f64,
+    pub observed_win_rate: f64,
+}
+
+#[derive(Default)]
+struct BucketTotals {
+    amount_of_pairs: usize,
+    predicted: f64,
+    observed: f64,
+}
+
+impl Prediction {
+    /// # predict a heat
+    /// predict the result of a heat with the current ratings of the drivers, their uncertainty
+    /// grown for the time since their last heat. drivers that have not been rated in the system
+    /// get its default rating.
+    ///
+    /// ## Arguments
+    /// * `drivers` - the drivers in the heat
+    /// * `system` - the rating system
+    ///
+    /// ## Returns
+    /// * `Prediction` - the prediction
+    pub async fn of_drivers(drivers: &[Driver], system: Arc<dyn RatingSystem>) -> CustomResult<Prediction> {
+        let client = &get_pool().get().await?;
+        let driver_ids: Vec<i32> = drivers.iter().map(|driver| driver.id).collect();
+        let ratings = DriverRating::get_of_drivers(client, system.name(), &driver_ids).await?;
+
+        let now = Utc::now().naive_utc();
+        let ratings: Vec<SystemRating> = drivers
+            .iter()
+            .map(|driver| match ratings.get(&driver.id) {
+                Some(rating) => rating.rating_at(system.as_ref(), now),
+                None => system.default_rating(),
+            })
+            .collect();
+        let distributions = position_probabilities(system.as_ref(), &ratings);
+
+        let mut predicted: Vec<PredictedDriver> = drivers
+            .iter()
+            .zip(ratings)
+            .zip(distributions)
+            .map(|((driver, rating), probabilities)| PredictedDriver {
+                driver_name: driver.name.clone(),
+                rating,
+                expected_position: expected_position(&probabilities),
+                position_probabilities: probabilities,
+            })
+            .collect();
+        predicted.sort_by(|a, b| a.expected_position.total_cmp(&b.expected_position));
+
+        Ok(Prediction {
+            system: system.name().to_string(),
+            drivers: predicted,
+        })
+    }
+}
+
+impl BacktestReport {
+    /// # backtest a rating system
+    /// predict every rated heat with the ratings from before it and compare the prediction to
+    /// the ranking the ratings were updated with.
+    ///
+    /// ## Arguments
+    /// * `system` - the rating system
+    ///
+    /// ## Returns
+    /// * `BacktestReport` - the report
+    pub async fn new(system: Arc<dyn RatingSystem>) -> CustomResult<BacktestReport> {
+        let heats = Session::get_all_chronologicaly().await?;
+        let laps = Lap::from_heats(&heats).await?;
+        let history = RatingHistory::from_system(system.name()).await?;
+
+        let mut heat_laps: HashMap<i32, Vec<Lap>> = HashMap::new();
+        for lap in laps {
+            heat_laps.entry(lap.heat).or_default().push(lap);
+        }
+        let mut ratings_before: HashMap<(i32, i32), SystemRating> = HashMap::new();
+        for change in history {
+            ratings_before.insert((change.session, change.driver), SystemRating {
+                rating: change.rating_before,
+                uncertainty: change.uncertainty_before,
+                volatility: change.volatility_before,
+            });
+        }
+
+        let mut amount_of_heats = 0;
+        let mut winners_predicted = 0;
+        let mut position_errors: Vec<f64> = Vec::new();
+        let mut squared_errors: Vec<f64> = Vec::new();
+        let mut correct_pairs = 0;
+        let mut buckets: Vec<BucketTotals> = (0..CALIBRATION_BUCKETS).map(|_| BucketTotals::default()).collect();
+
+        for heat in &heats {
+            let ranking = heat.ranking_mode().rank(heat_laps.get(&heat.id).map_or(&[], |laps| laps.as_slice()));
+            // heats that were not rated, or are still waiting on a replay, can not be tested
+            let Some(ratings) = ranking
+                .iter()
+                .map(|driver| ratings_before.get(&(heat.id, driver.driver)).copied())
+                .collect::<Option<Vec<SystemRating>>>()
+            else {
+                continue;
+            };
+            if ranking.len() < 2 {
+                continue;
+            }
+
+            amount_of_heats += 1;
+            let expected: Vec<f64> = position_probabilities(system.as_ref(), &ratings)
+                .iter()
+                .map(|probabilities| expected_position(probabilities))
+                .collect();
+            for (driver, expected_position) in ranking.iter().zip(&expected) {
+                position_errors.push((driver.position as f64 - expected_position).abs());
+            }
+
+            let favourite = (0..expected.len())
+                .min_by(|a, b| expected[*a].total_cmp(&expected[*b]))
+                .unwrap();
+            if ranking[favourite].position == 1 {
+                winners_predicted += 1;
+            }
+
+            for (index, driver) in ranking.iter().enumerate() {
+                for (other_index, other) in ranking.iter().enumerate().skip(index + 1) {
+                    let won = match driver.position.cmp(&other.position) {
+                        Ordering::Less => 1.0,
+                        Ordering::Greater => 0.0,
+                        Ordering::Equal => continue,
+                    };
+
+                    let probability = system.win_probability(&ratings[index], &ratings[other_index]);
+                    squared_errors.push((probability - won).powi(2));
+                    if (probability > 0.5) == (won == 1.0) {
+                        correct_pairs += 1;
+                    }
+
+                    let bucket = &mut buckets[((probability * CALIBRATION_BUCKETS as f64) as usize).min(CALIBRATION_BUCKETS - 1)];
+                    bucket.amount_of_pairs += 1;
+                    bucket.predicted += probability;
+                    bucket.observed += won;
+                }
+            }
+        }
+
+        let amount_of_pairs = squared_errors.len();
+        Ok(BacktestReport {
+            system: system.name().to_string(),
+            amount_of_heats,
+            amount_of_pairs,
+            pairwise_accuracy: correct_pairs as f64 / amount_of_pairs.max(1) as f64,
+            winner_accuracy: winners_predicted as f64 / amount_of_heats.max(1) as f64,
+            mean_absolute_position_error: position_errors.iter().sum::<f64>() / position_errors.len().max(1) as f64,
+            brier_score: squared_errors.iter().sum::<f64>() / amount_of_pairs.max(1) as f64,
+            calibration: buckets
+                .iter()
+                .enumerate()
+                .filter(|(_, bucket)| bucket.amount_of_pairs > 0)
+                .map(|(index, bucket)| CalibrationBucket {
+                    min_probability: index as f64 / CALIBRATION_BUCKETS as f64,
+                    max_probability: (index + 1) as f64 / CALIBRATION_BUCKETS as f64,
+                    amount_of_pairs: bucket.amount_of_pairs,
+                    predicted_win_rate: bucket.predicted / bucket.amount_of_pairs as f64,
+                    observed_win_rate: bucket.observed / bucket.amount_of_pairs as f64,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// # get the chance of every driver finishing in every position
+/// the amount of drivers finishing ahead of a driver is the sum of the pairwise results against
+/// the other drivers. those are treated as independent, so the chances of a position do not
+/// exactly add up to 1 over the drivers.
+///
+/// ## Arguments
+/// * `system` - the rating system
+/// * `ratings` - the ratings of the drivers
+///
+/// ## Returns
+/// * `Vec<Vec<f64>>` - for every driver the chance of finishing in every position
+fn position_probabilities(system: &dyn RatingSystem, ratings: &[SystemRating]) -> Vec<Vec<f64>> {
+    ratings
+        .iter()
+        .enumerate()
+        .map(|(index, rating)| {
+            // probabilities[n] is the chance of n drivers finishing ahead
+            let mut probabilities = vec![0.0; ratings.len()];
+            probabilities[0] = 1.0;
+
+            for (other_index, other) in ratings.iter().enumerate() {
+                if other_index == index {
+                    continue;
+                }
+
+                let lose = 1.0 - system.win_probability(rating, other);
+                for ahead in (0..ratings.len()).rev() {
+                    let from_ahead = if ahead > 0 { probabilities[ahead - 1] * lose } else { 0.0 };
+                    probabilities[ahead] = probabilities[ahead] * (1.0 - lose) + from_ahead;
+                }
+            }
+
+            probabilities
+        })
+        .collect()
+}
+
+fn expected_position(probabilities: &[f64]) -> f64 {
+    probabilities
+        .iter()
+        .enumerate()
+        .map(|(ahead, probability)| (ahead + 1) as f64 * probability)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::rating_systems::get_rating_system;
+
+    fn ratings(system: &dyn RatingSystem) -> Vec<SystemRating> {
+        let default = system.default_rating();
+        let rated = system.rate(&[(default, 1), (default, 2), (default, 3)]);
+        vec![rated[2], rated[0], default, rated[1]]
+    }
+
+    #[test]
+    fn position_probabilities_add_up() {
+        for name in ["weng_lin", "glicko2", "trueskill", "elo"] {
+            let system = get_rating_system(name).unwrap();
+            let ratings = ratings(system.as_ref());
+            let probabilities = position_probabilities(system.as_ref(), &ratings);
+
+            for driver in &probabilities {
+                assert_eq!(driver.len(), ratings.len());
+                assert!((driver.iter().sum::<f64>() - 1.0).abs() < 0.000_001, "{}", name);
+            }
+
+            // the pairwise chances are symmetric, so the expected positions are 1 to 4 on average
+            let expected: Vec<f64> = probabilities.iter().map(|driver| expected_position(driver)).collect();
+            assert!((expected.iter().sum::<f64>() - 10.0).abs() < 0.000_001, "{}", name);
+            assert!(expected[1] < expected[3] && expected[3] < expected[0], "{}", name);
+        }
+    }
+
+    #[test]
+    fn equal_drivers() {
+        let system = get_rating_system("elo").unwrap();
+        let default = system.default_rating();
+        let probabilities = position_probabilities(system.as_ref(), &[default, default]);
+
+        assert_eq!(probabilities, vec![vec![0.5, 0.5], vec![0.5, 0.5]]);
+        assert_eq!(expected_position(&probabilities[0]), 1.5);
+    }
+
+    #[test]
+    fn single_driver() {
+        let system = get_rating_system("elo").unwrap();
+
+        assert_eq!(position_probabilities(system.as_ref(), &[system.default_rating()]), vec![vec![1.0]]);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use json_response_derive::JsonResponse;
+use rocket::response;
+use rocket::response::Responder;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::database::models::driver::Driver;
+use crate::modules::database::models::lap::Lap;
+use crate::modules::helpers::math::Math;
+
+/// # race chart
+/// the positions of the drivers in a heat after every lap. the positions are reconstructed
+/// from the cumulative lap times, so they assume all drivers crossed the start line together.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonResponse)]
+pub struct RaceChart {
+    pub heat_id: String,
+    pub laps: Vec<RaceChartLap>,
+    pub overtakes: Vec<Overtake>,
+    pub lapped: Vec<Lapping>,
+}
+
+/// # the order after a lap
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RaceChartLap {
+    pub lap: i32,
+    pub positions: Vec<RacePosition>,
+}
+
+/// # the position of a driver after a lap
+/// drivers that did not complete the lap are placed behind the drivers that did,
+/// their times are those of the last lap they completed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RacePosition {
+    pub driver_name: String,
+    pub position: usize,
+    pub laps_completed: i32,
+    pub race_time: f64,
+    pub gap_to_leader: f64,
+    /// the time to the driver ahead, `None` for the leader
+    pub interval: Option<f64>,
+    /// the amount of laps the leader is ahead
+    pub laps_down: i32,
+}
+
+/// # a driver passing another driver
+/// only drivers that both completed the lap are compared, so losing positions to drivers
+/// that stopped is not an overtake
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Overtake {
+    pub lap: i32,
+    pub driver_name: String,
+    pub overtaken_driver_name: String,
+    pub position: usize,
+}
+
+/// # a driver being lapped by the leader
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Lapping {
+    /// the lap of the leader when the driver was lapped
+    pub lap: i32,
+    pub leader_name: String,
+    pub driver_name: String,
+    pub laps_down: i32,
+}
+
+impl RaceChart {
+    /// # create the race chart of a heat
+    ///
+    /// ## Arguments
+    /// * `heat_id` - the id of the heat
+    /// * `laps` - all laps driven in the heat
+    /// * `drivers` - the drivers of the heat
+    ///
+    /// ## Returns
+    /// * `RaceChart` - the race chart
+    pub fn new(heat_id: &str, laps: &[Lap], drivers: &[Driver]) -> RaceChart {
+        let names: HashMap<i32, String> = drivers
+            .iter()
+            .map(|driver| (driver.id, driver.name.clone()))
+            .collect();
+
+        // the time every driver crossed the line, per lap
+        let mut driver_laps: HashMap<i32, Vec<&Lap>> = HashMap::new();
+        for lap in laps {
+            driver_laps.entry(lap.driver).or_default().push(lap);
+        }
+        let mut race_times: Vec<(String, Vec<f64>)> = driver_laps
+            .into_iter()
+            .filter_map(|(driver, mut laps)| {
+                laps.sort_by_key(|lap| lap.lap_in_heat);
+                let times = laps
+                    .iter()
+                    .scan(0.0, |total, lap| {
+                        *total += lap.lap_time;
+                        Some(*total)
+                    })
+                    .collect();
+
+                Some((names.get(&driver)?.clone(), times))
+            })
+            .collect();
+        race_times.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let total_laps = race_times.iter().map(|(_, times)| times.len()).max().unwrap_or(0);
+
+        let mut chart = RaceChart {
+            heat_id: heat_id.to_string(),
+            laps: Vec::new(),
+            overtakes: Vec::new(),
+            lapped: Vec::new(),
+        };
+        let mut previous_positions: HashMap<&str, usize> = HashMap::new();
+        let mut laps_down_recorded: HashMap<&str, usize> = HashMap::new();
+
+        for lap in 1..=total_laps {
+            let mut order: Vec<(&str, &Vec<f64>)> = race_times
+                .iter()
+                .map(|(name, times)| (name.as_str(), times))
+                .collect();
+            // more completed laps first, then the first to cross the line
+            order.sort_by(|(_, a), (_, b)| {
+                let a_laps = a.len().min(lap);
+                let b_laps = b.len().min(lap);
+                b_laps.cmp(&a_laps).then(a[a_laps - 1].total_cmp(&b[b_laps - 1]))
+            });
+
+            let (leader_name, leader_times) = order[0];
+            let leader_time = leader_times[lap - 1];
+
+            let mut positions: Vec<RacePosition> = Vec::new();
+            for (index, (name, times)) in order.iter().enumerate() {
+                let completed = times.len().min(lap);
+                let race_time = times[completed - 1];
+
+                // the drivers ahead completed at least as many laps, so compare on the same lap
+                let interval = if index > 0 {
+                    Some(race_time - order[index - 1].1[completed - 1])
+                } else {
+                    None
+                };
+
+                // drivers on the lead lap completed at least the previous lap when the leader crosses the line
+                let laps_at_leader_time = times.iter().filter(|time| **time <= leader_time).count();
+                let laps_down = (lap - 1).saturating_sub(laps_at_leader_time);
+
+                // only drivers that are still driving can be lapped
+                let recorded = laps_down_recorded.entry(*name).or_insert(0);
+                if laps_down > *recorded && laps_at_leader_time < times.len() {
+                    for down in (*recorded + 1)..=laps_down {
+                        chart.lapped.push(Lapping {
+                            lap: lap as i32,
+                            leader_name: leader_name.to_string(),
+                            driver_name: name.to_string(),
+                            laps_down: down as i32,
+                        });
+                    }
+                    *recorded = laps_down;
+                }
+
+                positions.push(RacePosition {
+                    driver_name: name.to_string(),
+                    position: index + 1,
+                    laps_completed: completed as i32,
+                    race_time: Math::round_float_to_n_decimals(race_time, 3),
+                    gap_to_leader: Math::round_float_to_n_decimals(race_time - leader_times[completed - 1], 3),
+                    interval: interval.map(|interval| Math::round_float_to_n_decimals(interval, 3)),
+                    laps_down: laps_down as i32,
+                });
+            }
+
+            if lap > 1 {
+                chart.overtakes.append(&mut RaceChart::get_overtakes(lap, &order, &previous_positions));
+            }
+
+            previous_positions = order
+                .iter()
+                .enumerate()
+                .map(|(index, (name, _))| (*name, index + 1))
+                .collect();
+
+            chart.laps.push(RaceChartLap {
+                lap: lap as i32,
+                positions,
+            });
+        }
+
+        chart
+    }
+
+    /// # get the overtakes of a lap
+    /// a driver overtook another driver when they were behind them after the previous lap
+    /// and ahead of them after this lap
+    ///
+    /// ## Arguments
+    /// * `lap` - the lap
+    /// * `order` - the drivers in the order they are in after the lap, with their race times
+    /// * `previous_positions` - the positions after the previous lap
+    ///
+    /// ## Returns
+    /// * `Vec<Overtake>` - the overtakes in the lap
+    fn get_overtakes(lap: usize, order: &[(&str, &Vec<f64>)], previous_positions: &HashMap<&str, usize>) -> Vec<Overtake> {
+        let on_lap: Vec<(usize, &str)> = order
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, times))| times.len() >= lap)
+            .map(|(index, (name, _))| (index + 1, *name))
+            .collect();
+
+        let mut overtakes = Vec::new();
+        for (position, name) in &on_lap {
+            for (other_position, other_name) in &on_lap {
+                if other_position <= position {
+                    continue;
+                }
+
+                if previous_positions.get(name) > previous_positions.get(other_name) {
+                    overtakes.push(Overtake {
+                        lap: lap as i32,
+                        driver_name: name.to_string(),
+                        overtaken_driver_name: other_name.to_string(),
+                        position: *position,
+                    });
+                }
+            }
+        }
+
+        overtakes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::helpers::test_data::{driver, laps};
+
+    fn order(chart: &RaceChart, lap: usize) -> Vec<&str> {
+        chart.laps[lap - 1].positions.iter().map(|position| position.driver_name.as_str()).collect()
+    }
+
+    #[test]
+    fn single_driver() {
+        let chart = RaceChart::new("heat", &laps(1, &[30.0, 29.5, 31.0]), &[driver(1, "a")]);
+
+        assert_eq!(chart.laps.len(), 3);
+        let last = &chart.laps[2].positions[0];
+        assert_eq!(last.position, 1);
+        assert_eq!(last.race_time, 90.5);
+        assert_eq!(last.interval, None);
+        assert!(chart.overtakes.is_empty());
+        assert!(chart.lapped.is_empty());
+    }
+
+    #[test]
+    fn overtake() {
+        let laps = [laps(1, &[30.0, 30.0, 30.0]), laps(2, &[31.0, 28.0, 30.0])].concat();
+        let chart = RaceChart::new("heat", &laps, &[driver(1, "a"), driver(2, "b")]);
+
+        assert_eq!(order(&chart, 1), vec!["a", "b"]);
+        assert_eq!(order(&chart, 2), vec!["b", "a"]);
+        assert_eq!(chart.overtakes.len(), 1);
+        assert_eq!(chart.overtakes[0].lap, 2);
+        assert_eq!(chart.overtakes[0].driver_name, "b");
+        assert_eq!(chart.overtakes[0].overtaken_driver_name, "a");
+        assert_eq!(chart.overtakes[0].position, 1);
+        assert_eq!(chart.laps[1].positions[1].interval, Some(1.0));
+    }
+
+    #[test]
+    fn identical_lap_times() {
+        let laps = [laps(1, &[30.0, 30.0]), laps(2, &[30.0, 30.0])].concat();
+        let chart = RaceChart::new("heat", &laps, &[driver(1, "a"), driver(2, "b")]);
+
+        assert_eq!(order(&chart, 2), vec!["a", "b"]);
+        assert_eq!(chart.laps[1].positions[1].interval, Some(0.0));
+        assert_eq!(chart.laps[1].positions[1].gap_to_leader, 0.0);
+        assert!(chart.overtakes.is_empty());
+    }
+
+    #[test]
+    fn different_amount_of_laps() {
+        let laps = [laps(1, &[30.0, 30.0, 30.0]), laps(2, &[29.0, 29.0])].concat();
+        let chart = RaceChart::new("heat", &laps, &[driver(1, "a"), driver(2, "b")]);
+
+        assert_eq!(chart.laps.len(), 3);
+        assert_eq!(order(&chart, 2), vec!["b", "a"]);
+        // the driver that stopped is placed behind, it is not an overtake
+        assert_eq!(order(&chart, 3), vec!["a", "b"]);
+        assert_eq!(chart.laps[2].positions[1].laps_completed, 2);
+        assert_eq!(chart.laps[2].positions[1].race_time, 58.0);
+        assert!(chart.overtakes.is_empty());
+    }
+
+    #[test]
+    fn lapped_driver() {
+        let laps = [laps(1, &[10.0; 6]), laps(2, &[25.0; 3])].concat();
+        let chart = RaceChart::new("heat", &laps, &[driver(1, "a"), driver(2, "b")]);
+
+        let lapped: Vec<(i32, i32)> = chart.lapped.iter().map(|lapping| (lapping.lap, lapping.laps_down)).collect();
+        assert_eq!(lapped, vec![(2, 1), (4, 2), (6, 3)]);
+        assert!(chart.lapped.iter().all(|lapping| lapping.leader_name == "a" && lapping.driver_name == "b"));
+        assert_eq!(chart.laps[5].positions[1].laps_down, 3);
+    }
+}
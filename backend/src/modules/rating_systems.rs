@@ -0,0 +1,559 @@
+use std::cmp::Ordering;
+use std::env;
+use std::sync::{Arc, OnceLock};
+
+use dotenvy::dotenv;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use skillratings::elo::{elo, EloConfig, EloRating};
+use skillratings::glicko2::{glicko2_rating_period, Glicko2Config, Glicko2Rating};
+use skillratings::trueskill::{trueskill, TrueSkillConfig, TrueSkillRating};
+use skillratings::weng_lin::{weng_lin_multi_team, WengLinConfig, WengLinRating};
+use skillratings::{elo, glicko2, trueskill, weng_lin, MultiTeamOutcome, Outcomes};
+
+/// the rating systems used when `RATING_SYSTEMS` is not set
+pub const DEFAULT_RATING_SYSTEMS: &str = "weng_lin";
+/// the part of the uncertainty of a new driver the uncertainty grows with per year of inactivity,
+/// used when `RATING_UNCERTAINTY_DECAY` is not set
+pub const DEFAULT_UNCERTAINTY_DECAY: f64 = 0.5;
+/// the amount of rated heats needed for a rating that is no longer provisional,
+/// used when `PROVISIONAL_SESSIONS` is not set
+pub const DEFAULT_PROVISIONAL_SESSIONS: i32 = 5;
+/// the names of all rating systems
+const RATING_SYSTEM_NAMES: [&str; 4] = ["weng_lin", "glicko2", "trueskill", "elo"];
+
+/// # rating of a driver
+/// the rating of a driver in one rating system. systems without a volatility leave it empty.
+/// for elo the uncertainty is always 0.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SystemRating {
+    pub rating: f64,
+    pub uncertainty: f64,
+    pub volatility: Option<f64>,
+}
+
+/// # rating system
+/// an algorithm that rates drivers on the positions they finish in
+pub trait RatingSystem: Send + Sync {
+    /// # name of the system
+    /// the name the ratings of the system are stored under
+    fn name(&self) -> &'static str;
+
+    /// # the rating of a new driver
+    fn default_rating(&self) -> SystemRating;
+
+    /// # rate a heat
+    /// get the new ratings of the drivers in a heat
+    ///
+    /// ## Arguments
+    /// * `results` - the rating of every driver with the position they finished in.
+    ///   the winner has position 1, drivers with the same position tied
+    ///
+    /// ## Returns
+    /// * `Vec<SystemRating>` - the new ratings in the same order as the results
+    fn rate(&self, results: &[(SystemRating, usize)]) -> Vec<SystemRating>;
+
+    /// # win probability
+    /// the chance a driver finishes ahead of another driver
+    ///
+    /// ## Arguments
+    /// * `rating` - the rating of the driver
+    /// * `opponent` - the rating of the other driver
+    ///
+    /// ## Returns
+    /// * `f64` - the chance, between 0 and 1
+    fn win_probability(&self, rating: &SystemRating, opponent: &SystemRating) -> f64;
+}
+
+/// # weng-lin
+/// rates all drivers of a heat at once as a free for all
+pub struct WengLinSystem {
+    pub config: WengLinConfig,
+}
+
+impl RatingSystem for WengLinSystem {
+    fn name(&self) -> &'static str {
+        "weng_lin"
+    }
+
+    fn default_rating(&self) -> SystemRating {
+        let rating = WengLinRating::new();
+        SystemRating {
+            rating: rating.rating,
+            uncertainty: rating.uncertainty,
+            volatility: None,
+        }
+    }
+
+    fn rate(&self, results: &[(SystemRating, usize)]) -> Vec<SystemRating> {
+        let teams: Vec<Vec<WengLinRating>> = results
+            .iter()
+            .map(|(rating, _)| {
+                vec![WengLinRating {
+                    rating: rating.rating,
+                    uncertainty: rating.uncertainty,
+                }]
+            })
+            .collect();
+
+        let rating_groups: Vec<(&[WengLinRating], MultiTeamOutcome)> = teams
+            .iter()
+            .zip(results)
+            .map(|(team, (_, position))| (&team[..], MultiTeamOutcome::new(*position)))
+            .collect();
+
+        weng_lin_multi_team(&rating_groups, &self.config)
+            .iter()
+            .map(|team| SystemRating {
+                rating: team[0].rating,
+                uncertainty: team[0].uncertainty,
+                volatility: None,
+            })
+            .collect()
+    }
+
+    fn win_probability(&self, rating: &SystemRating, opponent: &SystemRating) -> f64 {
+        weng_lin::expected_score(
+            &WengLinRating { rating: rating.rating, uncertainty: rating.uncertainty },
+            &WengLinRating { rating: opponent.rating, uncertainty: opponent.uncertainty },
+            &self.config,
+        ).0
+    }
+}
+
+/// # glicko-2
+/// a heat is seen as a rating period in which every driver played against all others
+pub struct Glicko2System {
+    pub config: Glicko2Config,
+}
+
+impl RatingSystem for Glicko2System {
+    fn name(&self) -> &'static str {
+        "glicko2"
+    }
+
+    fn default_rating(&self) -> SystemRating {
+        let rating = Glicko2Rating::new();
+        SystemRating {
+            rating: rating.rating,
+            uncertainty: rating.deviation,
+            volatility: Some(rating.volatility),
+        }
+    }
+
+    fn rate(&self, results: &[(SystemRating, usize)]) -> Vec<SystemRating> {
+        let default_volatility = Glicko2Rating::new().volatility;
+        let ratings: Vec<Glicko2Rating> = results
+            .iter()
+            .map(|(rating, _)| Glicko2Rating {
+                rating: rating.rating,
+                deviation: rating.uncertainty,
+                volatility: rating.volatility.unwrap_or(default_volatility),
+            })
+            .collect();
+
+        results
+            .iter()
+            .enumerate()
+            .map(|(index, (_, position))| {
+                let games: Vec<(Glicko2Rating, Outcomes)> = results
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_index, _)| *other_index != index)
+                    .map(|(other_index, (_, other_position))| {
+                        (ratings[other_index], outcome(*position, *other_position))
+                    })
+                    .collect();
+
+                let new_rating = glicko2_rating_period(&ratings[index], &games, &self.config);
+                SystemRating {
+                    rating: new_rating.rating,
+                    uncertainty: new_rating.deviation,
+                    volatility: Some(new_rating.volatility),
+                }
+            })
+            .collect()
+    }
+
+    fn win_probability(&self, rating: &SystemRating, opponent: &SystemRating) -> f64 {
+        let default_volatility = Glicko2Rating::new().volatility;
+        glicko2::expected_score(
+            &Glicko2Rating {
+                rating: rating.rating,
+                deviation: rating.uncertainty,
+                volatility: rating.volatility.unwrap_or(default_volatility),
+            },
+            &Glicko2Rating {
+                rating: opponent.rating,
+                deviation: opponent.uncertainty,
+                volatility: opponent.volatility.unwrap_or(default_volatility),
+            },
+        ).0
+    }
+}
+
+/// # trueskill
+/// approximated as a game against every other driver, the changes of those games are averaged
+pub struct TrueSkillSystem {
+    pub config: TrueSkillConfig,
+}
+
+impl RatingSystem for TrueSkillSystem {
+    fn name(&self) -> &'static str {
+        "trueskill"
+    }
+
+    fn default_rating(&self) -> SystemRating {
+        let rating = TrueSkillRating::new();
+        SystemRating {
+            rating: rating.rating,
+            uncertainty: rating.uncertainty,
+            volatility: None,
+        }
+    }
+
+    fn rate(&self, results: &[(SystemRating, usize)]) -> Vec<SystemRating> {
+        rate_pairwise(results, |player, opponent, outcome| {
+            let (new_rating, _) = trueskill(
+                &TrueSkillRating { rating: player.rating, uncertainty: player.uncertainty },
+                &TrueSkillRating { rating: opponent.rating, uncertainty: opponent.uncertainty },
+                &outcome,
+                &self.config,
+            );
+
+            SystemRating {
+                rating: new_rating.rating,
+                uncertainty: new_rating.uncertainty,
+                volatility: None,
+            }
+        })
+    }
+
+    fn win_probability(&self, rating: &SystemRating, opponent: &SystemRating) -> f64 {
+        trueskill::expected_score(
+            &TrueSkillRating { rating: rating.rating, uncertainty: rating.uncertainty },
+            &TrueSkillRating { rating: opponent.rating, uncertainty: opponent.uncertainty },
+            &self.config,
+        ).0
+    }
+}
+
+/// # multiplayer elo
+/// every driver plays against all others, the changes of those games are averaged
+/// so a heat moves a rating at most `k`
+pub struct EloSystem {
+    pub config: EloConfig,
+}
+
+impl RatingSystem for EloSystem {
+    fn name(&self) -> &'static str {
+        "elo"
+    }
+
+    fn default_rating(&self) -> SystemRating {
+        SystemRating {
+            rating: EloRating::new().rating,
+            uncertainty: 0.0,
+            volatility: None,
+        }
+    }
+
+    fn rate(&self, results: &[(SystemRating, usize)]) -> Vec<SystemRating> {
+        rate_pairwise(results, |player, opponent, outcome| {
+            let (new_rating, _) = elo(
+                &EloRating { rating: player.rating },
+                &EloRating { rating: opponent.rating },
+                &outcome,
+                &self.config,
+            );
+
+            SystemRating {
+                rating: new_rating.rating,
+                uncertainty: 0.0,
+                volatility: None,
+            }
+        })
+    }
+
+    fn win_probability(&self, rating: &SystemRating, opponent: &SystemRating) -> f64 {
+        elo::expected_score(&EloRating { rating: rating.rating }, &EloRating { rating: opponent.rating }).0
+    }
+}
+
+/// # outcome between two positions
+fn outcome(position: usize, other_position: usize) -> Outcomes {
+    match position.cmp(&other_position) {
+        Ordering::Less => Outcomes::WIN,
+        Ordering::Greater => Outcomes::LOSS,
+        Ordering::Equal => Outcomes::DRAW,
+    }
+}
+
+/// # rate a heat as games between every two drivers
+/// rate every driver against every other driver with a two player rating function and
+/// average the changes
+///
+/// ## Arguments
+/// * `results` - the ratings and positions of the drivers
+/// * `rate_game` - gets the new rating of a player after a game against an opponent
+///
+/// ## Returns
+/// * `Vec<SystemRating>` - the new ratings in the same order as the results
+fn rate_pairwise<F>(results: &[(SystemRating, usize)], rate_game: F) -> Vec<SystemRating>
+where
+    F: Fn(&SystemRating, &SystemRating, Outcomes) -> SystemRating,
+{
+    if results.len() < 2 {
+        return results.iter().map(|(rating, _)| *rating).collect();
+    }
+
+    let games = (results.len() - 1) as f64;
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, (rating, position))| {
+            let mut rating_change = 0.0;
+            let mut uncertainty_change = 0.0;
+            for (other_index, (other_rating, other_position)) in results.iter().enumerate() {
+                if other_index == index {
+                    continue;
+                }
+
+                let new_rating = rate_game(rating, other_rating, outcome(*position, *other_position));
+                rating_change += new_rating.rating - rating.rating;
+                uncertainty_change += new_rating.uncertainty - rating.uncertainty;
+            }
+
+            SystemRating {
+                rating: rating.rating + rating_change / games,
+                uncertainty: rating.uncertainty + uncertainty_change / games,
+                volatility: rating.volatility,
+            }
+        })
+        .collect()
+}
+
+/// # get a parameter from the environment
+fn env_parameter(name: &str, default: f64) -> f64 {
+    match env::var(name) {
+        Ok(value) => value.parse::<f64>().unwrap_or_else(|_| {
+            warn!(target:"modules/rating_systems:env_parameter", "{} is not a number, using {}", name, default);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// # build a rating system by name
+/// the parameters of the system are read from the environment, e.g. `ELO_K`
+fn build_rating_system(name: &str) -> Option<Arc<dyn RatingSystem>> {
+    match name {
+        "weng_lin" => {
+            let default = WengLinConfig::new();
+            Some(Arc::new(WengLinSystem {
+                config: WengLinConfig {
+                    beta: env_parameter("WENG_LIN_BETA", default.beta),
+                    uncertainty_tolerance: env_parameter("WENG_LIN_UNCERTAINTY_TOLERANCE", default.uncertainty_tolerance),
+                },
+            }))
+        }
+        "glicko2" => {
+            let default = Glicko2Config::new();
+            Some(Arc::new(Glicko2System {
+                config: Glicko2Config {
+                    tau: env_parameter("GLICKO2_TAU", default.tau),
+                    convergence_tolerance: env_parameter("GLICKO2_CONVERGENCE_TOLERANCE", default.convergence_tolerance),
+                },
+            }))
+        }
+        "trueskill" => {
+            let default = TrueSkillConfig::new();
+            Some(Arc::new(TrueSkillSystem {
+                config: TrueSkillConfig {
+                    draw_probability: env_parameter("TRUESKILL_DRAW_PROBABILITY", default.draw_probability),
+                    beta: env_parameter("TRUESKILL_BETA", default.beta),
+                    default_dynamics: env_parameter("TRUESKILL_DEFAULT_DYNAMICS", default.default_dynamics),
+                },
+            }))
+        }
+        "elo" => Some(Arc::new(EloSystem {
+            config: EloConfig {
+                k: env_parameter("ELO_K", EloConfig::new().k),
+            },
+        })),
+        _ => None,
+    }
+}
+
+struct RatingConfig {
+    /// every rating system, by name
+    all: Vec<Arc<dyn RatingSystem>>,
+    /// the systems in `RATING_SYSTEMS`, the main system first
+    configured: Vec<Arc<dyn RatingSystem>>,
+    uncertainty_decay: f64,
+    provisional_sessions: i32,
+}
+
+static RATING_CONFIG: OnceLock<RatingConfig> = OnceLock::new();
+
+/// # get the rating configuration
+/// the environment is read once, the systems are shared by every heat that is rated
+fn rating_config() -> &'static RatingConfig {
+    RATING_CONFIG.get_or_init(|| {
+        dotenv().ok();
+
+        let all: Vec<Arc<dyn RatingSystem>> = RATING_SYSTEM_NAMES
+            .iter()
+            .filter_map(|name| build_rating_system(name))
+            .collect();
+
+        let names = env::var("RATING_SYSTEMS").unwrap_or(DEFAULT_RATING_SYSTEMS.to_string());
+        let mut configured: Vec<Arc<dyn RatingSystem>> = Vec::new();
+        for name in names.split(',').map(|name| name.trim()).filter(|name| !name.is_empty()) {
+            if configured.iter().any(|system| system.name() == name) {
+                continue;
+            }
+
+            match all.iter().find(|system| system.name() == name) {
+                Some(system) => configured.push(system.clone()),
+                None => {
+                    warn!(target:"modules/rating_systems:rating_config", "unknown rating system: {}", name);
+                }
+            }
+        }
+
+        if configured.is_empty() {
+            configured.extend(all.iter().find(|system| system.name() == DEFAULT_RATING_SYSTEMS).cloned());
+        }
+
+        RatingConfig {
+            all,
+            configured,
+            uncertainty_decay: env_parameter("RATING_UNCERTAINTY_DECAY", DEFAULT_UNCERTAINTY_DECAY),
+            provisional_sessions: env_parameter("PROVISIONAL_SESSIONS", DEFAULT_PROVISIONAL_SESSIONS as f64) as i32,
+        }
+    })
+}
+
+/// # get a rating system by name
+///
+/// ## Arguments
+/// * `name` - the name of the system
+///
+/// ## Returns
+/// * `Option<Arc<dyn RatingSystem>>` - the system, `None` if there is no system with the name
+pub fn get_rating_system(name: &str) -> Option<Arc<dyn RatingSystem>> {
+    rating_config().all.iter().find(|system| system.name() == name).cloned()
+}
+
+/// # get the configured rating systems
+/// get the rating systems listed in `RATING_SYSTEMS`, separated by commas. all of them are
+/// applied to every heat and stored side by side. the first one is the main rating of a driver.
+/// the parameters of the systems are also read from the environment, e.g. `ELO_K`.
+///
+/// ## Returns
+/// * `Vec<Arc<dyn RatingSystem>>` - the rating systems, the main system first
+pub fn get_rating_systems() -> Vec<Arc<dyn RatingSystem>> {
+    rating_config().configured.clone()
+}
+
+/// # get the main rating system
+/// the first of the configured rating systems
+pub fn get_main_rating_system() -> Arc<dyn RatingSystem> {
+    rating_config().configured[0].clone()
+}
+
+/// # grow the uncertainty of an inactive driver
+/// a driver that has not been rated for a while might have gotten better or worse, so the
+/// uncertainty of their rating grows with the time since their last heat. the variance grows by
+/// `RATING_UNCERTAINTY_DECAY` times the uncertainty of a new driver, squared, per year. the
+/// uncertainty never grows past that of a new driver.
+///
+/// ## Arguments
+/// * `system` - the rating system of the rating
+/// * `rating` - the rating after the last heat of the driver
+/// * `days_inactive` - the days since the last heat of the driver
+///
+/// ## Returns
+/// * `SystemRating` - the rating with the grown uncertainty
+pub fn decay_uncertainty(system: &dyn RatingSystem, rating: &SystemRating, days_inactive: f64) -> SystemRating {
+    let default_uncertainty = system.default_rating().uncertainty;
+    if days_inactive <= 0.0 || rating.uncertainty >= default_uncertainty {
+        return *rating;
+    }
+
+    let rate = rating_config().uncertainty_decay;
+    let growth = (rate * default_uncertainty).powi(2) * days_inactive / 365.0;
+    SystemRating {
+        uncertainty: (rating.uncertainty.powi(2) + growth).sqrt().min(default_uncertainty),
+        ..*rating
+    }
+}
+
+/// # the amount of heats a rating is provisional for
+/// ratings of drivers with less than `PROVISIONAL_SESSIONS` rated heats are provisional
+///
+/// ## Returns
+/// * `i32` - the amount of heats
+pub fn provisional_sessions() -> i32 {
+    rating_config().provisional_sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn systems() -> Vec<Arc<dyn RatingSystem>> {
+        ["weng_lin", "glicko2", "trueskill", "elo"]
+            .iter()
+            .map(|name| get_rating_system(name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn systems_by_name() {
+        for system in systems() {
+            assert_eq!(get_rating_system(system.name()).unwrap().name(), system.name());
+        }
+        assert!(get_rating_system("unknown").is_none());
+    }
+
+    #[test]
+    fn rate_follows_the_finishing_order() {
+        for system in systems() {
+            let default = system.default_rating();
+            let rated = system.rate(&[(default, 2), (default, 1), (default, 3)]);
+
+            assert_eq!(rated.len(), 3, "{}", system.name());
+            assert!(rated[1].rating > default.rating, "{}", system.name());
+            assert!(rated[1].rating > rated[0].rating, "{}", system.name());
+            assert!(rated[0].rating > rated[2].rating, "{}", system.name());
+            assert!(rated[2].rating < default.rating, "{}", system.name());
+        }
+    }
+
+    #[test]
+    fn ties_are_rated_the_same() {
+        for system in systems() {
+            let default = system.default_rating();
+            let rated = system.rate(&[(default, 1), (default, 1), (default, 3)]);
+
+            assert!((rated[0].rating - rated[1].rating).abs() < 0.000_001, "{}", system.name());
+            assert!(rated[0].rating > rated[2].rating, "{}", system.name());
+        }
+    }
+
+    #[test]
+    fn win_probability_is_symmetric() {
+        for system in systems() {
+            let default = system.default_rating();
+            let stronger = system.rate(&[(default, 1), (default, 2)])[0];
+
+            let probability = system.win_probability(&stronger, &default);
+            let opponent_probability = system.win_probability(&default, &stronger);
+            assert!((probability + opponent_probability - 1.0).abs() < 0.000_001, "{}", system.name());
+            assert!(probability > 0.5, "{}", system.name());
+            assert!((system.win_probability(&default, &default) - 0.5).abs() < 0.000_001, "{}", system.name());
+        }
+    }
+}
@@ -1,48 +1,136 @@
-use dotenvy::dotenv;
-use redis::{Client, Commands, Connection, FromRedisValue, RedisResult, ToRedisArgs};
 use std::env;
+use std::sync::OnceLock;
+use std::time::Duration;
 
-//TODO:: implement better method to keep a connection alive without the use of constructing a new object
+use deadpool_redis::{Config, Connection, Pool, PoolConfig, Runtime, Timeouts};
+use dotenvy::dotenv;
+use redis::{AsyncCommands, FromRedisValue, RedisResult, ToRedisArgs};
 
-pub struct Redis {}
+use crate::errors::CustomResult;
 
-impl Redis {
-    pub fn connect() -> RedisResult<Connection> {
+/// how long to wait for a redis connection. when redis is down the cache is skipped after this
+const REDIS_TIMEOUT: Duration = Duration::from_millis(500);
+
+static REDIS_POOL: OnceLock<Pool> = OnceLock::new();
+
+/// # set up the redis connection pool
+/// the connections are made when they are first used, so redis does not have to be up yet
+pub fn setup_redis_pool() {
+    get_redis_pool();
+}
+
+/// # get the redis connection pool
+/// the pool is set up on first use when `setup_redis_pool` was not called
+pub fn get_redis_pool() -> &'static Pool {
+    REDIS_POOL.get_or_init(|| {
         dotenv().ok();
 
         let redis_url = env::var("REDIS_URL").expect("REDIS_URL must be set");
-        Client::open(redis_url).unwrap().get_connection()
+        let mut config = Config::from_url(redis_url);
+        config.pool = Some(PoolConfig {
+            timeouts: Timeouts {
+                wait: Some(REDIS_TIMEOUT),
+                create: Some(REDIS_TIMEOUT),
+                recycle: Some(REDIS_TIMEOUT),
+            },
+            ..PoolConfig::default()
+        });
+
+        config.create_pool(Some(Runtime::Tokio1)).expect("invalid REDIS_URL")
+    })
+}
+
+pub struct Redis {}
+
+impl Redis {
+    /// # get a connection
+    /// get a connection from the pool, the connection goes back to the pool when it is dropped
+    pub async fn connect() -> CustomResult<Connection> {
+        Ok(get_redis_pool().get().await?)
     }
 
-    pub fn set_data<K: ToRedisArgs, D: ToRedisArgs + FromRedisValue>(
+    pub async fn set_data<K: ToRedisArgs + Send + Sync, D: ToRedisArgs + FromRedisValue + Send + Sync>(
         conn: &mut Connection,
         key: K,
         data: D,
     ) -> RedisResult<D> {
 
-        conn.set::<K, D, D>(key, data)
+        conn.set::<K, D, D>(key, data).await
     }
 
-    pub fn get_data<K: ToRedisArgs, D: FromRedisValue>(
+    pub async fn get_data<K: ToRedisArgs + Send + Sync, D: FromRedisValue>(
         conn: &mut Connection,
         key: K,
     ) -> RedisResult<D> {
-        conn.get::<K, D>(key)
+        conn.get::<K, D>(key).await
     }
 
-    pub fn get_keys(conn: &mut Connection, pattern: &str) -> RedisResult<Vec<String>> {
-        Redis::keys::<String>(conn, format!("*{}*", pattern))
+    pub async fn has_data<K: ToRedisArgs + Send + Sync>(conn: &mut Connection, key: K) -> RedisResult<bool> {
+        conn.exists(key).await
     }
 
-    pub fn has_data<K: ToRedisArgs>(conn: &mut Connection, key: K) -> RedisResult<bool> {
-        conn.exists(key)
+    pub async fn delete<K: ToRedisArgs + Send + Sync>(conn: &mut Connection, key: K) -> RedisResult<()> {
+        conn.del::<K, ()>(key).await
     }
 
-    pub fn keys<K: ToRedisArgs>(conn: &mut Connection, partial: K) -> RedisResult<Vec<String>> {
-        conn.keys(partial)
+    /// # get data with its ttl
+    ///
+    /// ## Returns
+    /// * `(Option<String>, i64)` - the data and the seconds until it expires, negative when it does not
+    pub async fn get_data_with_ttl(conn: &mut Connection, key: &str) -> RedisResult<(Option<String>, i64)> {
+        redis::pipe().get(key).ttl(key).query_async(conn).await
+    }
+
+    /// # store data with tags
+    /// store the data for `ttl` and add its key to the set of every tag, in one transaction.
+    /// a set expires no sooner than the data in it, so sets of tags that are never invalidated
+    /// do not grow forever.
+    pub async fn set_tagged_data(
+        conn: &mut Connection,
+        key: &str,
+        data: &str,
+        ttl: Duration,
+        tags: &[String],
+    ) -> RedisResult<()> {
+        let ttl = ttl.as_secs() as usize;
+        let mut pipe = redis::pipe();
+        pipe.atomic().set_ex(key, data, ttl).ignore();
+        for tag in tags {
+            pipe.sadd(tag, key).ignore();
+            // a new set gets the ttl, an existing one only when it would expire sooner (redis 7)
+            pipe.cmd("EXPIRE").arg(tag).arg(ttl).arg("NX").ignore();
+            pipe.cmd("EXPIRE").arg(tag).arg(ttl).arg("GT").ignore();
+        }
+
+        pipe.query_async(conn).await
+    }
+
+    /// # delete the data of tags
+    /// delete the sets of the tags and every key in them. data stored with a tag while deleting is
+    /// added to a new set, so it is not lost from the tag.
+    ///
+    /// ## Returns
+    /// * `usize` - the amount of deleted keys
+    pub async fn delete_tagged(conn: &mut Connection, tags: &[String]) -> RedisResult<usize> {
+        if tags.is_empty() {
+            return Ok(0);
+        }
+
+        let (keys,): (Vec<String>,) = redis::pipe()
+            .atomic()
+            .sunion(tags)
+            .del(tags)
+            .ignore()
+            .query_async(conn)
+            .await?;
+
+        if !keys.is_empty() {
+            conn.del::<&[String], ()>(&keys).await?;
+        }
+        Ok(keys.len())
     }
 
-    pub fn delete<K: ToRedisArgs>(conn: &mut Connection, key: K) -> RedisResult<()> {
-        conn.del::<K, ()>(key)
+    pub async fn publish<D: ToRedisArgs + Send + Sync>(conn: &mut Connection, channel: &str, data: D) -> RedisResult<()> {
+        conn.publish::<&str, D, ()>(channel, data).await
     }
 }
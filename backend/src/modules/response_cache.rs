@@ -0,0 +1,199 @@
+use std::convert::Infallible;
+use std::env;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dotenvy::dotenv;
+use log::{error, warn};
+use rocket::http::Header;
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::{self, Responder};
+use rocket::Request;
+use serde::Serialize;
+
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::helpers::conditional_get::json_response;
+use crate::modules::redis::Redis;
+
+/// how long a response is cached for when the route does not say otherwise
+const DEFAULT_CACHE_TTL: u64 = 60 * 60;
+/// how long a client may keep a response, responses can be invalidated before their ttl is over
+const DEFAULT_CACHE_MAX_AGE: u64 = 60;
+
+struct CacheConfig {
+    enabled: bool,
+    ttl: Duration,
+    max_age: u64,
+}
+
+static CACHE_CONFIG: OnceLock<CacheConfig> = OnceLock::new();
+
+/// # get the cache configuration
+/// caching is on in release builds and off in debug builds, unless `CACHE_RESPONSES` says
+/// otherwise. `CACHE_TTL` and `CACHE_MAX_AGE` are in seconds.
+fn cache_config() -> &'static CacheConfig {
+    CACHE_CONFIG.get_or_init(|| {
+        dotenv().ok();
+
+        let enabled = match env::var("CACHE_RESPONSES") {
+            Ok(value) => value == "true" || value == "1",
+            Err(_) => !cfg!(debug_assertions),
+        };
+
+        CacheConfig {
+            enabled,
+            ttl: Duration::from_secs(env_seconds("CACHE_TTL", DEFAULT_CACHE_TTL)),
+            max_age: env_seconds("CACHE_MAX_AGE", DEFAULT_CACHE_MAX_AGE),
+        }
+    })
+}
+
+/// # are responses cached
+pub fn caching_enabled() -> bool {
+    cache_config().enabled
+}
+
+/// # get an amount of seconds from the environment
+fn env_seconds(name: &str, default: u64) -> u64 {
+    match env::var(name) {
+        Ok(value) => value.parse::<u64>().unwrap_or_else(|_| {
+            warn!(target:"modules/response_cache:env_seconds", "{} is not a number, using {}", name, default);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// # the response cache of a request
+/// request guard that reads and stores the response to the request in redis. the key is the full
+/// uri, the query is part of it as the same path can give different results per track.
+///
+/// when redis can not be reached the cache is skipped.
+pub struct ResponseCache {
+    key: String,
+    ttl: Duration,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ResponseCache {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(ResponseCache {
+            key: request.uri().to_string(),
+            ttl: cache_config().ttl,
+        })
+    }
+}
+
+impl ResponseCache {
+    /// # set the ttl
+    /// cache the response for `ttl` instead of `CACHE_TTL`, for responses that change without a
+    /// tag being invalidated
+    pub fn with_ttl(mut self, ttl: Duration) -> ResponseCache {
+        self.ttl = ttl;
+        self
+    }
+
+    /// # read the cached response
+    ///
+    /// ## Returns
+    /// * `Option<Cached<T>>` - the cached response, none when it is not cached or caching is off
+    pub async fn read<T>(&self) -> Option<Cached<T>> {
+        if !caching_enabled() {
+            return None;
+        }
+
+        let mut r_conn = match Redis::connect().await {
+            Ok(r_conn) => r_conn,
+            Err(error) => {
+                error!(target:"modules/response_cache:read", "Error connecting to redis: {}", error);
+                return None;
+            }
+        };
+
+        match Redis::get_data_with_ttl(&mut r_conn, &self.key).await {
+            Ok((Some(entry), ttl)) => {
+                // entries without a valid write time are treated as not cached and overwritten
+                let (modified, body) = entry.split_once('\n')?;
+                let modified = DateTime::from_timestamp(modified.parse().ok()?, 0)?;
+                Some(Cached::Hit { body: body.to_string(), ttl: ttl.max(0) as u64, modified })
+            }
+            Ok((None, _)) => None,
+            Err(error) => {
+                error!(target:"modules/response_cache:read", "Error getting data from redis: {}", error);
+                None
+            }
+        }
+    }
+
+    /// # cache a response
+    /// store the response in the background, tagged with what it depends on so it is removed when
+    /// one of them changes. see `CacheTag`. the time it is stored is kept on the line before the
+    /// json, it is sent as the last modified date.
+    ///
+    /// ## Arguments
+    /// * `data` - the response
+    /// * `tags` - what the response depends on
+    ///
+    /// ## Returns
+    /// * `Cached<T>` - the response, to return from the route
+    pub fn store<T: Serialize>(self, data: T, tags: &[CacheTag]) -> Cached<T> {
+        if !caching_enabled() {
+            return Cached::Uncached { data };
+        }
+
+        let body = serde_json::to_string(&data).unwrap();
+        // http dates are in whole seconds
+        let modified = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+        let tags: Vec<String> = tags.iter().map(|tag| tag.key()).collect();
+        let ttl = self.ttl;
+        let key = self.key;
+        // the json has no line breaks, they are escaped in its strings
+        let entry = format!("{}\n{}", modified.timestamp(), body);
+        tokio::spawn(async move {
+            match Redis::connect().await {
+                Ok(mut r_conn) => {
+                    if let Err(error) = Redis::set_tagged_data(&mut r_conn, &key, &entry, ttl, &tags).await {
+                        error!(target:"modules/response_cache:store", "Error storing data in redis: {}", error);
+                    }
+                }
+                Err(error) => {
+                    error!(target:"modules/response_cache:store", "Error connecting to redis: {}", error);
+                }
+            }
+        });
+
+        Cached::Miss { body, ttl: ttl.as_secs(), modified }
+    }
+}
+
+/// # a cacheable response
+/// responds with the json of the data and tells the client how long it can keep it, at most
+/// `CACHE_MAX_AGE` or the time left in the cache. `X-Cache` says whether it came from the cache.
+/// see `json_response` for the conditional requests
+pub enum Cached<T> {
+    /// the json read from the cache, the seconds it is still cached for and when it was cached
+    Hit { body: String, ttl: u64, modified: DateTime<Utc> },
+    /// the json that was just cached for `ttl` seconds
+    Miss { body: String, ttl: u64, modified: DateTime<Utc> },
+    /// the data, caching is off
+    Uncached { data: T },
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Cached<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let max_age = cache_config().max_age;
+        let (body, modified, cache_control, status) = match self {
+            Cached::Hit { body, ttl, modified } => (body, Some(modified), format!("public, max-age={}", ttl.min(max_age)), "HIT"),
+            Cached::Miss { body, ttl, modified } => (body, Some(modified), format!("public, max-age={}", ttl.min(max_age)), "MISS"),
+            Cached::Uncached { data } => (serde_json::to_string(&data).unwrap(), None, "no-cache".to_string(), "BYPASS"),
+        };
+
+        let mut response = json_response(request, body, modified)?;
+        response.set_header(Header::new("Cache-Control", cache_control));
+        response.set_header(Header::new("X-Cache", status));
+        Ok(response)
+    }
+}
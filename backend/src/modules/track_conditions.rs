@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::env;
+
+use dotenvy::dotenv;
+use log::warn;
+
+use crate::modules::helpers::math::Math;
+
+/// the amount of heats a driver needs on a track to be a regular when
+/// `CONDITION_MIN_SESSIONS` is not set
+pub const DEFAULT_CONDITION_MIN_SESSIONS: usize = 5;
+
+/// # best lap of a driver in a heat
+/// the best lap is adjusted for the kart it was driven in
+#[derive(Debug, Clone)]
+pub struct ConditionObservation {
+    pub session: i32,
+    pub track: i32,
+    pub driver: i32,
+    pub best_lap_time: f64,
+}
+
+/// # the conditions during a heat
+#[derive(Debug, Clone)]
+pub struct ConditionEstimate {
+    pub session: i32,
+    pub condition_index: f64,
+    pub amount_of_drivers: i32,
+}
+
+/// # estimate the track conditions of heats
+/// the best lap of every regular is divided by their usual best lap on the track, the median
+/// of those ratios is the condition index of the heat. an index of 1.0 are usual conditions,
+/// 1.02 means the regulars were 2% slower than usual. heats without regulars get no index.
+///
+/// ## Arguments
+/// * `observations` - the best laps of the drivers per heat
+///
+/// ## Returns
+/// * `Vec<ConditionEstimate>` - the conditions of every heat with regulars
+pub fn estimate_conditions(observations: &[ConditionObservation]) -> Vec<ConditionEstimate> {
+    let min_sessions = min_sessions();
+
+    // the usual best lap of every driver on every track
+    let mut best_laps: HashMap<(i32, i32), Vec<f64>> = HashMap::new();
+    for observation in observations {
+        best_laps
+            .entry((observation.track, observation.driver))
+            .or_default()
+            .push(observation.best_lap_time);
+    }
+    let references: HashMap<(i32, i32), f64> = best_laps
+        .into_iter()
+        .filter(|(_, laps)| laps.len() >= min_sessions)
+        .map(|(key, laps)| (key, Math::median(laps)))
+        .collect();
+
+    let mut ratios: HashMap<i32, Vec<f64>> = HashMap::new();
+    for observation in observations {
+        if let Some(reference) = references.get(&(observation.track, observation.driver)) {
+            ratios
+                .entry(observation.session)
+                .or_default()
+                .push(observation.best_lap_time / reference);
+        }
+    }
+
+    ratios
+        .into_iter()
+        .map(|(session, ratios)| ConditionEstimate {
+            session,
+            amount_of_drivers: ratios.len() as i32,
+            condition_index: Math::median(ratios),
+        })
+        .collect()
+}
+
+/// # get the amount of heats a regular has driven
+fn min_sessions() -> usize {
+    dotenv().ok();
+
+    match env::var("CONDITION_MIN_SESSIONS") {
+        Ok(value) => value.parse::<usize>().unwrap_or_else(|_| {
+            warn!(target:"modules/track_conditions:min_sessions", "CONDITION_MIN_SESSIONS is not a number, using {}", DEFAULT_CONDITION_MIN_SESSIONS);
+            DEFAULT_CONDITION_MIN_SESSIONS
+        }),
+        Err(_) => DEFAULT_CONDITION_MIN_SESSIONS,
+    }
+}
@@ -0,0 +1,23 @@
+use rocket::get;
+
+use crate::errors::CustomResult;
+use crate::modules::database::models::session_condition::{SessionCondition, TrackConditions};
+use crate::modules::database::models::track::Track;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::response_cache::{Cached, ResponseCache};
+
+/// # get the track conditions
+/// get the condition index of every heat and every day on a track. an index of 1.0 are usual
+/// conditions, 1.02 means the regulars were 2% slower than they usually are.
+#[get("/conditions?<track>")]
+pub async fn get_of_track(track: Option<i32>, cache: ResponseCache) -> CustomResult<Cached<TrackConditions>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let track = Track::get_or_current(track).await?;
+    let conditions = SessionCondition::of_track(track).await?;
+
+    let tags = [CacheTag::Track(conditions.track.id)];
+    Ok(cache.store(conditions, &tags))
+}
@@ -1,82 +1,151 @@
-use rocket::http::{ContentType, Status};
 use rocket::request::Request;
-use rocket::response::{self, Responder, Response};
+use rocket::response::{self, Responder};
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use std::collections::HashMap;
+use std::time::Duration;
 
 use json_response_derive::JsonResponse;
-use log::error;
-use rocket::http::uri::Origin;
 use rocket::{get, FromForm};
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{CustomResult, Error};
 use crate::modules::database::models::driver::{sanitize_name, Driver, DriverStats};
 use crate::modules::database::models::session::Session;
 use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::Lap;
-use crate::modules::redis::Redis;
+use crate::modules::database::models::kart_offset::KartOffsets;
+use crate::modules::database::models::leaderboard::{Leaderboard, LeaderboardMetric, LeaderboardWindow, Qualification};
+use crate::modules::database::models::session_condition::SessionConditions;
+use crate::modules::database::models::track::Track;
+use crate::modules::database::models::rating_history::RatingChange;
+use crate::modules::database::models::driver_rating::DriverRating;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::head_to_head::HeadToHead;
+use crate::modules::predictions::Prediction;
+use crate::modules::rating_systems::{get_main_rating_system, get_rating_system};
+use crate::modules::response_cache::{Cached, ResponseCache};
 use crate::routes::api::heat::ApiLap;
 
-use crate::macros::request_caching::{cache_response, read_cache_request};
 /**************************************************************************************************/
 /**************** ROUTES **************************************************************************/
 /**************************************************************************************************/
 
+/// how long a leaderboard is cached, the window of a leaderboard moves with the date
+const LEADERBOARD_TTL: Duration = Duration::from_secs(5 * 60);
+
 pub struct Paginated {
     pub limit: u32,
     pub page: u32,
 }
 
-#[get("/drivers/<driver_name>", rank = 1)]
-pub async fn get_one_stats(driver_name: String, origin: &Origin<'_> ) -> Result<DriverStats, Status> {
+/// # get the stats of a driver
+/// the stats of the driver on a track with their consistency and whether their rating is provisional.
+/// when `clean` is true only the clean laps are used for the stats, see `LapClassification`
+#[get("/drivers/<driver_name>?<track>&<clean>", rank = 1)]
+pub async fn get_one_stats(driver_name: String, track: Option<i32>, clean: Option<bool>, cache: ResponseCache) -> CustomResult<Cached<DriverStats>> {
     let sanitized = sanitize_name(&driver_name);
     if sanitized != driver_name {
-        return Err(Status::BadRequest);
+        return Err(Error::InvalidNameError { name: driver_name });
     }
 
-    read_cache_request!(origin);
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
 
-    let driver = Driver::get_driver_with_stats(driver_name).await;
+    let track = Track::get_or_current(track).await?;
+    let mut stats = Driver::get_driver_with_stats(driver_name, track.id, clean.unwrap_or(false)).await?;
+    let driver = Driver::get_by_name(&stats.name).await?;
+    stats.consistency = driver.get_consistency(track.id).await?;
+    stats.provisional = Some(driver.is_provisional().await?);
 
-    cache_response!(origin, driver);
+    Ok(cache.store(stats, &[CacheTag::Driver(driver.id)]))
 }
 
 #[get("/drivers/<driver_name>/full", rank = 1)]
-pub async fn get_one(driver_name: String, origin: &Origin<'_>) -> Result<ApiDriver, Status> {
+pub async fn get_one(driver_name: String, cache: ResponseCache) -> CustomResult<Cached<ApiDriver>> {
     // check if the input is valid
     let sanitized = sanitize_name(&driver_name);
     if sanitized != driver_name {
-        println!("{} != {}", sanitized, driver_name);
-        return Err(Status::BadRequest);
+        return Err(Error::InvalidNameError { name: driver_name });
     }
 
     // check if request is cached.
     // faster to check input then to make a request to the cache
-    read_cache_request!(origin);
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let driver = Driver::get_by_name(&driver_name).await?;
+    let laps = driver.get_laps().await?;
+    let heats = Session::from_laps(&laps).await?;
+    let karts = Vehicle::from_laps(&laps).await?;
+    let offsets = KartOffsets::from_laps(&laps).await?;
+    let conditions = SessionConditions::from_sessions(&heats).await?;
+
+    let api_driver = ApiDriver::new(&driver, &heats, &laps, &karts, &offsets, &conditions);
+    let tags = [vec![CacheTag::Driver(driver.id)], CacheTag::of_heats(&heats), CacheTag::of_karts(&karts)].concat();
+    Ok(cache.store(api_driver, &tags))
+}
+
+#[get("/drivers/<driver_name>/ratings?<system>", rank = 1)]
+pub async fn get_ratings(driver_name: String, system: Option<String>, cache: ResponseCache) -> CustomResult<Cached<Vec<RatingChange>>> {
+    let sanitized = sanitize_name(&driver_name);
+    if sanitized != driver_name {
+        return Err(Error::InvalidNameError { name: driver_name });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let system = match system {
+        Some(system) => get_rating_system(&system).ok_or(Error::InvalidArgumentError { name: "system".to_string(), value: system.clone() })?,
+        None => get_main_rating_system(),
+    };
+
+    let driver = Driver::get_by_name(&driver_name).await?;
+    let ratings = driver.get_rating_history(system.name()).await?;
+
+    Ok(cache.store(ratings, &[CacheTag::Driver(driver.id)]))
+}
 
-    let driver = Driver::get_by_name(&driver_name).await;
-    let laps = driver.get_laps().await;
-    let heats = Session::from_laps(&laps).await;
-    let karts = Vehicle::from_laps(&laps).await;
+/// # get the current ratings of a driver
+/// get the rating of the driver in every rating system, to compare the systems
+#[get("/drivers/<driver_name>/ratings/current", rank = 1)]
+pub async fn get_current_ratings(driver_name: String, cache: ResponseCache) -> CustomResult<Cached<Vec<DriverRating>>> {
+    let sanitized = sanitize_name(&driver_name);
+    if sanitized != driver_name {
+        return Err(Error::InvalidNameError { name: driver_name });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let driver = Driver::get_by_name(&driver_name).await?;
+    let ratings = driver.get_ratings().await?;
 
-    let api_driver = ApiDriver::new(&driver, &heats, &laps, &karts);
-    cache_response!(origin, api_driver.clone());
+    Ok(cache.store(ratings, &[CacheTag::Driver(driver.id)]))
 }
 
 #[get("/drivers/search/full?<q>&<page>&<page_size>")]
-pub async fn search_full(q: String, page: Option<i32>, page_size: Option<i32>) -> Result<String, Status> {
+pub async fn search_full(q: String, page: Option<i32>, page_size: Option<i32>, cache: ResponseCache) -> CustomResult<Cached<Vec<ApiDriver>>> {
     let sanitized = sanitize_name(&q);
     if sanitized != q {
-        return Err(Status::BadRequest);
+        return Err(Error::InvalidNameError { name: q });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
     }
 
     let drivers = Driver::search_by_name(
         &q,
         page.unwrap_or(0),
-        page_size.unwrap_or(500)).await;
+        page_size.unwrap_or(500)).await?;
 
-    let all_laps_map = Lap::from_drivers_as_map(&drivers).await;
+    let all_laps_map = Lap::from_drivers_as_map(&drivers).await?;
 
     let all_laps: Vec<Lap> = all_laps_map
         .iter()
@@ -85,29 +154,30 @@ pub async fn search_full(q: String, page: Option<i32>, page_size: Option<i32>) -
         .map(|e| e.to_owned())
         .collect();
 
-    let all_heats = Session::from_laps(&all_laps).await;
-    let all_karts = Vehicle::from_laps(&all_laps).await;
+    let all_heats = Session::from_laps(&all_laps).await?;
+    let all_karts = Vehicle::from_laps(&all_laps).await?;
+    let offsets = KartOffsets::from_laps(&all_laps).await?;
+    let conditions = SessionConditions::from_sessions(&all_heats).await?;
 
     let api_drivers: Vec<ApiDriver> =
-        ApiDriver::bulk_new(&drivers, &all_laps_map, &all_heats, &all_karts);
-    Ok(serde_json::to_string(&api_drivers).unwrap())
-}
-
-#[get("/drivers/search?<q>&<page>&<page_size>&<sort_col>&<sort_dir>")]
-pub async fn search(
-    q: String,
-    page: Option<u32>,
-    page_size: Option<u32>,
-    sort_col: Option<String>,
-    sort_dir: Option<String>,
-) -> Result<String, Status> {
+        ApiDriver::bulk_new(&drivers, &all_laps_map, &all_heats, &all_karts, &offsets, &conditions);
+    Ok(cache.store(api_drivers, &[CacheTag::Drivers]))
+}
+
+#[get("/drivers/search?<query..>")]
+pub async fn search(query: DriverSearchQuery, cache: ResponseCache) -> CustomResult<Cached<Vec<DriverStats>>> {
+    let q = query.q;
     let sanitized = sanitize_name(&q);
     if sanitized != q {
-        return Err(Status::BadRequest);
+        return Err(Error::InvalidNameError { name: q });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
     }
 
-    let mut sort_col = sort_col.unwrap_or("name".to_string());
-    let mut sort_dir = sort_dir.unwrap_or("asc".to_string());
+    let mut sort_col = query.sort_col.unwrap_or("name".to_string());
+    let mut sort_dir = query.sort_dir.unwrap_or("asc".to_string());
 
     if sort_col.is_empty() {
         sort_col = "name".to_string();
@@ -117,37 +187,190 @@ pub async fn search(
     }
 
 
+    let track = Track::get_or_current(query.track).await?;
     let drivers = Driver::search_with_stats(
         q.clone(),
-        page_size.unwrap_or(10),
-        page.unwrap_or(1),
+        query.page_size.unwrap_or(10),
+        query.page.unwrap_or(1),
         sort_col,
         sort_dir,
-    ).await;
+        track.id,
+        query.clean.unwrap_or(false),
+    ).await?;
+
+    Ok(cache.store(drivers, &[CacheTag::Drivers]))
+}
+
+/// # get a leaderboard
+/// rank the drivers on a track within a time window. `window` is `day`, `week`, `month`, `season`
+/// or `custom`, the first four contain `date` (default today) and custom goes from `from` up to and
+/// including `to`. dates are formatted as `YYYY-MM-DD`. `metric` is `fastest_lap`,
+/// `adjusted_fastest_lap`, `rating` or `laps_driven`. drivers need `min_laps` clean laps in the
+/// window to qualify. drivers with a provisional rating are left off the rating leaderboard,
+/// unless `include_provisional` is true, and are on the others, unless it is false.
+#[get("/drivers/leaderboard?<query..>")]
+pub async fn get_leaderboard(query: LeaderboardQuery, cache: ResponseCache) -> CustomResult<Cached<Leaderboard>> {
+    let window = query.window.unwrap_or("season".to_string());
+    let window = LeaderboardWindow::from_name(&window).ok_or(Error::InvalidArgumentError { name: "window".to_string(), value: window.clone() })?;
+    let metric = query.metric.unwrap_or("fastest_lap".to_string());
+    let metric = LeaderboardMetric::from_name(&metric).ok_or(Error::InvalidArgumentError { name: "metric".to_string(), value: metric.clone() })?;
+
+    let today = Utc::now().date_naive();
+    let date = parse_date("date", query.date, today)?;
+    let days = window.days(date, parse_date("from", query.from, today)?, parse_date("to", query.to, today)?);
+    if days.0 > days.1 {
+        return Err(Error::InvalidArgumentError { name: "window".to_string(), value: format!("{} to {}", days.0, days.1) });
+    }
 
-    let drivers = drivers;
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let track = Track::get_or_current(query.track).await?;
+    let leaderboard = Leaderboard::get(
+        track,
+        window,
+        days,
+        metric,
+        Qualification {
+            min_laps: query.min_laps.unwrap_or(10),
+            include_provisional: query.include_provisional.unwrap_or(metric != LeaderboardMetric::Rating),
+        },
+        query.page.unwrap_or(0),
+        query.page_size.unwrap_or(50),
+    ).await?;
+
+    let tags = [CacheTag::Track(leaderboard.track.id)];
+    Ok(cache.with_ttl(LEADERBOARD_TTL).store(leaderboard, &tags))
+}
+
+/// # compare two drivers
+/// the results of driver `a` against driver `b` in the heats they both drove in
+#[get("/drivers/compare?<a>&<b>")]
+pub async fn compare(a: String, b: String, cache: ResponseCache) -> CustomResult<Cached<HeadToHead>> {
+    for name in [&a, &b] {
+        if sanitize_name(name) != *name {
+            return Err(Error::InvalidNameError { name: name.clone() });
+        }
+    }
+    if a == b {
+        return Err(Error::InvalidArgumentError { name: "b".to_string(), value: b });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let driver = Driver::get_by_name(&a).await?;
+    let opponent = Driver::get_by_name(&b).await?;
+    let laps = Lap::from_drivers_as_map(&[driver.clone(), opponent.clone()]).await?;
+    let driver_laps = laps.get(&driver).cloned().unwrap_or_default();
+    let opponent_laps = laps.get(&opponent).cloned().unwrap_or_default();
+
+    let all_laps: Vec<Lap> = driver_laps.iter().chain(&opponent_laps).cloned().collect();
+    let heats = Session::from_laps(&all_laps).await?;
+    let karts = Vehicle::from_laps(&all_laps).await?;
+
+    let head_to_head = HeadToHead::new(&driver, &opponent, &driver_laps, &opponent_laps, &heats, &karts);
+    Ok(cache.store(head_to_head, &[CacheTag::Driver(driver.id), CacheTag::Driver(opponent.id)]))
+}
+
+/// # predict a heat
+/// predict the finishing order of the drivers with their current ratings. every driver is given
+/// with `names`, as in `?names=a&names=b`.
+#[get("/drivers/predict?<names>&<system>")]
+pub async fn predict(names: Vec<String>, system: Option<String>, cache: ResponseCache) -> CustomResult<Cached<Prediction>> {
+    for name in &names {
+        if sanitize_name(name) != *name {
+            return Err(Error::InvalidNameError { name: name.clone() });
+        }
+    }
+    if names.len() < 2 {
+        return Err(Error::InvalidArgumentError { name: "names".to_string(), value: names.join(",") });
+    }
+
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let system = match system {
+        Some(system) => get_rating_system(&system).ok_or(Error::InvalidArgumentError { name: "system".to_string(), value: system.clone() })?,
+        None => get_main_rating_system(),
+    };
 
-    Ok(serde_json::to_string(&drivers).unwrap())
+    let mut drivers = Vec::new();
+    for name in &names {
+        drivers.push(Driver::get_by_name(name).await?);
+    }
+    let prediction = Prediction::of_drivers(&drivers, system).await?;
+
+    Ok(cache.store(prediction, &CacheTag::of_drivers(&drivers)))
 }
 
-#[get("/drivers/all")]
-pub async fn get_all_ids(origin: &Origin<'_>) -> Result<String, Status> {
-    read_cache_request!(origin);
+#[get("/drivers/all?<track>")]
+pub async fn get_all_ids(track: Option<i32>, cache: ResponseCache) -> CustomResult<Cached<Vec<DriverStats>>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
 
-    let drivers = Driver::get_all_with_stats().await;
-    Ok(serde_json::to_string(&drivers).unwrap())
-    // cache_response!(origin, drivers);
+    let track = Track::get_or_current(track).await?;
+    let drivers = Driver::get_all_with_stats(track.id, false).await?;
+    Ok(cache.store(drivers, &[CacheTag::Drivers]))
 }
 
 /**************************************************************************************************/
 /**************** HELPERS *************************************************************************/
 /**************************************************************************************************/
 
+/// # parse a date from a query parameter
+///
+/// ## Arguments
+/// * `name` - the name of the parameter
+/// * `date` - the date, formatted as `YYYY-MM-DD`
+/// * `default` - the date when none is given
+///
+/// ## Returns
+/// * `NaiveDate` - the date
+fn parse_date(name: &str, date: Option<String>, default: NaiveDate) -> CustomResult<NaiveDate> {
+    match date {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|_| Error::InvalidArgumentError { name: name.to_string(), value: date }),
+        None => Ok(default),
+    }
+}
+
 #[derive(FromForm)]
 pub struct NewHeatFormData {
     pub heat_id: String,
 }
 
+/// # the query parameters of a driver search
+#[derive(FromForm)]
+pub struct DriverSearchQuery {
+    pub q: String,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    pub sort_col: Option<String>,
+    pub sort_dir: Option<String>,
+    pub track: Option<i32>,
+    pub clean: Option<bool>,
+}
+
+/// # the query parameters of a leaderboard
+#[derive(FromForm)]
+pub struct LeaderboardQuery {
+    pub track: Option<i32>,
+    pub window: Option<String>,
+    pub date: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub metric: Option<String>,
+    pub min_laps: Option<i64>,
+    pub include_provisional: Option<bool>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
 /// # Struct representing a json response for a heat
 #[derive(Serialize, Deserialize, Clone, JsonResponse)]
 pub struct ApiDriver {
@@ -166,7 +389,9 @@ impl ApiDriver {
     /// * `laps` - The laps driven in the heat
     /// * `drivers` - The drivers that drove in the heat
     /// * `karts` - The karts that were driven in the heat
-    pub fn new(driver: &Driver, heats: &[Session], laps: &[Lap], karts: &[Vehicle]) -> ApiDriver {
+    /// * `offsets` - The offsets of the karts, used for the kart-adjusted lap times
+    /// * `conditions` - The track conditions, used for the normalized lap times
+    pub fn new(driver: &Driver, heats: &[Session], laps: &[Lap], karts: &[Vehicle], offsets: &KartOffsets, conditions: &SessionConditions) -> ApiDriver {
         ApiDriver {
             name: driver.name.to_string(),
             rating: driver.rating,
@@ -183,6 +408,7 @@ impl ApiDriver {
                     ApiHeat {
                         heat_id: heat.heat_id.to_string(),
                         start_date: heat.start_date,
+                        track: heat.track,
                         kart: ApiKart {
                             number: kart.number.clone(),
                             brand: kart.brand.clone(),
@@ -190,12 +416,17 @@ impl ApiDriver {
                             horsepower: kart.horsepower.clone(),
                             modified: kart.modified.clone(),
                         },
+                        kart_offset: offsets.get(kart.id, heat.start_date),
+                        condition_index: conditions.get(heat.id),
                         laps: laps
                             .iter()
                             .filter(|l| l.heat.eq(&heat.id))
                             .map(|lap| ApiLap {
                                 lap_number: lap.lap_in_heat,
                                 lap_time: lap.lap_time,
+                                adjusted_lap_time: offsets.adjust(lap, heat.start_date),
+                                normalized_lap_time: conditions.normalize(offsets.adjust(lap, heat.start_date), heat.id),
+                                classification: lap.classification,
                             })
                             .collect(),
                     }
@@ -209,6 +440,8 @@ impl ApiDriver {
         all_laps: &HashMap<Driver, Vec<Lap>>,
         all_heats: &[Session],
         all_karts: &[Vehicle],
+        offsets: &KartOffsets,
+        conditions: &SessionConditions,
     ) -> Vec<ApiDriver> {
         drivers
             .iter()
@@ -217,7 +450,7 @@ impl ApiDriver {
                 let heats = Session::from_laps_offline(&all_heats, laps);
                 let karts = Vehicle::from_laps_offline(&all_karts, laps);
 
-                ApiDriver::new(driver, &heats, laps, &karts)
+                ApiDriver::new(driver, &heats, laps, &karts, offsets, conditions)
             })
             .collect()
     }
@@ -227,7 +460,12 @@ impl ApiDriver {
 pub struct ApiHeat {
     pub heat_id: String,
     pub start_date: NaiveDateTime,
+    pub track: i32,
     pub kart: ApiKart,
+    /// the time the kart was slower than the average kart
+    pub kart_offset: f64,
+    /// how much slower the regulars were than usual, `None` when no regulars drove
+    pub condition_index: Option<f64>,
     pub laps: Vec<ApiLap>,
 }
 
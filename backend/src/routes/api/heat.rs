@@ -2,26 +2,30 @@ use std::collections::HashMap;
 
 use chrono::NaiveDateTime;
 use json_response_derive::JsonResponse;
-use log::error;
 use rocket::form::Form;
-use rocket::http::uri::Origin;
-use rocket::http::ContentType;
 use rocket::http::Status;
 use rocket::response;
 use rocket::response::Responder;
-use rocket::response::Response;
 use rocket::serde::Deserialize;
 use rocket::Request;
 use rocket::{get, post, FromForm};
 use serde::Serialize;
 
-use crate::macros::request_caching::{cache_response, read_cache_request};
-use crate::modules::heat_api::{get_heats_from_api, save_heat, WebResponse};
+use crate::errors::{CustomResult, Error};
+use crate::modules::heat_api::{save_heat_on_track, WebResponse};
+use crate::modules::heat_ranking::RankingMode;
+use crate::modules::lap_classification::LapClassification;
+use crate::modules::race_chart::RaceChart;
+use crate::modules::heat_source::get_heat_source;
 use crate::modules::database::models::driver::{sanitize_name, Driver};
 use crate::modules::database::models::session::{Session, HeatStats};
 use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::Lap;
-use crate::modules::redis::Redis;
+use crate::modules::database::models::kart_offset::KartOffsets;
+use crate::modules::database::models::session_condition::SessionConditions;
+use crate::modules::database::models::track::Track;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::response_cache::{Cached, ResponseCache};
 
 /**************************************************************************************************/
 /**************** ROUTES **************************************************************************/
@@ -31,56 +35,93 @@ use crate::modules::redis::Redis;
 
 /// # load a new heat into the db
 #[post("/heats/new", data = "<new_heat>")]
-pub async fn save_one(new_heat: Form<NewHeatFormData>) -> Status {
+pub async fn save_one(new_heat: Form<NewHeatFormData>) -> CustomResult<Status> {
     let sanitized = sanitize_name(&new_heat.heat_id);
     if sanitized != new_heat.heat_id {
-        return Status::BadRequest;
+        return Err(Error::InvalidNameError { name: new_heat.heat_id.clone() });
     }
 
-
-    let heat = new_heat.into_inner().heat_id;
-    let response = get_heats_from_api(vec![heat]).await;
-    if response.len() == 0 {
-        return Status::NotFound;
+    let new_heat = new_heat.into_inner();
+    if let Some(track) = new_heat.track {
+        // make sure the track exists before the heat is fetched
+        Track::get_by_id(track).await?;
     }
 
-    let heat: WebResponse = response[0].clone();
-    save_heat(heat).await.unwrap();
+    // the venue does not answer with a heat for ids it does not know
+    let heat: WebResponse = match get_heat_source().get_heat(&new_heat.heat_id).await {
+        Ok(heat) if !heat.results.is_empty() => heat,
+        Ok(_) | Err(Error::InvalidResponseError { .. }) => {
+            return Err(Error::NotFoundError { what: format!("heat {}", new_heat.heat_id) });
+        }
+        Err(err) => return Err(err),
+    };
+    save_heat_on_track(heat, new_heat.track).await?;
 
-    Status::Ok
+    Ok(Status::Ok)
 }
 
-#[get("/heats/<heat_id>", rank = 1)]
-pub async fn get_one_stats(heat_id: String, origin: &Origin<'_>) -> Result<HeatStats, Status> {
-    read_cache_request!(origin);
+/// # get the stats of a heat
+/// the stats of the heat with the consistency of its drivers.
+/// when `clean` is true only the clean laps are used for the stats, see `LapClassification`
+#[get("/heats/<heat_id>?<clean>", rank = 1)]
+pub async fn get_one_stats(heat_id: String, clean: Option<bool>, cache: ResponseCache) -> CustomResult<Cached<HeatStats>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
 
-    let heat = Session::get_with_stats(heat_id).await;
+    let mut heat_stats = Session::get_with_stats(heat_id.clone(), clean.unwrap_or(false)).await?;
+    let heat = Session::get_by_id(&heat_id).await?;
+    heat_stats.consistency = Some(heat.get_consistency().await?);
 
-    cache_response!(origin, heat);
+    Ok(cache.store(heat_stats, &[CacheTag::Heat(heat.heat_id)]))
 }
 
 /***** GETTERS *****/
 #[get("/heats/<heat_id>/full", rank = 1)]
-pub async fn get_one(heat_id: String, origin: &Origin<'_>) -> Result<ApiHeat, Status> {
-    read_cache_request!(origin);
+pub async fn get_one(heat_id: String, cache: ResponseCache) -> CustomResult<Cached<ApiHeat>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let heat = Session::get_by_id( &heat_id).await?;
+    let laps = heat.get_laps().await?;
+    let karts = Vehicle::from_laps(&laps).await?;
+    let drivers = Driver::from_laps(&laps).await?;
+    let offsets = KartOffsets::from_laps(&laps).await?;
+    let conditions = SessionConditions::from_sessions(std::slice::from_ref(&heat)).await?;
+
+    let tags = [vec![CacheTag::Heat(heat.heat_id.clone())], CacheTag::of_drivers(&drivers), CacheTag::of_karts(&karts)].concat();
+    Ok(cache.store(ApiHeat::new(&heat, &drivers, &laps, &karts, &offsets, &conditions), &tags))
+}
 
-    let heat = Session::get_by_id( &heat_id).await;
-    let laps = heat.get_laps().await;
-    let karts = Vehicle::from_laps(&laps).await;
-    let drivers = Driver::from_laps(&laps).await;
+/// # get the race chart of a heat
+/// the position, gap to the leader and interval to the driver ahead of every driver after
+/// every lap, with the overtakes and lapped drivers
+#[get("/heats/<heat_id>/chart", rank = 1)]
+pub async fn get_chart(heat_id: String, cache: ResponseCache) -> CustomResult<Cached<RaceChart>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let heat = Session::get_by_id(&heat_id).await?;
+    let laps = heat.get_laps().await?;
+    let drivers = Driver::from_laps(&laps).await?;
 
-    cache_response!(origin, ApiHeat::new(&heat, &drivers, &laps, &karts));
+    let tags = [vec![CacheTag::Heat(heat.heat_id.clone())], CacheTag::of_drivers(&drivers)].concat();
+    Ok(cache.store(RaceChart::new(&heat.heat_id, &laps, &drivers), &tags))
 }
 
 /****** SEARSH ROUTES ******/
-#[get("/heats/search?<page>&<page_size>&<sort_col>&<sort_dir>")]
+#[get("/heats/search?<page>&<page_size>&<sort_col>&<sort_dir>&<track>&<clean>")]
 pub async fn search(
     page: Option<i64>,
     page_size: Option<i64>,
     sort_dir: Option<String>,
     sort_col: Option<String>,
-    origin: &Origin<'_>,
-) -> Result<String, Status> {
+    track: Option<i32>,
+    clean: Option<bool>,
+    cache: ResponseCache,
+) -> CustomResult<Cached<Vec<HeatStats>>> {
     let mut sort_col = sort_col.unwrap_or("start".to_string());
     let mut sort_dir = sort_dir.unwrap_or("asc".to_string());
 
@@ -91,21 +132,30 @@ pub async fn search(
         sort_dir = "asc".to_string();
     }
 
-    read_cache_request!(origin);
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
     let search_results = Session::get_all_paginated(
         page.unwrap_or(0),
         page_size.unwrap_or(500),
         sort_dir,
-        sort_col).await;
-    cache_response!(origin, serde_json::to_string(&search_results).unwrap());
+        sort_col,
+        track,
+        clean.unwrap_or(false)).await?;
+    Ok(cache.store(search_results, &[CacheTag::Heats]))
 }
 
 /// # get all heats
-/// get info about all heats.
-#[get("/heats/all")]
-pub async fn get_all_ids() -> Result<String, Status> {
-    let heats = Session::get_all_with_stats().await;
-    Ok(serde_json::to_string(&heats).unwrap())
+/// get info about all heats. when a track is given only the heats driven on it are returned.
+#[get("/heats/all?<track>")]
+pub async fn get_all_ids(track: Option<i32>, cache: ResponseCache) -> CustomResult<Cached<Vec<HeatStats>>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let heats = Session::get_all_with_stats(track, false).await?;
+    Ok(cache.store(heats, &[CacheTag::Heats]))
 }
 
 /**************************************************************************************************/
@@ -115,6 +165,7 @@ pub async fn get_all_ids() -> Result<String, Status> {
 #[derive(FromForm)]
 pub struct NewHeatFormData {
     pub heat_id: String,
+    pub track: Option<i32>,
 }
 
 /// # Struct representing a json response for a heat
@@ -123,6 +174,10 @@ pub struct ApiHeat {
     pub heat_id: String,
     pub heat_type: String,
     pub start_time: NaiveDateTime,
+    pub track: i32,
+    pub ranking: RankingMode,
+    /// how much slower the regulars were than usual, `None` when no regulars drove
+    pub condition_index: Option<f64>,
     pub results: Vec<ApiDriverResult>,
 }
 
@@ -130,21 +185,31 @@ impl ApiHeat {
     /// # Create a object to represent the heat and its driven laps.
     /// we expect that the drivers and laps are for the given heat.
     /// We also expect that a driver has only driven in a single kart.
+    /// the results are in the order the drivers finished, using the ranking mode of the heat type.
     ///
     /// # Arguments
     /// * `heat` - The heat to represent
     /// * `laps` - The laps driven in the heat
     /// * `drivers` - The drivers that drove in the heat
     /// * `karts` - The karts that were driven in the heat
-    pub fn new(heat: &Session, drivers: &[Driver], laps: &[Lap], karts: &[Vehicle]) -> ApiHeat {
+    /// * `offsets` - The offsets of the karts, used for the kart-adjusted lap times
+    /// * `conditions` - The track conditions, used for the normalized lap times
+    pub fn new(heat: &Session, drivers: &[Driver], laps: &[Lap], karts: &[Vehicle], offsets: &KartOffsets, conditions: &SessionConditions) -> ApiHeat {
         ApiHeat {
             heat_id: heat.heat_id.clone(),
             heat_type: heat.heat_type.to_string(),
             start_time: heat.start_date,
+            track: heat.track,
+
+            ranking: heat.ranking_mode(),
+            condition_index: conditions.get(heat.id),
 
-            results: drivers
+            results: heat
+                .ranking_mode()
+                .rank(laps)
                 .iter()
-                .map(|driver| {
+                .filter_map(|ranked| {
+                    let driver = drivers.iter().find(|driver| driver.id == ranked.driver)?;
                     let driver_laps = laps
                         .iter()
                         .filter(|lap| lap.driver == driver.id)
@@ -152,8 +217,10 @@ impl ApiHeat {
                     let kart_id = driver_laps.first().unwrap().kart_id;
                     let kart = karts.iter().find(|kart| kart.id == kart_id).unwrap();
 
-                    ApiDriverResult {
+                    Some(ApiDriverResult {
+                        position: ranked.position,
                         kart: kart.number,
+                        kart_offset: offsets.get(kart.id, heat.start_date),
                         driver: ApiDriver {
                             driver_name: driver.name.to_string(),
                         },
@@ -161,10 +228,13 @@ impl ApiHeat {
                             .iter()
                             .map(|lap| ApiLap {
                                 lap_time: lap.lap_time,
+                                adjusted_lap_time: offsets.adjust(lap, heat.start_date),
+                                normalized_lap_time: conditions.normalize(offsets.adjust(lap, heat.start_date), heat.id),
                                 lap_number: lap.lap_in_heat,
+                                classification: lap.classification,
                             })
                             .collect(),
-                    }
+                    })
                 })
                 .collect(),
         }
@@ -175,6 +245,8 @@ impl ApiHeat {
         all_laps: HashMap<Session, Vec<Lap>>,
         all_drivers: Vec<Driver>,
         all_karts: Vec<Vehicle>,
+        offsets: &KartOffsets,
+        conditions: &SessionConditions,
     ) -> Vec<ApiHeat> {
         all_heats
             .iter()
@@ -185,6 +257,9 @@ impl ApiHeat {
                         heat_id: "".to_string(),
                         heat_type: "".to_string(),
                         start_time: Default::default(),
+                        track: 0,
+                        ranking: RankingMode::FastestLap,
+                        condition_index: None,
                         results: vec![],
                     };
                 }
@@ -194,7 +269,7 @@ impl ApiHeat {
                 let drivers: Vec<Driver> = drivers_laps.iter().map(|(a, _)| a.to_owned()).collect();
                 let karts = Vehicle::from_laps_offline(&all_karts, laps);
 
-                ApiHeat::new(&heat, &drivers, laps, &karts)
+                ApiHeat::new(&heat, &drivers, laps, &karts, offsets, conditions)
             })
             .filter(|e| !e.heat_id.is_empty())
             .collect()
@@ -204,7 +279,10 @@ impl ApiHeat {
 /// # Struct representing a json response for a drivers result in a heat
 #[derive(Serialize, Deserialize)]
 pub struct ApiDriverResult {
+    pub position: usize,
     pub kart: i32,
+    /// the time the kart was slower than the average kart
+    pub kart_offset: f64,
     pub driver: ApiDriver,
     pub laps: Vec<ApiLap>,
 }
@@ -220,4 +298,9 @@ pub struct ApiDriver {
 pub struct ApiLap {
     pub lap_number: i32,
     pub lap_time: f64,
+    /// the lap time without the offset of the kart
+    pub adjusted_lap_time: f64,
+    /// the kart-adjusted lap time in usual track conditions
+    pub normalized_lap_time: f64,
+    pub classification: LapClassification,
 }
@@ -1,7 +1,7 @@
+use crate::errors::CustomResult;
 use crate::modules::database::models::driver::Driver;
 use chrono::NaiveDateTime;
 use rocket::get;
-use rocket::http::Status;
 use rocket::serde::Serialize;
 use std::collections::HashMap;
 
@@ -10,63 +10,52 @@ use crate::modules::database::models::vehicle::Vehicle;
 use crate::modules::database::models::lap::Lap;
 
 use json_response_derive::JsonResponse;
-use log::error;
-use rocket::http::ContentType;
 use rocket::response;
 use rocket::response::Responder;
-use rocket::response::Response;
 use rocket::Request;
 
-use crate::macros::request_caching::{cache_generation, cache_response, read_cache_request};
-use crate::modules::redis::Redis;
-use rocket::http::uri::Origin;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::vehicle::KartStats;
+use crate::modules::response_cache::{Cached, ResponseCache};
 use serde::Deserialize;
 
-#[tokio::main]
-pub async fn get_one_cache_gen(kart_number: i32) -> Result<String, Status> {
-    match Vehicle::get_with_stats(kart_number).await {
-        Some(kart) => return Ok(serde_json::to_string(&kart).unwrap()),
-        None => return Err(Status::NotFound),
-    };
-}
-
+#[get("/karts/<kart_number>?<track>&<clean>")]
+pub async fn get_one(kart_number: i32, track: Option<i32>, clean: Option<bool>, cache: ResponseCache) -> CustomResult<Cached<KartStats>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
 
-#[get("/karts/<kart_number>")]
-pub fn get_one(kart_number: i32, origin: &Origin) -> Result<String, Status> {
-    cache_generation!(origin, KartStats, ||{
-        get_one_cache_gen(kart_number)
-    });
+    let kart = Vehicle::get_with_stats(kart_number, track, clean.unwrap_or(false)).await?;
+    Ok(cache.store(kart, &[CacheTag::Kart(kart_number)]))
 }
 
 #[get("/karts/<kart_number>/full")]
-pub async fn get_one_full(kart_number: i32, origin: &Origin<'_>) -> Result<ApiKartResult, Status> {
-    read_cache_request!(origin);
-
-    let kart: Vehicle = match Vehicle::get_by_number(kart_number).await {
-        Some(kart) => kart,
-        None => {
-            error!(target:"routes/api/kart:get_one_full", "Error getting kart");
-            return Err(Status::InternalServerError);
-        }
-    };
+pub async fn get_one_full(kart_number: i32, cache: ResponseCache) -> CustomResult<Cached<ApiKartResult>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
 
-    let all_laps = Lap::from_kart(&kart).await;
-    let all_heats = Session::from_laps(&all_laps).await;
-    let result = Driver::from_laps(&all_laps).await;
+    let kart: Vehicle = Vehicle::get_by_number(kart_number).await?;
 
-    Ok(ApiKartResult::new(&kart, &all_laps, &result, &all_heats))
-    //TODO:: add caching
+    let all_laps = Lap::from_kart(&kart).await?;
+    let all_heats = Session::from_laps(&all_laps).await?;
+    let result = Driver::from_laps(&all_laps).await?;
 
-    // cache_response!(origin, result);
+    let tags = [vec![CacheTag::Kart(kart.number)], CacheTag::of_heats(&all_heats), CacheTag::of_drivers(&result)].concat();
+    Ok(cache.store(ApiKartResult::new(&kart, &all_laps, &result, &all_heats), &tags))
 }
 
-#[get("/karts/all?<sort_col>&<sort_dir>")]
+#[get("/karts/all?<sort_col>&<sort_dir>&<track>&<clean>")]
 pub async fn get_all(
-    origin: &Origin<'_>,
+    cache: ResponseCache,
     sort_dir: Option<String>,
     sort_col: Option<String>,
-) -> Result<String, Status> {
-    read_cache_request!(origin);
+    track: Option<i32>,
+    clean: Option<bool>,
+) -> CustomResult<Cached<Vec<KartStats>>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
     let mut sort_col = sort_col.unwrap_or("number".to_string());
     let mut sort_dir = sort_dir.unwrap_or("asc".to_string());
 
@@ -77,9 +66,9 @@ pub async fn get_all(
         sort_dir = "asc".to_string();
     }
 
-    let all_karts = Vehicle::get_all_with_stats(sort_col, sort_dir).await;
+    let all_karts = Vehicle::get_all_with_stats(sort_col, sort_dir, track, clean.unwrap_or(false)).await?;
 
-    cache_response!(origin, serde_json::to_string(&all_karts).unwrap());
+    Ok(cache.store(all_karts, &[CacheTag::Karts]))
 }
 
 #[derive(Serialize, Deserialize, JsonResponse)]
@@ -107,6 +96,7 @@ pub struct ApiLapResult {
 pub struct ApiHeatResult {
     heat_id: String,
     start_date: NaiveDateTime,
+    track: i32,
     driver: ApiDriverResult,
     laps: Vec<ApiLapResult>,
 }
@@ -138,6 +128,7 @@ impl ApiKartResult {
                     Some(ApiHeatResult {
                         heat_id: heat.heat_id.clone(),
                         start_date: heat.start_date.clone(),
+                        track: heat.track,
                         driver: ApiDriverResult {
                             name: driver.name.clone(),
                         },
@@ -0,0 +1,34 @@
+use rocket::get;
+
+use crate::errors::{CustomResult, Error};
+use crate::modules::database::models::record::{Record, RecordCategory, Records};
+use crate::modules::database::models::track::Track;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::response_cache::{Cached, ResponseCache};
+
+/// # get the records of a track
+/// get the holder and the previous holders of every record on a track. personal bests are only
+/// included when asked for with `category=personal_best`.
+#[get("/records?<track>&<category>")]
+pub async fn get_of_track(track: Option<i32>, category: Option<String>, cache: ResponseCache) -> CustomResult<Cached<Records>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let categories = match category {
+        Some(category) => vec![RecordCategory::from_name(&category).ok_or(Error::InvalidArgumentError { name: "category".to_string(), value: category.clone() })?],
+        None => vec![
+            RecordCategory::AllTime,
+            RecordCategory::Yearly,
+            RecordCategory::Monthly,
+            RecordCategory::Kart,
+            RecordCategory::HeatType,
+        ],
+    };
+
+    let track = Track::get_or_current(track).await?;
+    let records = Record::of_track(track, &categories).await?;
+
+    let tags = [CacheTag::Track(records.track.id)];
+    Ok(cache.store(records, &tags))
+}
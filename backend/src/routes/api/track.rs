@@ -0,0 +1,21 @@
+use rocket::get;
+
+use crate::errors::CustomResult;
+use crate::modules::cache_tags::CacheTag;
+use crate::modules::database::models::track::Track;
+use crate::modules::response_cache::{Cached, ResponseCache};
+
+/// # get all tracks
+/// get all tracks and their layouts. the ids can be used to filter the other routes by track.
+#[get("/tracks")]
+pub async fn get_all(cache: ResponseCache) -> CustomResult<Cached<Vec<Track>>> {
+    if let Some(cached) = cache.read().await {
+        return Ok(cached);
+    }
+
+    let tracks = Track::get_all().await?;
+
+    // a new track is only added with the heats driven on it
+    let tags = [vec![CacheTag::Heats], CacheTag::of_tracks(&tracks)].concat();
+    Ok(cache.store(tracks, &tags))
+}